@@ -0,0 +1,413 @@
+use crate::commands::graph::{format_duration, parse_duration};
+use crate::commands::query::{check_query, datasource_autocomplete, resolve_datasource};
+use crate::db::alerts::{self, AlertRule, AlertSeverity, AlertStatus, Comparison};
+use crate::db::stat_channels::StatFormat;
+use crate::metrics::{QueryResult, DEFAULT_DATASOURCE};
+use crate::util::truncate_description;
+use crate::{Context, Error};
+use poise::serenity_prelude::{ChannelId, Color, CreateEmbed, RoleId};
+use poise::CreateReply;
+use std::sync::Arc;
+
+const MAX_NAME_LEN: usize = 64;
+
+/// Alerts posted when a PromQL query crosses a threshold.
+#[poise::command(
+    slash_command,
+    subcommands("add", "edit", "remove", "list", "set_channel", "set_role")
+)]
+pub async fn alerts(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+async fn name_autocomplete<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let partial = partial.to_lowercase();
+    let rules = match ctx.guild_id() {
+        Some(guild_id) => alerts::list(&ctx.data().pool, guild_id)
+            .await
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    rules
+        .into_iter()
+        .map(|rule| rule.name)
+        .filter(move |name| name.to_lowercase().contains(&partial))
+        .take(25)
+}
+
+fn parse_for(input: Option<&str>) -> Result<u64, String> {
+    match input {
+        None | Some("0") => Ok(0),
+        Some(value) => parse_duration(value).ok_or_else(|| {
+            format!(
+                "`{}` isn't a valid duration. Use something like `90s`, `5m` or `1h`.",
+                value
+            )
+        }),
+    }
+}
+
+/// How many series an alert query returns, each is checked on its own.
+fn series_count(result: QueryResult) -> Result<usize, String> {
+    result
+        .samples()
+        .map(|samples| samples.len())
+        .ok_or_else(|| "Alert queries have to return a vector or a scalar.".to_string())
+}
+
+/// Adds a rule that alerts when a query crosses a threshold.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+#[allow(clippy::too_many_arguments)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Name of the alert, e.g. High memory"] name: String,
+    #[description = "PromQL query, every series it returns is checked"] query: String,
+    #[description = "When to alert"] comparison: Comparison,
+    #[description = "Value to compare against"] threshold: f64,
+    #[description = "How bad it is (default: warning)"] severity: Option<AlertSeverity>,
+    #[description = "How long it has to hold before alerting, e.g. 5m (default: right away)"]
+    #[rename = "for"]
+    for_: Option<String>,
+    #[description = "How to show values (default: count)"] unit: Option<StatFormat>,
+    #[description = "Datasource to query (default: the default one)"]
+    #[autocomplete = datasource_autocomplete]
+    datasource: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let name = name.trim().to_string();
+
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        ctx.say(format!(
+            "❌ The name has to be 1 to {} characters long.",
+            MAX_NAME_LEN
+        ))
+        .await?;
+        return Ok(());
+    }
+    let for_secs = match parse_for(for_.as_deref()) {
+        Ok(for_secs) => for_secs,
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+    let datasource = match resolve_datasource(ctx, datasource) {
+        Ok(datasource) => datasource,
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+
+    ctx.defer_ephemeral().await?;
+    let series = match check_query(ctx, datasource.as_deref(), &query)
+        .await
+        .and_then(series_count)
+    {
+        Ok(series) => series,
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+
+    let rule = AlertRule {
+        id: 0,
+        name,
+        query,
+        comparison,
+        threshold,
+        for_secs,
+        severity: severity.unwrap_or(AlertSeverity::Warning),
+        format: unit.unwrap_or(StatFormat::Count),
+        datasource,
+    };
+    if !alerts::add(&pool, guild_id, &rule, ctx.author().id).await? {
+        ctx.say(format!(
+            "❌ There's already an alert called `{}`. Use `/alerts edit` to change it.",
+            rule.name
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let mut reply = format!(
+        "✅ Added `{}`. It's checked every minute and currently returns {} series.",
+        rule.name, series
+    );
+    let alerts_channel = ctx
+        .data()
+        .settings
+        .read()
+        .await
+        .get_guild_settings(guild_id)
+        .alerts_channel;
+    if alerts_channel.is_none() {
+        reply.push_str(
+            "\n⚠️ No alerts channel is set, so nothing will be posted until you run `/alerts set_channel`.",
+        );
+    }
+    ctx.say(reply).await?;
+
+    Ok(())
+}
+
+/// Changes an alert rule. Firing alerts resolve once they stop matching.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+#[allow(clippy::too_many_arguments)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "Alert to change"]
+    #[autocomplete = name_autocomplete]
+    name: String,
+    #[description = "New PromQL query"] query: Option<String>,
+    #[description = "New comparison"] comparison: Option<Comparison>,
+    #[description = "New threshold"] threshold: Option<f64>,
+    #[description = "New severity"] severity: Option<AlertSeverity>,
+    #[description = "New time it has to hold before alerting, 0 for right away"]
+    #[rename = "for"]
+    for_: Option<String>,
+    #[description = "New way to show values"] unit: Option<StatFormat>,
+    #[description = "New datasource to query"]
+    #[autocomplete = datasource_autocomplete]
+    datasource: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let Some(mut rule) = alerts::get(&pool, guild_id, &name).await? else {
+        ctx.say(format!("❌ There's no alert called `{}`.", name))
+            .await?;
+        return Ok(());
+    };
+    if let Some(query) = query {
+        rule.query = query;
+    }
+    if let Some(comparison) = comparison {
+        rule.comparison = comparison;
+    }
+    if let Some(threshold) = threshold {
+        rule.threshold = threshold;
+    }
+    if let Some(severity) = severity {
+        rule.severity = severity;
+    }
+    if let Some(unit) = unit {
+        rule.format = unit;
+    }
+    if for_.is_some() {
+        match parse_for(for_.as_deref()) {
+            Ok(for_secs) => rule.for_secs = for_secs,
+            Err(reason) => {
+                ctx.say(format!("❌ {}", reason)).await?;
+                return Ok(());
+            }
+        }
+    }
+    if datasource.is_some() {
+        match resolve_datasource(ctx, datasource) {
+            Ok(datasource) => rule.datasource = datasource,
+            Err(reason) => {
+                ctx.say(format!("❌ {}", reason)).await?;
+                return Ok(());
+            }
+        }
+    }
+
+    ctx.defer_ephemeral().await?;
+    if let Err(reason) = check_query(ctx, rule.datasource.as_deref(), &rule.query)
+        .await
+        .and_then(series_count)
+    {
+        ctx.say(format!("❌ {}", reason)).await?;
+        return Ok(());
+    }
+
+    alerts::update(&pool, &rule).await?;
+    ctx.say(format!(
+        "✅ `{}` now alerts when `{}` is {}.",
+        rule.name,
+        rule.query,
+        describe_condition(&rule)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Deletes an alert rule.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Alert to delete"]
+    #[autocomplete = name_autocomplete]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    if alerts::delete(&pool, guild_id, &name).await? {
+        ctx.say(format!("🗑️ Deleted `{}`.", name)).await?;
+    } else {
+        ctx.say(format!("❌ There's no alert called `{}`.", name))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// The condition with how long it has to hold, e.g. `> 90.0% for 5m`.
+pub(crate) fn describe_condition(rule: &AlertRule) -> String {
+    let mut condition = rule.condition();
+    if rule.for_secs > 0 {
+        condition.push_str(&format!(" for {}", format_duration(rule.for_secs)));
+    }
+    condition
+}
+
+/// Lists the alert rules and what's firing.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let rules = alerts::list(&pool, guild_id).await?;
+    if rules.is_empty() {
+        ctx.say("🔔 No alerts yet. Add one with `/alerts add`.")
+            .await?;
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    for rule in &rules {
+        let states = alerts::states(&pool, rule.id).await?;
+        let firing = states
+            .iter()
+            .filter(|state| state.status == AlertStatus::Firing)
+            .count();
+        let pending = states.len() - firing;
+        let status = match (firing, pending) {
+            (0, 0) => "✅ ok".to_string(),
+            (0, pending) => format!("⏳ {} pending", pending),
+            (firing, 0) => format!("{} {} firing", rule.severity.emoji(), firing),
+            (firing, pending) => format!(
+                "{} {} firing, {} pending",
+                rule.severity.emoji(),
+                firing,
+                pending
+            ),
+        };
+        entries.push(format!(
+            "**{}** — {} ({}, {})\n`{}` {}",
+            rule.name,
+            status,
+            rule.severity.as_str(),
+            rule.datasource.as_deref().unwrap_or(DEFAULT_DATASOURCE),
+            rule.query,
+            describe_condition(rule)
+        ));
+    }
+
+    let settings = ctx
+        .data()
+        .settings
+        .read()
+        .await
+        .get_guild_settings(guild_id);
+    let channel = match settings.alerts_channel {
+        Some(channel) => format!("Posting in <#{}>", channel),
+        None => "No alerts channel set".to_string(),
+    };
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::default()
+                .title("🔔 Alerts")
+                .description(truncate_description(entries.join("\n\n")))
+                .field("Channel", channel, false)
+                .color(Color::from_rgb(255, 255, 255)),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Sets the channel alerts are posted in.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn set_channel(
+    ctx: Context<'_>,
+    #[description = "Channel for alerts, leave empty to stop posting them"]
+    #[channel_types("Text")]
+    channel: Option<ChannelId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        guild_settings.alerts_channel = channel;
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+    }
+
+    match channel {
+        Some(channel) => {
+            ctx.say(format!("✅ Alerts will be posted in <#{}>.", channel))
+                .await?;
+        }
+        None => {
+            ctx.say("Okay, alerts are still checked but won't be posted anywhere.")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets the role pinged when an alert of a severity fires.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn set_role(
+    ctx: Context<'_>,
+    #[description = "Severity to ping for"] severity: AlertSeverity,
+    #[description = "Role to ping, leave empty to stop pinging"] role: Option<RoleId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        match role {
+            Some(role) => guild_settings.alert_roles.insert(severity, role),
+            None => guild_settings.alert_roles.remove(&severity),
+        };
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+    }
+
+    match role {
+        Some(role) => {
+            ctx.say(format!(
+                "✅ <@&{}> will be pinged for {} alerts.",
+                role,
+                severity.as_str()
+            ))
+            .await?;
+        }
+        None => {
+            ctx.say(format!(
+                "Okay, {} alerts won't ping anyone.",
+                severity.as_str()
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
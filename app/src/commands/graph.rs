@@ -0,0 +1,217 @@
+use crate::commands::query::{datasource_autocomplete, resolve_datasource};
+use crate::db::stat_channels::StatFormat;
+use crate::metrics::series_name;
+use crate::util::{truncate, truncate_field};
+use crate::{Context, Error};
+use chrono::Utc;
+use poise::serenity_prelude::{Color, CreateAttachment, CreateEmbed, CreateEmbedFooter};
+use poise::CreateReply;
+
+mod chart;
+
+use chart::Line;
+
+const DEFAULT_RANGE_SECS: u64 = 60 * 60;
+// Points per series when no step is given, about one per two pixels
+const DEFAULT_POINTS: u64 = 500;
+// Prometheus refuses range queries with more points per series than this
+const MAX_POINTS: u64 = 11_000;
+// Longest range, step or offset, so the query window stays within sensible timestamps
+const MAX_DURATION_SECS: u64 = 60 * 60 * 24 * 365;
+const MAX_SERIES: usize = 10;
+const MAX_LEGEND_LEN: usize = 60;
+
+/// Parses a Prometheus-style duration such as `90s`, `6h` or `1h30m` into
+/// seconds.
+pub(crate) fn parse_duration(input: &str) -> Option<u64> {
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in input.trim().chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 60 * 60 * 24,
+            'w' => 60 * 60 * 24 * 7,
+            _ => return None,
+        };
+        total = total.checked_add(number.parse::<u64>().ok()?.checked_mul(unit)?)?;
+        number.clear();
+    }
+    (number.is_empty() && total > 0).then_some(total)
+}
+
+pub(crate) fn format_duration(secs: u64) -> String {
+    [(60 * 60 * 24, "d"), (60 * 60, "h"), (60, "m"), (1, "s")]
+        .iter()
+        .fold((secs, String::new()), |(left, mut out), (unit, suffix)| {
+            if left >= *unit {
+                out.push_str(&format!("{}{}", left / unit, suffix));
+            }
+            (left % unit, out)
+        })
+        .1
+}
+
+/// Draws a chart of a PromQL query over time.
+#[poise::command(slash_command, user_cooldown = 5)]
+pub async fn graph(
+    ctx: Context<'_>,
+    #[description = "PromQL query to chart"] query: String,
+    #[description = "How far back to chart, e.g. 6h or 2d (default: 1h)"] range: Option<String>,
+    #[description = "Time between points, e.g. 1m (default: fits the range)"] step: Option<String>,
+    #[description = "End the chart this long ago, e.g. 12h (default: now)"] ago: Option<String>,
+    #[description = "How to label the y axis (default: count)"] unit: Option<StatFormat>,
+    #[description = "Datasource to query (default: the default one)"]
+    #[autocomplete = datasource_autocomplete]
+    datasource: Option<String>,
+) -> Result<(), Error> {
+    let client = match resolve_datasource(ctx, datasource) {
+        Ok(datasource) => ctx.data().datasources.get(datasource.as_deref()).unwrap(),
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+
+    let parse = |option: &Option<String>, name: &str| match option {
+        Some(value) => parse_duration(value)
+            .filter(|&secs| secs <= MAX_DURATION_SECS)
+            .map(Some)
+            .ok_or_else(|| {
+                format!(
+                    "❌ `{}` isn't a valid {}. Use something like `90s`, `15m`, `6h` or `2d`.",
+                    value, name
+                )
+            }),
+        None => Ok(None),
+    };
+    let durations = (
+        parse(&range, "range"),
+        parse(&step, "step"),
+        parse(&ago, "offset"),
+    );
+    let (range, step, ago) = match durations {
+        (Ok(range), Ok(step), Ok(ago)) => {
+            (range.unwrap_or(DEFAULT_RANGE_SECS), step, ago.unwrap_or(0))
+        }
+        (Err(reason), _, _) | (_, Err(reason), _) | (_, _, Err(reason)) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
+    let step = step.unwrap_or((range / DEFAULT_POINTS).max(1));
+    if range / step > MAX_POINTS {
+        ctx.say(format!(
+            "❌ That's more than {} points per series. Use a bigger step.",
+            MAX_POINTS
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+    let end = Utc::now().timestamp() - ago as i64;
+    let start = end - range as i64;
+    let series = match client.fetch_range(&query, start, end, step).await {
+        Ok(series) => series,
+        Err(e) => {
+            ctx.say(format!("❌ Query failed: {}", e)).await?;
+            return Ok(());
+        }
+    };
+    if series.is_empty() {
+        ctx.say("📉 The query returned no data for that range.")
+            .await?;
+        return Ok(());
+    }
+
+    let lines = series
+        .iter()
+        .take(MAX_SERIES)
+        .map(|s| {
+            let label = match series_name(&s.metric).as_str() {
+                "{}" => query.clone(),
+                name => name.to_string(),
+            };
+            Line {
+                label: truncate(label, MAX_LEGEND_LEN),
+                points: s
+                    .values
+                    .iter()
+                    .filter_map(|v| v.as_f64().filter(|y| y.is_finite()).map(|y| (v.0, y)))
+                    .collect(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let unit = unit.unwrap_or(StatFormat::Count);
+    let png =
+        tokio::task::spawn_blocking(move || chart::render(&lines, start, end, unit)).await??;
+
+    let mut footer = format!(
+        "{} • step {} • {} series",
+        format_duration(range),
+        format_duration(step),
+        series.len()
+    );
+    if series.len() > MAX_SERIES {
+        footer.push_str(&format!(", first {} shown", MAX_SERIES));
+    }
+    if ago > 0 {
+        footer.push_str(&format!(" • ending {} ago", format_duration(ago)));
+    }
+    footer.push_str(" • times in UTC");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::default()
+                    .title("Prometheus Graph")
+                    .field("Query", truncate_field(format!("`{}`", query)), false)
+                    .field("Datasource", format!("`{}`", client.name()), false)
+                    .image("attachment://graph.png")
+                    .footer(CreateEmbedFooter::new(footer))
+                    .color(Color::from_rgb(255, 255, 255)),
+            )
+            .attachment(CreateAttachment::bytes(png, "graph.png")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90s"), Some(90));
+        assert_eq!(parse_duration("15m"), Some(900));
+        assert_eq!(parse_duration("1h30m"), Some(5400));
+        assert_eq!(parse_duration(" 2d "), Some(172_800));
+        assert_eq!(parse_duration("1w"), Some(604_800));
+    }
+
+    #[test]
+    fn rejects_bad_durations() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("5"), None);
+        assert_eq!(parse_duration("0s"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("1.5h"), None);
+        assert_eq!(parse_duration("99999999999999999999s"), None);
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(90), "1m30s");
+        assert_eq!(format_duration(5400), "1h30m");
+        assert_eq!(format_duration(172_800), "2d");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::db::stat_channels::StatFormat;
+use crate::Error;
+use chrono::DateTime;
+use epaint_default_fonts::HACK_REGULAR;
+use plotters::prelude::*;
+use plotters::style::register_font;
+use std::sync::Once;
+use tracing::warn;
+
+const WIDTH: u32 = 1000;
+const HEIGHT: u32 = 500;
+// Discord's dark theme, so the chart blends into the embed
+const BACKGROUND: RGBColor = RGBColor(49, 51, 56);
+const FOREGROUND: RGBColor = RGBColor(219, 222, 225);
+const GRID: RGBColor = RGBColor(78, 80, 88);
+// Above this, x labels also show the date
+const MULTI_DAY_SECS: i64 = 60 * 60 * 36;
+
+static FONT: Once = Once::new();
+
+/// One series on the chart.
+pub struct Line {
+    pub label: String,
+    /// Unix seconds and value.
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Draws the lines from `start` to `end` (unix seconds) and encodes the chart
+/// as a PNG. Blocks, so run it off the async runtime.
+pub fn render(lines: &[Line], start: i64, end: i64, format: StatFormat) -> Result<Vec<u8>, Error> {
+    // The bitmap backend has no system fonts to fall back on
+    FONT.call_once(|| {
+        if register_font("sans-serif", FontStyle::Normal, HACK_REGULAR).is_err() {
+            warn!("Failed to register the chart font");
+        }
+    });
+
+    let (y_min, y_max) = y_bounds(lines);
+    let mut buffer = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&BACKGROUND)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(16)
+            .x_label_area_size(32)
+            .y_label_area_size(80)
+            .build_cartesian_2d(start as f64..end as f64, y_min..y_max)?;
+
+        let time_label = |x: &f64| {
+            let pattern = if end - start > MULTI_DAY_SECS {
+                "%b %d %H:%M"
+            } else {
+                "%H:%M"
+            };
+            DateTime::from_timestamp(*x as i64, 0)
+                .map(|t| t.format(pattern).to_string())
+                .unwrap_or_default()
+        };
+        let value_label = |y: &f64| axis_label(*y, format);
+        chart
+            .configure_mesh()
+            .bold_line_style(GRID)
+            .light_line_style(GRID.mix(0.3))
+            .axis_style(FOREGROUND)
+            .label_style(("sans-serif", 14).into_font().color(&FOREGROUND))
+            .x_labels(8)
+            .y_labels(8)
+            .x_label_formatter(&time_label)
+            .y_label_formatter(&value_label)
+            .draw()?;
+
+        for (i, line) in lines.iter().enumerate() {
+            let style = Palette99::pick(i).stroke_width(2);
+            chart
+                .draw_series(LineSeries::new(line.points.iter().copied(), style))?
+                .label(&line.label)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 16, y)], style));
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(BACKGROUND.mix(0.85))
+            .border_style(GRID)
+            .label_font(("sans-serif", 13).into_font().color(&FOREGROUND))
+            .draw()?;
+
+        root.present()?;
+    }
+
+    let mut png = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png, WIDTH, HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&buffer)?;
+    }
+    Ok(png)
+}
+
+/// Spans every point, starting at zero unless something is negative.
+fn y_bounds(lines: &[Line]) -> (f64, f64) {
+    let values = lines.iter().flat_map(|l| l.points.iter().map(|p| p.1));
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    });
+    if !min.is_finite() || !max.is_finite() {
+        return (0.0, 1.0);
+    }
+
+    let min = min.min(0.0);
+    // Leave some headroom so the top line isn't drawn on the border
+    let max = if max > min {
+        max + (max - min) * 0.05
+    } else {
+        min + 1.0
+    };
+    (min, max)
+}
+
+fn axis_label(value: f64, format: StatFormat) -> String {
+    match format {
+        StatFormat::Count => {
+            let abs = value.abs();
+            if abs >= 1e9 {
+                format!("{:.1}G", value / 1e9)
+            } else if abs >= 1e6 {
+                format!("{:.1}M", value / 1e6)
+            } else if abs >= 1e3 {
+                format!("{:.1}k", value / 1e3)
+            } else if abs >= 10.0 || value.fract() == 0.0 {
+                format!("{:.0}", value)
+            } else {
+                format!("{:.2}", value)
+            }
+        }
+        _ => format.format(value),
+    }
+}
@@ -1,5 +1,15 @@
-use crate::metrics::MetricsClient;
-use crate::settings::LoraxState;
+use crate::db::lorax_history::{self, LoraxOutcome};
+use crate::db::lorax_names::{self, NameConflict, NameRule};
+use crate::db::lorax_schedule::{self, ScheduledEvent};
+use crate::db::lorax_stats;
+use crate::db::lorax_templates;
+use crate::db::nodes;
+use crate::settings::{
+    GuildSettings, LoraxEligibility, LoraxEventConfig, LoraxLocale, LoraxMessage, LoraxRound,
+    LoraxState, LoraxTimeline, Submission, VotingMethod, DEFAULT_TIEBREAKER_ROUNDS,
+};
+use crate::util::{truncate, truncate_description, truncate_field};
+use crate::voting::{self, RunoffResult};
 use crate::{Context, Data, Error};
 use chrono::Utc;
 use poise::serenity_prelude::{
@@ -9,23 +19,47 @@ use poise::serenity_prelude::{
     CreateSelectMenuOption, RoleId,
 };
 use poise::serenity_prelude::{futures::future::BoxFuture, FutureExt};
-use poise::CreateReply;
+use poise::{ChoiceParameter, CreateReply};
 use rand::rngs::{OsRng, StdRng};
-use rand::{SeedableRng, Rng};
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tracing::{debug, info, warn};
 use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, error, info, warn};
+
+mod analytics;
+mod announcement;
+mod campaign;
+mod copy;
+mod export;
+
+use export::{EventExport, ExportFormat};
+
+/// Events moving on to their next phase, see `start_voting` and
+/// `announce_winner`.
+static ADVANCING: Mutex<BTreeSet<(serenity::GuildId, u64)>> = Mutex::new(BTreeSet::new());
+
+/// Locations whose event is being announced, see `open_submissions`.
+static OPENING_LOCATIONS: Mutex<BTreeSet<(serenity::GuildId, String)>> =
+    Mutex::new(BTreeSet::new());
 
 /// Main command for Lorax events, with subcommands for managing the events.
 #[poise::command(
     slash_command,
     subcommands(
         "set_role",
+        "set_winner_role",
         "set_channel",
+        "set_campaign_forum",
+        "set_reminders",
+        "set_eligibility",
+        "set_locale",
+        "messages",
         "start",
         "submit",
+        "statement",
+        "withdraw",
         "vote",
         "list",
         "cancel",
@@ -33,6 +67,15 @@ use rand::seq::SliceRandom;
         "status",
         "remove",
         "force_end",
+        "history",
+        "export",
+        "audit",
+        "void_ballot",
+        "leaderboard",
+        "me",
+        "schedule",
+        "template",
+        "names",
     )
 )]
 pub async fn lorax(_ctx: Context<'_>) -> Result<(), Error> {
@@ -65,6 +108,42 @@ pub async fn set_channel(
     Ok(())
 }
 
+/// Sets the forum for events that campaign in a post per name.
+#[poise::command(slash_command, required_permissions = "MANAGE_CHANNELS")]
+pub async fn set_campaign_forum(
+    ctx: Context<'_>,
+    #[description = "Forum for campaign posts, leave empty to stop using one"]
+    #[channel_types("Forum")]
+    forum: Option<ChannelId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        guild_settings.lorax_campaign_forum = forum;
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+    }
+
+    match forum {
+        Some(forum) => {
+            ctx.say(format!(
+                "Got it! Events with campaign posts turned on will get a post per name in <#{}>. Turn them on with a template, e.g. `/lorax template create name:default campaign_forum:True`. 💬",
+                forum
+            ))
+            .await?;
+        }
+        None => {
+            ctx.say("Okay, every event will campaign in a single thread again.")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Sets the role to ping for Lorax events.
 #[poise::command(slash_command, required_permissions = "MANAGE_ROLES")]
 pub async fn set_role(
@@ -91,235 +170,1747 @@ pub async fn set_role(
     Ok(())
 }
 
-/// Starts a new Lorax event.
+/// Sets the role given to the top namers on the Lorax leaderboard.
+///
+/// The role follows the leaderboard, it's taken back from anyone who drops out
+/// of the top namers.
+#[poise::command(slash_command, required_permissions = "MANAGE_ROLES")]
+pub async fn set_winner_role(
+    ctx: Context<'_>,
+    #[description = "Role for the top namers, leave empty to stop giving one"]
+    role: Option<RoleId>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    ctx.defer().await?;
+
+    let previous = {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        let previous = guild_settings.lorax_winner_role;
+        guild_settings.lorax_winner_role = role;
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+        previous
+    };
+
+    // Whoever had the old role loses it, then the new one is handed out
+    if previous != role {
+        if let Some(previous) = previous {
+            for user_id in lorax_stats::role_holders(&pool, guild_id).await? {
+                let reason = Some("Lorax winner role changed");
+                if let Err(e) = ctx
+                    .http()
+                    .remove_member_role(guild_id, user_id, previous, reason)
+                    .await
+                {
+                    warn!(
+                        "Failed to take Lorax winner role from {} in guild {}: {}",
+                        user_id, guild_id, e
+                    );
+                }
+            }
+        }
+        lorax_stats::set_role_holders(&pool, guild_id, &[]).await?;
+    }
+    update_winner_role(ctx.http(), &pool, guild_id, role).await;
+
+    match role {
+        Some(role) => {
+            ctx.say(format!(
+                "Nice! The top {} namers on the leaderboard will get the <@&{}> role. 🏆",
+                TOP_NAMERS, role
+            ))
+            .await?;
+        }
+        None => {
+            ctx.say("Okay, winners won't get a role anymore.").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets the language of the Lorax announcements.
 #[poise::command(slash_command, required_permissions = "MANAGE_GUILD")]
-pub async fn start(
+pub async fn set_locale(
     ctx: Context<'_>,
-    #[description = "Location of the new node (e.g., 'US-East', 'EU-West')"] location: String,
-    #[description = "Submission duration in minutes"] submission_duration: Option<u64>,
-    #[description = "Voting duration in minutes"] voting_duration: Option<u64>,
-    #[description = "Tiebreaker duration in minutes"] tiebreaker_duration: Option<u64>,
+    #[description = "Language for announcements"] locale: LoraxLocale,
 ) -> Result<(), Error> {
-    let submission_duration = submission_duration.unwrap_or(60);
-    let voting_duration = voting_duration.unwrap_or(30);
-    let tiebreaker_duration = tiebreaker_duration.unwrap_or(15);
     let guild_id = ctx.guild_id().unwrap();
-    let data = ctx.data().clone();
+    let pool = Arc::clone(&ctx.data().pool);
 
-    let mut settings = data.settings.write().await;
-    let pool = data.pool;
-    let guild_settings = settings.get_guild_settings(guild_id);
+    let custom = {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        guild_settings.lorax_locale = locale;
+        let custom = guild_settings.lorax_messages.len();
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+        custom
+    };
 
-    if guild_settings.lorax_channel.is_none() || guild_settings.lorax_role.is_none() {
-        ctx.say("Hold on! Please set the Lorax channel and role before starting an event. Use `/lorax set_channel` and `/lorax set_role`.").await?;
-        return Ok(());
+    let mut reply = format!("🌍 Lorax announcements will now be in {}.", locale.name());
+    if custom > 0 {
+        reply.push_str(&format!(
+            " {} message(s) use your own copy and stay as they are, see `/lorax messages show`.",
+            custom
+        ));
     }
+    ctx.say(reply).await?;
 
-    if let LoraxState::Idle = guild_settings.lorax_state {
-        let end_time = Utc::now().timestamp() + (submission_duration * 60) as i64;
+    Ok(())
+}
 
-        let role_id = guild_settings.lorax_role.unwrap();
-        let announcement = format!(
-            "Hey <@&{}>! We're launching a new node in **{}**, and we need your help to name it! 🌳\n\n\
-            Got any cool tree names in mind? Submit your idea using `/lorax submit`! Just make sure it's all lowercase letters, like `oak` or `willow`.\n\n\
-            You've got until {} to send in your suggestions. Good luck!",
-            role_id,
-            location,
-            discord_timestamp(end_time, TimestampStyle::ShortDateTime)
-        );
+/// Sets when reminders go out before submissions and voting close.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD")]
+pub async fn set_reminders(
+    ctx: Context<'_>,
+    #[description = "Minutes before a phase ends, comma separated (e.g. '60, 10'), or 'off'"]
+    minutes: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
 
-        let channel_id = guild_settings.lorax_channel.unwrap();
-        let announcement_msg = channel_id.say(&ctx, announcement).await?;
+    let reminders = if minutes.trim().eq_ignore_ascii_case("off") {
+        Vec::new()
+    } else {
+        let parsed: Result<Vec<u64>, _> = minutes
+            .split(',')
+            .map(|m| m.trim().parse::<u64>())
+            .collect();
+        match parsed {
+            Ok(mut reminders) if reminders.iter().all(|&m| m > 0) => {
+                reminders.sort_unstable_by(|a, b| b.cmp(a));
+                reminders.dedup();
+                reminders
+            }
+            _ => {
+                ctx.say("❌ Please give a comma separated list of minutes, like `60, 10`, or `off`.")
+                    .await?;
+                return Ok(());
+            }
+        }
+    };
 
-        settings.guilds.get_mut(&guild_id).unwrap().lorax_state = LoraxState::Submissions {
-            end_time,
-            message_id: announcement_msg.id,
-            submissions: HashMap::new(),
-            location,
-            voting_duration,
-            tiebreaker_duration,
-        };
+    {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        guild_settings.lorax_reminders = Some(reminders.clone());
+        settings.set_guild_settings(guild_id, guild_settings);
         settings.save(&pool).await?;
+    }
 
-        ctx.say("🎉 Lorax event started! Submissions are now open.")
+    if reminders.is_empty() {
+        ctx.say("Okay, I won't send any reminders before Lorax phases end. 🔕")
             .await?;
     } else {
-        ctx.say("⚠️ A Lorax event is already in progress.").await?;
+        ctx.say(format!(
+            "Got it! I'll send reminders {} before submissions and voting close. ⏰",
+            reminders
+                .iter()
+                .map(|&m| format_minutes(m))
+                .collect::<Vec<_>>()
+                .join(" and ")
+        ))
+        .await?;
     }
 
     Ok(())
 }
 
-pub async fn start_voting(
-    ctx: &serenity::Context,
-    data: &Data,
-    guild_id: serenity::GuildId,
+/// Sets who can submit names and vote in Lorax events.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD")]
+pub async fn set_eligibility(
+    ctx: Context<'_>,
+    #[description = "Minimum Discord account age in days (0 for no minimum)"]
+    account_age_days: Option<u64>,
+    #[description = "Minimum days since joining this server (0 for no minimum)"]
+    member_age_days: Option<u64>,
+    #[description = "Role needed to take part"] required_role: Option<RoleId>,
+    #[description = "Stop requiring a role"] clear_role: Option<bool>,
 ) -> Result<(), Error> {
-    let mut settings = data.settings.write().await;
-    let pool = Arc::clone(&data.pool);
-    let state = &settings.guilds.get(&guild_id).unwrap().lorax_state;
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
 
-    let channel_id = settings
-        .guilds
-        .get(&guild_id)
-        .unwrap()
-        .lorax_channel
-        .unwrap();
-    let role_id = settings.guilds.get(&guild_id).unwrap().lorax_role.unwrap();
+    let rules = {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        let rules = &mut guild_settings.lorax_eligibility;
+        if let Some(days) = account_age_days {
+            rules.min_account_age_days = days;
+        }
+        if let Some(days) = member_age_days {
+            rules.min_member_age_days = days;
+        }
+        if clear_role.unwrap_or(false) {
+            rules.required_role = None;
+        }
+        if let Some(role) = required_role {
+            rules.required_role = Some(role);
+        }
+        let rules = rules.clone();
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+        rules
+    };
 
-    if let LoraxState::Submissions {
-        submissions,
-        location,
+    if rules.is_unrestricted() {
+        ctx.say("Okay, anyone in the server can submit names and vote in Lorax events. 🌍")
+            .await?;
+        return Ok(());
+    }
+
+    let mut requirements = Vec::new();
+    if rules.min_account_age_days > 0 {
+        requirements.push(format!(
+            "• a Discord account at least {} old",
+            format_days(rules.min_account_age_days)
+        ));
+    }
+    if rules.min_member_age_days > 0 {
+        requirements.push(format!(
+            "• been in the server for at least {}",
+            format_days(rules.min_member_age_days)
+        ));
+    }
+    if let Some(role) = rules.required_role {
+        requirements.push(format!("• the <@&{}> role", role));
+    }
+    ctx.say(format!(
+        "Got it! To submit names and vote in Lorax events, members now need:\n{} 🛡️",
+        requirements.join("\n")
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Starts a new Lorax event.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD")]
+pub async fn start(
+    ctx: Context<'_>,
+    #[description = "Location of the new node (e.g., 'US-East', 'EU-West')"] location: String,
+    #[description = "Submission duration in minutes"] submission_duration: Option<u64>,
+    #[description = "Voting duration in minutes"] voting_duration: Option<u64>,
+    #[description = "Tiebreaker duration in minutes"] tiebreaker_duration: Option<u64>,
+    #[description = "How votes are cast and counted (default: single choice)"]
+    voting_method: Option<VotingMethod>,
+    #[description = "Tiebreaker rounds before the winner is drawn at random (default: 3)"]
+    tiebreaker_rounds: Option<u32>,
+    #[description = "Hide the running results until voting closes (default: true)"]
+    blind_voting: Option<bool>,
+    #[description = "Names each person can submit (default: 1)"]
+    #[min = 1]
+    max_submissions: Option<u32>,
+    #[description = "Template to start from, the 'default' template is used if there is one"]
+    #[autocomplete = template_autocomplete]
+    template: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let Some(mut config) = template_config(ctx, template.as_deref()).await? else {
+        return Ok(());
+    };
+    ConfigOptions {
+        submission_duration,
         voting_duration,
         tiebreaker_duration,
-        ..
-    } = state
-    {
-        if submissions.is_empty() {
-            channel_id
-                .say(
-                    ctx,
-                    "🌳 Hmm, looks like we didn't get any submissions this time. :(",
-                )
+        voting_method,
+        tiebreaker_rounds,
+        blind_voting,
+        max_submissions,
+        ..Default::default()
+    }
+    .apply(&mut config);
+
+    match open_submissions(ctx.http(), ctx.data(), guild_id, location, &config).await? {
+        OpenOutcome::Opened(event_id) => {
+            ctx.say(format!(
+                "🎉 Lorax event `#{}` started! Submissions are now open.",
+                event_id
+            ))
+            .await?;
+        }
+        OpenOutcome::NotConfigured => {
+            ctx.say("Hold on! Please set the Lorax channel and role before starting an event. Use `/lorax set_channel` and `/lorax set_role`.").await?;
+        }
+        OpenOutcome::AlreadyRunning => {
+            ctx.say("⚠️ A Lorax event for that location is already in progress.")
                 .await?;
-            settings.guilds.get_mut(&guild_id).unwrap().lorax_state = LoraxState::Idle;
-            settings.save(&pool).await?;
-            return Ok(());
         }
+    }
 
-        // Use voting_duration instead of hardcoded value
-        let end_time = Utc::now().timestamp() + (voting_duration * 60) as i64;
-        let options = submissions.values().cloned().collect::<Vec<_>>();
-        let submission_count = options.len();
-
-        let announcement = format!(
-            "Hey <@&{}>! It's voting time for our new **{}** node! 🗳️\n\n\
-            We've got {} awesome name suggestions. Use `/lorax vote` to pick your favorite!\n\n\
-            Voting ends {}.",
-            role_id,
-            location,
-            submission_count,
-            discord_timestamp(end_time, TimestampStyle::ShortDateTime)
-        );
-
-        let announcement_msg = channel_id.say(ctx, announcement).await?;
+    Ok(())
+}
 
-        // Create campaign thread
-        let thread = channel_id.create_thread(
-            ctx,
-            serenity::CreateThread::new("🗳️ Tree Name Campaign Thread")
-                .kind(serenity::ChannelType::PublicThread)
-                .auto_archive_duration(serenity::AutoArchiveDuration::OneDay)
-        )
-        .await?;
+pub enum OpenOutcome {
+    /// With the new event's ID.
+    Opened(u64),
+    NotConfigured,
+    /// There's already an event running for the location.
+    AlreadyRunning,
+}
 
-        // Send initial thread message
-        thread.id.send_message(
-            ctx, 
-            serenity::CreateMessage::new()
-                .content("🌳 Welcome to the campaign thread! This is where submitters can advocate for their tree names and voters can discuss the options.")
-        ).await?;
+/// Announces a new event and opens submissions. Used by `/lorax start` and by
+/// the scheduler for queued events.
+pub async fn open_submissions(
+    http: &serenity::Http,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    location: String,
+    config: &LoraxEventConfig,
+) -> Result<OpenOutcome, Error> {
+    // The lock isn't held while announcing, so keep a second start for the
+    // same location out until this one is in the settings
+    let key = (guild_id, location.to_lowercase());
+    if !OPENING_LOCATIONS.lock().unwrap().insert(key.clone()) {
+        return Ok(OpenOutcome::AlreadyRunning);
+    }
+    let result = announce_event(http, data, guild_id, location, config).await;
+    OPENING_LOCATIONS.lock().unwrap().remove(&key);
+    result
+}
 
-        let representatives = {
-            let mut rng = StdRng::from_rng(OsRng)?;
-            let mut submitter_list: Vec<_> = submissions.iter().collect();
-            submitter_list.shuffle(&mut rng);
-            submitter_list.into_iter().take(5).collect::<Vec<_>>()
-        };
+async fn announce_event(
+    http: &serenity::Http,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    location: String,
+    config: &LoraxEventConfig,
+) -> Result<OpenOutcome, Error> {
+    let pool = Arc::clone(&data.pool);
+    let guild_settings = data.settings.read().await.get_guild_settings(guild_id);
 
-        // Send initial thread message with random representatives
-        let reps = futures::future::join_all(representatives.iter().map(|(user_id, tree)| async move {
-            let _name = get_submitter_name(ctx, **user_id).await;
-            format!("🗣️ <@{}> representing `{}`", user_id, tree)
-        })).await.join("\n");
+    let (Some(channel_id), Some(role_id)) = (guild_settings.lorax_channel, guild_settings.lorax_role)
+    else {
+        return Ok(OpenOutcome::NotConfigured);
+    };
 
-        let campaign_msg = if !representatives.is_empty() {
-            format!(
-                "🌳 Welcome to the Tree Name Campaign Thread!\n\n\
-                Here, submitters can advocate for their tree names and voters can discuss the options.\n\n\
-                Some of our candidates speaking today:\n{}\n\n\
-                May the best tree win! 🎉",
-                reps
-            )
-        } else {
-            "🌳 Welcome to the Tree Name Campaign Thread! This is where submitters can advocate for their tree names and voters can discuss the options.".to_string()
-        };
+    if guild_settings
+        .lorax_events
+        .values()
+        .any(|event| event.location().is_some_and(|l| l.eq_ignore_ascii_case(&location)))
+    {
+        return Ok(OpenOutcome::AlreadyRunning);
+    }
 
-        thread.id.send_message(
-            ctx, 
-            serenity::CreateMessage::new().content(campaign_msg)
-        ).await?;
+    let started_at = Utc::now().timestamp();
+    let end_time = started_at + (config.submission_duration * 60) as i64;
+
+    let role = format!("<@&{}>", role_id);
+    let end = discord_timestamp(end_time, TimestampStyle::Relative);
+    let values = [
+        ("role", role.as_str()),
+        ("location", location.as_str()),
+        ("end", end.as_str()),
+    ];
+    let announcement = match &config.announcement {
+        Some(text) => copy::render(text, &values),
+        None => copy::message(&guild_settings, LoraxMessage::SubmissionsOpen, &values),
+    };
 
-        settings.guilds.get_mut(&guild_id).unwrap().lorax_state = LoraxState::Voting {
-            end_time,
-            message_id: announcement_msg.id,
-            thread_id: Some(thread.id),  // Store thread ID
-            options,
-            votes: HashMap::new(),
+    // The event exists before anyone hears about it
+    let event_id = lorax_history::open_event(&pool, guild_id, &location, started_at).await?;
+    let announcement_msg = match channel_id.say(http, announcement).await {
+        Ok(message) => message,
+        Err(e) => {
+            if let Err(e) = lorax_history::discard_event(&pool, event_id).await {
+                error!("Failed to discard unannounced Lorax event {}: {}", event_id, e);
+            }
+            return Err(e.into());
+        }
+    };
+    let event_id = event_id as u64;
 
-            submissions: submissions.clone(),
-            location: location.clone(),
-            tiebreaker_duration: *tiebreaker_duration,
-        };
+    let state = LoraxState::Submissions {
+        end_time,
+        message_id: announcement_msg.id,
+        submissions: Vec::new(),
+        location,
+        voting_duration: config.voting_duration,
+        tiebreaker_duration: config.tiebreaker_duration,
+        timeline: LoraxTimeline {
+            started_at,
+            ..Default::default()
+        },
+        voting_method: config.voting_method,
+        max_tiebreaker_rounds: config.max_tiebreaker_rounds,
+        reminders_sent: reminders_to_skip(
+            guild_settings.lorax_reminders(),
+            config.submission_duration,
+        ),
+        blind_voting: config.blind_voting,
+        max_submissions_per_user: config.max_submissions_per_user,
+        eligibility: config.eligibility.clone(),
+        campaign_thread: config.campaign_thread,
+        campaign_forum: config.campaign_forum,
+    };
+    {
+        let mut settings = data.settings.write().await;
+        settings
+            .guilds
+            .entry(guild_id)
+            .or_default()
+            .lorax_events
+            .insert(event_id, state.clone());
         settings.save(&pool).await?;
     }
+    announcement::update(http, channel_id, event_id, &state, false, guild_settings.lorax_locale)
+        .await;
 
-    Ok(())
+    Ok(OpenOutcome::Opened(event_id))
 }
 
-pub fn start_tiebreaker(
-    http: Arc<serenity::Http>,
-    data: Data,
+/// Redraws an event's announcement for its current phase.
+pub async fn refresh_announcement(
+    http: &serenity::Http,
+    data: &Data,
     guild_id: serenity::GuildId,
-    tied_options: Vec<(usize, String)>,
-    location: String,
-    round: u32,
-    tiebreaker_duration: u64,
-) -> BoxFuture<'static, Result<(), Error>> {
-    async move {
-        let mut settings = data.settings.write().await;
-        let pool = Arc::clone(&data.pool);
-        let guild = settings.guilds.get_mut(&guild_id).unwrap();
+    event_id: u64,
+) {
+    announcement::refresh(http, data, guild_id, event_id).await;
+}
 
-        let channel_id = guild.lorax_channel.unwrap();
-        let role_id = guild.lorax_role.unwrap();
+/// Posts the heads-up for a scheduled event that's about to open.
+pub async fn announce_scheduled_event(
+    http: &serenity::Http,
+    data: &Data,
+    event: &ScheduledEvent,
+) -> Result<(), Error> {
+    let guild_settings = data.settings.read().await.get_guild_settings(event.guild_id);
+    let (Some(channel_id), Some(role_id)) = (guild_settings.lorax_channel, guild_settings.lorax_role)
+    else {
+        return Ok(());
+    };
 
-        let end_time = Utc::now().timestamp() + (tiebreaker_duration * 60) as i64;
-        
-        // Clone tied_options before consuming it
-        let tied_options_clone = tied_options.clone();
-        let options: Vec<String> = tied_options.into_iter().map(|(_, name)| name).collect();
+    let teaser = copy::message(
+        &guild_settings,
+        LoraxMessage::Teaser,
+        &[
+            ("role", &format!("<@&{}>", role_id)),
+            ("location", &event.location),
+            (
+                "start",
+                &discord_timestamp(event.start_time, TimestampStyle::Relative),
+            ),
+        ],
+    );
+    channel_id.say(http, teaser).await?;
+
+    Ok(())
+}
+
+/// Manages Lorax events queued to open later.
+#[poise::command(
+    slash_command,
+    subcommands("schedule_add", "schedule_list", "schedule_edit", "schedule_cancel"),
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn schedule(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
 
-        let submissions = match &guild.lorax_state {
-            LoraxState::Voting { submissions, .. } => submissions.clone(),
-            _ => HashMap::new(),
+/// Queues a Lorax event to open automatically.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, rename = "add", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn schedule_add(
+    ctx: Context<'_>,
+    #[description = "Location of the new node (e.g., 'US-East', 'EU-West')"] location: String,
+    #[description = "When submissions open: 'YYYY-MM-DD HH:MM' (UTC), a unix or Discord timestamp"]
+    start_time: String,
+    #[description = "Submission duration in minutes"] submission_duration: Option<u64>,
+    #[description = "Voting duration in minutes"] voting_duration: Option<u64>,
+    #[description = "Tiebreaker duration in minutes"] tiebreaker_duration: Option<u64>,
+    #[description = "How votes are cast and counted (default: single choice)"]
+    voting_method: Option<VotingMethod>,
+    #[description = "Tiebreaker rounds before the winner is drawn at random (default: 3)"]
+    tiebreaker_rounds: Option<u32>,
+    #[description = "Hide the running results until voting closes (default: true)"]
+    blind_voting: Option<bool>,
+    #[description = "Names each person can submit (default: 1)"]
+    #[min = 1]
+    max_submissions: Option<u32>,
+    #[description = "Template to start from, the 'default' template is used if there is one"]
+    #[autocomplete = template_autocomplete]
+    template: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let Some(start_time) = parse_time(&start_time).filter(|t| *t > Utc::now().timestamp()) else {
+        ctx.say("❌ I couldn't read that start time, or it's in the past. Try something like `2024-12-01 18:00` (UTC).").await?;
+        return Ok(());
+    };
+
+    let Some(mut config) = template_config(ctx, template.as_deref()).await? else {
+        return Ok(());
+    };
+    ConfigOptions {
+        submission_duration,
+        voting_duration,
+        tiebreaker_duration,
+        voting_method,
+        tiebreaker_rounds,
+        blind_voting,
+        max_submissions,
+        ..Default::default()
+    }
+    .apply(&mut config);
+
+    let id = lorax_schedule::insert(&pool, guild_id, &location, start_time, &config).await?;
+
+    ctx.say(format!(
+        "📅 Scheduled event `#{}` for **{}**! Submissions open {} ({}).",
+        id,
+        location,
+        discord_timestamp(start_time, TimestampStyle::ShortDateTime),
+        discord_timestamp(start_time, TimestampStyle::Relative),
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the queued Lorax events.
+#[poise::command(slash_command, rename = "list", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn schedule_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let events = lorax_schedule::list(&pool, guild_id).await?;
+
+    if events.is_empty() {
+        ctx.say("🌱 No Lorax events are scheduled.").await?;
+        return Ok(());
+    }
+
+    let event_list = events
+        .iter()
+        .map(|e| {
+            let mut line = format!(
+                "`#{}` **{}** — opens {} ({})\n　Submissions {}m • Voting {}m • Tiebreakers {}m • {}{}",
+                e.id,
+                e.location,
+                discord_timestamp(e.start_time, TimestampStyle::ShortDateTime),
+                discord_timestamp(e.start_time, TimestampStyle::Relative),
+                e.config.submission_duration,
+                e.config.voting_duration,
+                e.config.tiebreaker_duration,
+                e.config.voting_method.name(),
+                if e.config.blind_voting { " • Blind" } else { "" },
+            );
+            if let (Some(retry_at), Some(error)) = (e.retry_at, &e.last_error) {
+                line.push_str(&format!(
+                    "\n　⚠️ Failed to open {} time(s), retrying {}: {}",
+                    e.failures,
+                    discord_timestamp(retry_at, TimestampStyle::Relative),
+                    truncate(error.clone(), 200),
+                ));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::default()
+                    .title("📅 Scheduled Lorax Events")
+                    .description(event_list)
+                    .color(Color::from_rgb(67, 160, 71)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Changes a queued Lorax event.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, rename = "edit", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn schedule_edit(
+    ctx: Context<'_>,
+    #[description = "ID of the scheduled event"] id: i64,
+    #[description = "Location of the new node"] location: Option<String>,
+    #[description = "When submissions open: 'YYYY-MM-DD HH:MM' (UTC), a unix or Discord timestamp"]
+    start_time: Option<String>,
+    #[description = "Submission duration in minutes"] submission_duration: Option<u64>,
+    #[description = "Voting duration in minutes"] voting_duration: Option<u64>,
+    #[description = "Tiebreaker duration in minutes"] tiebreaker_duration: Option<u64>,
+    #[description = "How votes are cast and counted"] voting_method: Option<VotingMethod>,
+    #[description = "Tiebreaker rounds before the winner is drawn at random"]
+    tiebreaker_rounds: Option<u32>,
+    #[description = "Hide the running results until voting closes"] blind_voting: Option<bool>,
+    #[description = "Names each person can submit"]
+    #[min = 1]
+    max_submissions: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let Some(mut event) = lorax_schedule::get(&pool, guild_id, id).await? else {
+        ctx.say("❌ No scheduled event with that ID.").await?;
+        return Ok(());
+    };
+
+    if let Some(start_time) = start_time {
+        let Some(start_time) = parse_time(&start_time).filter(|t| *t > Utc::now().timestamp())
+        else {
+            ctx.say("❌ I couldn't read that start time, or it's in the past. Try something like `2024-12-01 18:00` (UTC).").await?;
+            return Ok(());
+        };
+        if start_time != event.start_time {
+            event.start_time = start_time;
+            // The old teaser had the wrong time in it
+            event.teaser_sent = false;
+        }
+    }
+    if let Some(location) = location {
+        event.location = location;
+    }
+    ConfigOptions {
+        submission_duration,
+        voting_duration,
+        tiebreaker_duration,
+        voting_method,
+        tiebreaker_rounds,
+        blind_voting,
+        max_submissions,
+        ..Default::default()
+    }
+    .apply(&mut event.config);
+    // Whatever kept it from opening may be fixed now
+    event.failures = 0;
+    event.retry_at = None;
+    event.last_error = None;
+
+    lorax_schedule::update(&pool, &event).await?;
+
+    ctx.say(format!(
+        "✅ Updated scheduled event `#{}` for **{}**, opening {}.",
+        event.id,
+        event.location,
+        discord_timestamp(event.start_time, TimestampStyle::ShortDateTime),
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Removes a queued Lorax event.
+#[poise::command(slash_command, rename = "cancel", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn schedule_cancel(
+    ctx: Context<'_>,
+    #[description = "ID of the scheduled event"] id: i64,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    if lorax_schedule::delete(&pool, guild_id, id).await? {
+        ctx.say(format!("🛑 Scheduled event `#{}` has been cancelled.", id))
+            .await?;
+    } else {
+        ctx.say("❌ No scheduled event with that ID.").await?;
+    }
+
+    Ok(())
+}
+
+/// Manages the names nobody can submit.
+#[poise::command(
+    slash_command,
+    subcommands(
+        "names_reserve",
+        "names_unreserve",
+        "names_block",
+        "names_unblock",
+        "names_list"
+    ),
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn names(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Reserves a tree name so nobody can submit it.
+#[poise::command(slash_command, rename = "reserve", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn names_reserve(
+    ctx: Context<'_>,
+    #[description = "Tree name to reserve"] name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let name = name.trim().to_lowercase();
+
+    if name.is_empty() {
+        ctx.say("❌ Please give a name to reserve.").await?;
+        return Ok(());
+    }
+    // Anything else could never be submitted anyway
+    if let Err(reason) = validate_tree_name(&name) {
+        ctx.say(format!("❌ {}", reason)).await?;
+        return Ok(());
+    }
+
+    if lorax_names::add(&pool, guild_id, &name, NameRule::Reserved, ctx.author().id).await? {
+        ctx.say(format!("🔒 `{}` is now reserved.", name)).await?;
+    } else {
+        ctx.say(format!("`{}` is already reserved.", name)).await?;
+    }
+
+    Ok(())
+}
+
+/// Lets a reserved tree name be submitted again.
+#[poise::command(slash_command, rename = "unreserve", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn names_unreserve(
+    ctx: Context<'_>,
+    #[description = "Tree name to unreserve"] name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let name = name.trim().to_lowercase();
+
+    let removed = lorax_names::remove(&pool, guild_id, &name, NameRule::Reserved).await?;
+    let msg = match lorax_names::conflict(&pool, guild_id, &name).await? {
+        Some(NameConflict::PastWinner) => {
+            format!("`{}` won an earlier Lorax event, so it stays reserved.", name)
+        }
+        Some(NameConflict::ExistingNode) => {
+            format!("`{}` is the name of a node, so it stays reserved.", name)
+        }
+        _ if removed => format!("🔓 `{}` is no longer reserved.", name),
+        _ => format!("❌ `{}` isn't reserved.", name),
+    };
+    ctx.say(msg).await?;
+
+    Ok(())
+}
+
+/// Blocks every tree name containing a word.
+#[poise::command(slash_command, rename = "block", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn names_block(
+    ctx: Context<'_>,
+    #[description = "Word to block, anywhere in a name"] word: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let word = word.trim().to_lowercase();
+
+    if word.is_empty() {
+        ctx.say("❌ Please give a word to block.").await?;
+        return Ok(());
+    }
+
+    if lorax_names::add(&pool, guild_id, &word, NameRule::Blocked, ctx.author().id).await? {
+        ctx.say(format!("🚫 Names containing ||{}|| are now blocked.", word))
+            .await?;
+    } else {
+        ctx.say(format!("||{}|| is already blocked.", word)).await?;
+    }
+
+    Ok(())
+}
+
+/// Stops blocking tree names containing a word.
+#[poise::command(slash_command, rename = "unblock", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn names_unblock(
+    ctx: Context<'_>,
+    #[description = "Blocked word"] word: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let word = word.trim().to_lowercase();
+
+    if lorax_names::remove(&pool, guild_id, &word, NameRule::Blocked).await? {
+        ctx.say(format!("✅ ||{}|| is no longer blocked.", word)).await?;
+    } else {
+        ctx.say(format!("❌ ||{}|| isn't blocked.", word)).await?;
+    }
+
+    Ok(())
+}
+
+/// Lists the reserved names and blocked words.
+#[poise::command(slash_command, rename = "list", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn names_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let listed = lorax_names::list(&pool, guild_id).await?;
+    let past_winners = lorax_names::past_winners(&pool, guild_id).await?;
+
+    let join = |names: Vec<String>| {
+        if names.is_empty() {
+            "None".to_string()
+        } else {
+            truncate_field(names.join(", "))
+        }
+    };
+    let reserved = listed
+        .iter()
+        .filter(|n| n.rule == NameRule::Reserved)
+        .map(|n| format!("`{}`", n.name))
+        .collect();
+    // Spoilered, some of these are going to be slurs
+    let blocked = listed
+        .iter()
+        .filter(|n| n.rule == NameRule::Blocked)
+        .map(|n| format!("||{}||", n.name))
+        .collect();
+    let past_winners = past_winners.iter().map(|n| format!("`{}`", n)).collect();
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::default()
+                    .title("📛 Lorax Name Rules")
+                    .field("Reserved", join(reserved), false)
+                    .field("Past winners (reserved automatically)", join(past_winners), false)
+                    .field("Blocked words", join(blocked), false)
+                    .color(Color::from_rgb(67, 160, 71)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Event options given to a command, each one replaces the value it's
+/// applied to when it's set.
+#[derive(Default)]
+struct ConfigOptions {
+    submission_duration: Option<u64>,
+    voting_duration: Option<u64>,
+    tiebreaker_duration: Option<u64>,
+    voting_method: Option<VotingMethod>,
+    tiebreaker_rounds: Option<u32>,
+    blind_voting: Option<bool>,
+    max_submissions: Option<u32>,
+    min_account_age_days: Option<u64>,
+    min_member_age_days: Option<u64>,
+    required_role: Option<RoleId>,
+    announcement: Option<String>,
+    campaign_thread: Option<bool>,
+    campaign_forum: Option<bool>,
+}
+
+impl ConfigOptions {
+    fn apply(self, config: &mut LoraxEventConfig) {
+        if let Some(submission_duration) = self.submission_duration {
+            config.submission_duration = submission_duration;
+        }
+        if let Some(voting_duration) = self.voting_duration {
+            config.voting_duration = voting_duration;
+        }
+        if let Some(tiebreaker_duration) = self.tiebreaker_duration {
+            config.tiebreaker_duration = tiebreaker_duration;
+        }
+        if let Some(voting_method) = self.voting_method {
+            config.voting_method = voting_method;
+        }
+        if let Some(tiebreaker_rounds) = self.tiebreaker_rounds {
+            config.max_tiebreaker_rounds = tiebreaker_rounds;
+        }
+        if let Some(blind_voting) = self.blind_voting {
+            config.blind_voting = blind_voting;
+        }
+        if let Some(max_submissions) = self.max_submissions {
+            config.max_submissions_per_user = max_submissions;
+        }
+        if self.min_account_age_days.is_some()
+            || self.min_member_age_days.is_some()
+            || self.required_role.is_some()
+        {
+            let rules = config.eligibility.get_or_insert_with(LoraxEligibility::default);
+            if let Some(days) = self.min_account_age_days {
+                rules.min_account_age_days = days;
+            }
+            if let Some(days) = self.min_member_age_days {
+                rules.min_member_age_days = days;
+            }
+            if let Some(role) = self.required_role {
+                rules.required_role = Some(role);
+            }
+        }
+        if let Some(announcement) = self.announcement {
+            let announcement = announcement.trim();
+            config.announcement = (!announcement.is_empty()).then(|| announcement.to_string());
+        }
+        if let Some(campaign_thread) = self.campaign_thread {
+            config.campaign_thread = campaign_thread;
+        }
+        if let Some(campaign_forum) = self.campaign_forum {
+            config.campaign_forum = campaign_forum;
+        }
+    }
+}
+
+/// The config in the named template, or in the guild's default template if
+/// none is named. Replies and returns `None` if the named one doesn't exist.
+async fn template_config(
+    ctx: Context<'_>,
+    template: Option<&str>,
+) -> Result<Option<LoraxEventConfig>, Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    match template.map(|name| name.trim().to_lowercase()) {
+        Some(name) => match lorax_templates::get(&pool, guild_id, &name).await? {
+            Some(template) => Ok(Some(template.config)),
+            None => {
+                ctx.say(format!("❌ There's no template called `{}`.", name))
+                    .await?;
+                Ok(None)
+            }
+        },
+        None => Ok(Some(
+            lorax_templates::get(&pool, guild_id, lorax_templates::DEFAULT_TEMPLATE)
+                .await?
+                .map(|template| template.config)
+                .unwrap_or_default(),
+        )),
+    }
+}
+
+async fn template_autocomplete<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let templates = match ctx.guild_id() {
+        Some(guild_id) => lorax_templates::list(&ctx.data().pool, guild_id)
+            .await
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    templates
+        .into_iter()
+        .map(|template| template.name)
+        .filter(move |name| name.starts_with(&partial.to_lowercase()))
+}
+
+/// One line describing what a template sets up.
+fn describe_config(config: &LoraxEventConfig) -> String {
+    let mut parts = vec![
+        format!(
+            "{}/{}/{} min",
+            config.submission_duration, config.voting_duration, config.tiebreaker_duration
+        ),
+        config.voting_method.name().to_string(),
+    ];
+    if !config.blind_voting {
+        parts.push("live results".to_string());
+    }
+    if config.max_submissions_per_user > 1 {
+        parts.push(format!("{} names each", config.max_submissions_per_user));
+    }
+    if let Some(rules) = &config.eligibility {
+        parts.push(if rules.is_unrestricted() {
+            "anyone can take part".to_string()
+        } else {
+            "own eligibility rules".to_string()
+        });
+    }
+    if config.announcement.is_some() {
+        parts.push("custom announcement".to_string());
+    }
+    if !config.campaign_thread {
+        parts.push("no campaign thread".to_string());
+    } else if config.campaign_forum {
+        parts.push("campaign posts".to_string());
+    }
+    parts.join(" • ")
+}
+
+/// Manages reusable Lorax event setups.
+#[poise::command(
+    slash_command,
+    subcommands("template_create", "template_edit", "template_delete", "template_list"),
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn template(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Saves a new event template. Name it 'default' to use it whenever no
+/// template is picked.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, rename = "create", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn template_create(
+    ctx: Context<'_>,
+    #[description = "Template name, 'default' is used when no template is picked"]
+    #[max_length = 32]
+    name: String,
+    #[description = "Submission duration in minutes (default: 60)"] submission_duration: Option<u64>,
+    #[description = "Voting duration in minutes (default: 30)"] voting_duration: Option<u64>,
+    #[description = "Tiebreaker duration in minutes (default: 15)"] tiebreaker_duration: Option<u64>,
+    #[description = "How votes are cast and counted (default: single choice)"]
+    voting_method: Option<VotingMethod>,
+    #[description = "Tiebreaker rounds before the winner is drawn at random (default: 3)"]
+    tiebreaker_rounds: Option<u32>,
+    #[description = "Hide the running results until voting closes (default: true)"]
+    blind_voting: Option<bool>,
+    #[description = "Names each person can submit (default: 1)"]
+    #[min = 1]
+    max_submissions: Option<u32>,
+    #[description = "Minimum Discord account age in days, replaces the server's rules"]
+    min_account_age_days: Option<u64>,
+    #[description = "Minimum days in this server, replaces the server's rules"]
+    min_member_age_days: Option<u64>,
+    #[description = "Role needed to take part, replaces the server's rules"] required_role: Option<RoleId>,
+    #[description = "Announcement text, with the same placeholders as the submissions open message"]
+    #[max_length = 1500]
+    announcement: Option<String>,
+    #[description = "Open a campaign thread when voting starts (default: true)"]
+    campaign_thread: Option<bool>,
+    #[description = "Campaign in a forum post per name instead of one thread (default: false)"]
+    campaign_forum: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let name = name.trim().to_lowercase();
+
+    if lorax_templates::get(&pool, guild_id, &name).await?.is_some() {
+        ctx.say(format!(
+            "❌ There's already a template called `{}`, use `/lorax template edit` to change it.",
+            name
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    if let Some(Err(reason)) = announcement
+        .as_deref()
+        .map(|text| copy::validate(LoraxMessage::SubmissionsOpen, text))
+    {
+        ctx.say(reason).await?;
+        return Ok(());
+    }
+
+    let mut config = LoraxEventConfig::default();
+    ConfigOptions {
+        submission_duration,
+        voting_duration,
+        tiebreaker_duration,
+        voting_method,
+        tiebreaker_rounds,
+        blind_voting,
+        max_submissions,
+        min_account_age_days,
+        min_member_age_days,
+        required_role,
+        announcement,
+        campaign_thread,
+        campaign_forum,
+    }
+    .apply(&mut config);
+    lorax_templates::save(&pool, guild_id, &name, &config, ctx.author().id).await?;
+
+    ctx.say(format!(
+        "📋 Saved template `{}`: {}",
+        name,
+        describe_config(&config)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Changes an event template. Events already running keep their old setup.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, rename = "edit", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn template_edit(
+    ctx: Context<'_>,
+    #[description = "Template to change"]
+    #[autocomplete = template_autocomplete]
+    name: String,
+    #[description = "Submission duration in minutes"] submission_duration: Option<u64>,
+    #[description = "Voting duration in minutes"] voting_duration: Option<u64>,
+    #[description = "Tiebreaker duration in minutes"] tiebreaker_duration: Option<u64>,
+    #[description = "How votes are cast and counted"] voting_method: Option<VotingMethod>,
+    #[description = "Tiebreaker rounds before the winner is drawn at random"]
+    tiebreaker_rounds: Option<u32>,
+    #[description = "Hide the running results until voting closes"] blind_voting: Option<bool>,
+    #[description = "Names each person can submit"]
+    #[min = 1]
+    max_submissions: Option<u32>,
+    #[description = "Minimum Discord account age in days, replaces the server's rules"]
+    min_account_age_days: Option<u64>,
+    #[description = "Minimum days in this server, replaces the server's rules"]
+    min_member_age_days: Option<u64>,
+    #[description = "Role needed to take part, replaces the server's rules"] required_role: Option<RoleId>,
+    #[description = "Go back to the server's eligibility rules"] server_eligibility: Option<bool>,
+    #[description = "Announcement text, same placeholders as the submissions open message, empty for the usual one"]
+    #[max_length = 1500]
+    announcement: Option<String>,
+    #[description = "Open a campaign thread when voting starts"] campaign_thread: Option<bool>,
+    #[description = "Campaign in a forum post per name instead of one thread"]
+    campaign_forum: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let name = name.trim().to_lowercase();
+
+    let Some(mut template) = lorax_templates::get(&pool, guild_id, &name).await? else {
+        ctx.say(format!("❌ There's no template called `{}`.", name))
+            .await?;
+        return Ok(());
+    };
+
+    if let Some(Err(reason)) = announcement
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+        .map(|text| copy::validate(LoraxMessage::SubmissionsOpen, text))
+    {
+        ctx.say(reason).await?;
+        return Ok(());
+    }
+
+    if server_eligibility == Some(true) {
+        template.config.eligibility = None;
+    }
+    ConfigOptions {
+        submission_duration,
+        voting_duration,
+        tiebreaker_duration,
+        voting_method,
+        tiebreaker_rounds,
+        blind_voting,
+        max_submissions,
+        min_account_age_days,
+        min_member_age_days,
+        required_role,
+        announcement,
+        campaign_thread,
+        campaign_forum,
+    }
+    .apply(&mut template.config);
+    lorax_templates::save(&pool, guild_id, &name, &template.config, ctx.author().id).await?;
+
+    ctx.say(format!(
+        "✅ Updated template `{}`: {}",
+        name,
+        describe_config(&template.config)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Deletes an event template.
+#[poise::command(slash_command, rename = "delete", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn template_delete(
+    ctx: Context<'_>,
+    #[description = "Template to delete"]
+    #[autocomplete = template_autocomplete]
+    name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let name = name.trim().to_lowercase();
+
+    if lorax_templates::delete(&pool, guild_id, &name).await? {
+        ctx.say(format!("🗑️ Deleted template `{}`.", name)).await?;
+    } else {
+        ctx.say(format!("❌ There's no template called `{}`.", name))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Lists the saved event templates.
+#[poise::command(slash_command, rename = "list", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn template_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let templates = lorax_templates::list(&pool, guild_id).await?;
+    if templates.is_empty() {
+        ctx.say("📋 No templates yet. Create one with `/lorax template create`.")
+            .await?;
+        return Ok(());
+    }
+
+    let template_list = templates
+        .iter()
+        .map(|template| {
+            format!(
+                "`{}` — {}",
+                template.name,
+                describe_config(&template.config)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::default()
+                    .title("📋 Lorax Templates")
+                    .description(truncate_description(template_list))
+                    .footer(CreateEmbedFooter::new(format!(
+                        "Durations are submissions/voting/tiebreaker • '{}' is used when no template is picked",
+                        lorax_templates::DEFAULT_TEMPLATE
+                    )))
+                    .color(Color::from_rgb(67, 160, 71)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Manages the copy of the Lorax announcements.
+#[poise::command(
+    slash_command,
+    subcommands("messages_show", "messages_set", "messages_reset"),
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn messages(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Shows the announcement copy, or one message with a preview.
+#[poise::command(slash_command, rename = "show", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn messages_show(
+    ctx: Context<'_>,
+    #[description = "Message to preview, leave empty to list them all"] message: Option<LoraxMessage>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let guild_settings = ctx.data().settings.read().await.get_guild_settings(guild_id);
+
+    let Some(message) = message else {
+        let message_list = [
+            LoraxMessage::Teaser,
+            LoraxMessage::SubmissionsOpen,
+            LoraxMessage::NoSubmissions,
+            LoraxMessage::VotingOpen,
+            LoraxMessage::CampaignThread,
+            LoraxMessage::Tiebreaker,
+            LoraxMessage::Winner,
+            LoraxMessage::SubmissionsReminder,
+            LoraxMessage::VotingReminder,
+            LoraxMessage::Extended,
+            LoraxMessage::Shortened,
+            LoraxMessage::Cancelled,
+        ]
+        .iter()
+        .map(|message| {
+            format!(
+                "**{}** ({})\n{}",
+                message.name(),
+                if guild_settings.lorax_messages.contains_key(message) {
+                    "custom"
+                } else {
+                    "built-in"
+                },
+                truncate(copy::template(&guild_settings, *message).to_string(), 200)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+        ctx.send(
+            CreateReply::default()
+                .embed(
+                    CreateEmbed::default()
+                        .title("✏️ Lorax Messages")
+                        .description(truncate_description(message_list))
+                        .footer(CreateEmbedFooter::new(format!(
+                            "Built-in copy is in {} • Change it with /lorax set_locale",
+                            guild_settings.lorax_locale.name()
+                        )))
+                        .color(Color::from_rgb(67, 160, 71)),
+                )
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let role = guild_settings
+        .lorax_role
+        .map(|role| format!("<@&{}>", role))
+        .unwrap_or_else(|| "@Lorax".to_string());
+    let time = discord_timestamp(Utc::now().timestamp() + 3600, TimestampStyle::Relative);
+    let submitter = format!("<@{}>", ctx.author().id);
+    let preview = copy::message(
+        &guild_settings,
+        message,
+        &[
+            ("role", &role),
+            ("location", "EU-West"),
+            ("start", &time),
+            ("end", &time),
+            ("count", "5"),
+            ("round", "1"),
+            ("winner", "oak"),
+            ("submitter", &submitter),
+            ("minutes", "30"),
+        ],
+    );
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "**{}**, with placeholders {}:\n```\n{}\n```\n**Preview**\n{}",
+                message.name(),
+                copy::placeholder_list(message),
+                copy::template(&guild_settings, message),
+                preview
+            ))
+            .allowed_mentions(serenity::CreateAllowedMentions::new())
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Replaces the copy of an announcement. Write \n for a line break.
+#[poise::command(slash_command, rename = "set", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn messages_set(
+    ctx: Context<'_>,
+    #[description = "Message to change"] message: LoraxMessage,
+    #[description = "New copy, with placeholders like {role} and {location}. \\n starts a new line"]
+    #[max_length = 1500]
+    text: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    // Slash command options can't hold line breaks
+    let text = text.trim().replace("\\n", "\n");
+
+    if let Err(reason) = copy::validate(message, &text) {
+        ctx.say(reason).await?;
+        return Ok(());
+    }
+
+    {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        guild_settings.lorax_messages.insert(message, text);
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+    }
+
+    ctx.say(format!(
+        "✏️ Saved the **{}** message. Preview it with `/lorax messages show`.",
+        message.name()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Goes back to the built-in copy of an announcement.
+#[poise::command(slash_command, rename = "reset", required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn messages_reset(
+    ctx: Context<'_>,
+    #[description = "Message to reset"] message: LoraxMessage,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let removed = {
+        let mut settings = ctx.data().settings.write().await;
+        let mut guild_settings = settings.get_guild_settings(guild_id);
+        let removed = guild_settings.lorax_messages.remove(&message).is_some();
+        settings.set_guild_settings(guild_id, guild_settings);
+        settings.save(&pool).await?;
+        removed
+    };
+
+    if removed {
+        ctx.say(format!(
+            "↩️ The **{}** message is back to the built-in copy.",
+            message.name()
+        ))
+        .await?;
+    } else {
+        ctx.say(format!(
+            "The **{}** message already uses the built-in copy.",
+            message.name()
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Accepts a Discord timestamp (`<t:1700000000:f>`), a unix timestamp or a
+/// UTC date and time like `2024-12-01 18:00`.
+fn parse_time(input: &str) -> Option<i64> {
+    let input = input.trim();
+    if let Some(timestamp) = input.strip_prefix("<t:").and_then(|t| t.strip_suffix('>')) {
+        return timestamp.split(':').next()?.parse().ok();
+    }
+    if let Ok(timestamp) = input.parse() {
+        return Some(timestamp);
+    }
+    ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(input, format).ok())
+        .map(|time| time.and_utc().timestamp())
+}
+
+pub async fn start_voting(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+) -> Result<(), Error> {
+    // The scheduler and /lorax force_end can both get here for one event
+    let key = (guild_id, event_id);
+    if !ADVANCING.lock().unwrap().insert(key) {
+        return Ok(());
+    }
+    let result = open_voting(ctx, data, guild_id, event_id).await;
+    ADVANCING.lock().unwrap().remove(&key);
+    result
+}
+
+/// Closes submissions, then posts the voting announcement and campaign posts
+/// without holding the settings lock, as that's a couple of requests per name.
+async fn open_voting(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+) -> Result<(), Error> {
+    let pool = Arc::clone(&data.pool);
+
+    // Closed first, so the names can't change while the lock isn't held
+    let (guild, state) = {
+        let mut settings = data.settings.write().await;
+        let Some(LoraxState::Submissions { end_time, .. }) = settings
+            .guilds
+            .get_mut(&guild_id)
+            .and_then(|guild| guild.lorax_event_mut(event_id))
+        else {
+            return Ok(());
+        };
+        let now = Utc::now().timestamp();
+        if *end_time > now {
+            *end_time = now;
+            settings.save(&pool).await?;
+        }
+        let guild = settings.get_guild_settings(guild_id);
+        let state = guild.lorax_event(event_id).cloned().unwrap_or_default();
+        (guild, state)
+    };
+
+    let channel_id = guild.lorax_channel.unwrap();
+    let role_id = guild.lorax_role.unwrap();
+
+    let LoraxState::Submissions {
+        submissions,
+        location,
+        voting_duration,
+        tiebreaker_duration,
+        timeline,
+        voting_method,
+        max_tiebreaker_rounds,
+        blind_voting,
+        eligibility,
+        campaign_thread,
+        campaign_forum,
+        ..
+    } = &state
+    else {
+        return Ok(());
+    };
+
+    announcement::update(&ctx.http, channel_id, event_id, &state, true, guild.lorax_locale).await;
+
+    let phrases = copy::phrases(guild.lorax_locale);
+    let role = format!("<@&{}>", role_id);
+
+    if submissions.is_empty() {
+        if end_event(data, guild_id, event_id, &state, LoraxOutcome::NoSubmissions, None).await? {
+            channel_id
+                .say(
+                    ctx,
+                    copy::message(
+                        &guild,
+                        LoraxMessage::NoSubmissions,
+                        &[("role", &role), ("location", location)],
+                    ),
+                )
+                .await?;
+        }
+        return Ok(());
+    }
+
+    let reminders_sent = reminders_to_skip(guild.lorax_reminders(), *voting_duration);
+
+    // Use voting_duration instead of hardcoded value
+    let voting_started_at = Utc::now().timestamp();
+    let end_time = voting_started_at + (voting_duration * 60) as i64;
+    let options = submissions
+        .iter()
+        .map(|submission| submission.name.clone())
+        .collect::<Vec<_>>();
+    let submission_count = options.len().to_string();
+
+    let announcement = copy::message(
+        &guild,
+        LoraxMessage::VotingOpen,
+        &[
+            ("role", &role),
+            ("location", location),
+            ("end", &discord_timestamp(end_time, TimestampStyle::Relative)),
+            ("count", &submission_count),
+        ],
+    );
+
+    let announcement_msg = channel_id.say(ctx, announcement).await?;
+
+    let forum = guild.lorax_campaign_forum.filter(|_| *campaign_thread && *campaign_forum);
+    if *campaign_forum && forum.is_none() {
+        warn!(
+            "Event {} in guild {} wants campaign posts, but there's no campaign forum",
+            event_id, guild_id
+        );
+    }
+    let campaign_posts = match forum {
+        Some(forum) => campaign::open_posts(&ctx.http, forum, phrases, submissions).await,
+        None => BTreeMap::new(),
+    };
+
+    let thread_id = if *campaign_thread && forum.is_none() {
+        // Create campaign thread
+        let thread = channel_id.create_thread(
+            ctx,
+            serenity::CreateThread::new(copy::render(
+                phrases.thread_name,
+                &[("location", location)],
+            ))
+            .kind(serenity::ChannelType::PublicThread)
+            .auto_archive_duration(serenity::AutoArchiveDuration::OneDay)
+        )
+        .await?;
+
+        let representatives = {
+            let mut rng = StdRng::from_rng(OsRng)?;
+            let mut submitter_list: Vec<_> = submissions.iter().collect();
+            submitter_list.shuffle(&mut rng);
+            submitter_list.into_iter().take(5).collect::<Vec<_>>()
+        };
+
+        // Send initial thread message with random representatives
+        let reps = futures::future::join_all(representatives.iter().map(|submission| async move {
+            let _name = get_submitter_name(ctx, submission.submitter).await;
+            copy::render(
+                phrases.representing,
+                &[
+                    ("submitter", &format!("<@{}>", submission.submitter)),
+                    ("name", &submission.name),
+                ],
+            )
+        })).await.join("\n");
+
+        let mut campaign_msg = copy::message(
+            &guild,
+            LoraxMessage::CampaignThread,
+            &[("location", location), ("count", &submission_count)],
+        );
+        if !representatives.is_empty() {
+            campaign_msg.push_str("\n\n");
+            campaign_msg.push_str(&copy::render(phrases.speakers, &[("names", &reps)]));
+        }
+
+        thread.id.send_message(
+            ctx, 
+            serenity::CreateMessage::new().content(campaign_msg)
+        ).await?;
+
+        // What the names mean, so nobody has to ask what a `yew` is
+        let mut meanings: Vec<_> = submissions
+            .iter()
+            .filter_map(|s| Some((&s.name, s.meaning.as_ref()?)))
+            .collect();
+        meanings.sort();
+        if !meanings.is_empty() {
+            let mut meanings_msg = format!("{}\n", phrases.meanings);
+            for (name, meaning) in meanings {
+                let line = format!("\n• `{}`: {}", name, meaning);
+                // Stay under the 2000 character message limit
+                if meanings_msg.len() + line.len() > 1900 {
+                    thread.id.say(ctx, std::mem::take(&mut meanings_msg)).await?;
+                }
+                meanings_msg.push_str(&line);
+            }
+            thread.id.say(ctx, meanings_msg).await?;
+        }
+        Some(thread.id)
+    } else {
+        None
+    };
+
+    let mut settings = data.settings.write().await;
+    // Statements can still change while submissions are closed, names can't
+    let Some(LoraxState::Submissions { submissions: current, .. }) = settings
+        .guilds
+        .get(&guild_id)
+        .and_then(|guild| guild.lorax_event(event_id))
+    else {
+        // Cancelled in the meantime
+        drop(settings);
+        warn!(
+            "Event {} in guild {} ended while voting was being opened",
+            event_id, guild_id
+        );
+        for channel in thread_id.iter().chain(campaign_posts.values()) {
+            campaign::close(&ctx.http, *channel).await;
+        }
+        return Ok(());
+    };
+    // Posts were written from the copy, redo the ones whose statement changed
+    let edited_posts: Vec<_> = current
+        .iter()
+        .filter(|s| !submissions.contains(s))
+        .filter_map(|s| Some((*campaign_posts.get(&s.name)?, s.clone())))
+        .collect();
+
+    let voting_state = LoraxState::Voting {
+        end_time,
+        message_id: announcement_msg.id,
+        thread_id,
+        options,
+        votes: HashMap::new(),
+
+        submissions: current.clone(),
+        location: location.clone(),
+        tiebreaker_duration: *tiebreaker_duration,
+        timeline: LoraxTimeline {
+            voting_started_at: Some(voting_started_at),
+            ..timeline.clone()
+        },
+        voting_method: *voting_method,
+        max_tiebreaker_rounds: *max_tiebreaker_rounds,
+        reminders_sent,
+        ballots: HashMap::new(),
+        blind_voting: *blind_voting,
+        eligibility: eligibility.clone(),
+        campaign_posts,
+        vote_times: HashMap::new(),
+        join_times: HashMap::new(),
+        voided: Vec::new(),
+    };
+    settings
+        .guilds
+        .get_mut(&guild_id)
+        .unwrap()
+        .lorax_events
+        .insert(event_id, voting_state.clone());
+    settings.save(&pool).await?;
+    drop(settings);
+    announcement::update(&ctx.http, channel_id, event_id, &voting_state, false, guild.lorax_locale)
+        .await;
+    for (post, submission) in edited_posts {
+        if let Err(e) = campaign::update_post(&ctx.http, post, phrases, &submission).await {
+            warn!("Failed to update campaign post for `{}`: {}", submission.name, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Posts the next tiebreaker round and swaps it in for the round that just
+/// ended, whose tallies are already on `timeline`.
+#[allow(clippy::too_many_arguments)]
+pub fn start_tiebreaker(
+    http: Arc<serenity::Http>,
+    data: Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+    tied_options: Vec<(usize, String)>,
+    location: String,
+    round: u32,
+    tiebreaker_duration: u64,
+    timeline: LoraxTimeline,
+) -> BoxFuture<'static, Result<(), Error>> {
+    async move {
+        let pool = Arc::clone(&data.pool);
+        let guild = data.settings.read().await.get_guild_settings(guild_id);
+        let Some(current) = guild.lorax_event(event_id) else {
+            return Ok(());
+        };
+
+        let channel_id = guild.lorax_channel.unwrap();
+        let role_id = guild.lorax_role.unwrap();
+
+        let end_time = Utc::now().timestamp() + (tiebreaker_duration * 60) as i64;
+        
+        let options: Vec<String> = tied_options.into_iter().map(|(_, name)| name).collect();
+
+        let submissions = match current {
+            LoraxState::Voting { submissions, .. }
+            | LoraxState::TieBreaker { submissions, .. } => submissions.clone(),
+            _ => Vec::new(),
+        };
+        let (voting_method, max_tiebreaker_rounds, blind_voting) = match current {
+            LoraxState::Voting {
+                voting_method,
+                max_tiebreaker_rounds,
+                blind_voting,
+                ..
+            }
+            | LoraxState::TieBreaker {
+                voting_method,
+                max_tiebreaker_rounds,
+                blind_voting,
+                ..
+            } => (*voting_method, *max_tiebreaker_rounds, *blind_voting),
+            _ => (VotingMethod::default(), DEFAULT_TIEBREAKER_ROUNDS, true),
         };
 
+        let phrases = copy::phrases(guild.lorax_locale);
+        let tied_names = options
+            .iter()
+            .map(|name| format!("• `{}`", name))
+            .collect::<Vec<_>>()
+            .join("\n");
         let announcement = format!(
-            "🎯 Hey <@&{}>! We've got a tie! Time for tiebreaker round {}!\n\n\
-            The following names are tied:\n{}\n\n\
-            Use `/lorax vote` to break the tie! One name will be eliminated.\n\n\
-            This round ends {}.",
-            role_id,
-            round,
-            options
-                .iter()
-                .map(|name| format!("• `{}`", name))
-                .collect::<Vec<_>>()
-                .join("\n"),
-            discord_timestamp(end_time, TimestampStyle::ShortDateTime)
+            "{}\n\n{}\n{}\n\n{}",
+            copy::message(
+                &guild,
+                LoraxMessage::Tiebreaker,
+                &[
+                    ("role", &format!("<@&{}>", role_id)),
+                    ("location", &location),
+                    ("end", &discord_timestamp(end_time, TimestampStyle::Relative)),
+                    ("count", &options.len().to_string()),
+                    ("round", &round.to_string()),
+                ],
+            ),
+            phrases.tied_names,
+            tied_names,
+            if round >= max_tiebreaker_rounds {
+                phrases.last_round
+            } else {
+                phrases.draw_if_no_votes
+            },
         );
 
         let announcement_msg = channel_id.say(http.clone(), announcement).await?;
 
         // Get existing thread ID if any
-        let thread_id = match &guild.lorax_state {
+        let thread_id = match current {
             LoraxState::Voting { thread_id, .. } => *thread_id,
             LoraxState::TieBreaker { thread_id, .. } => *thread_id,
             _ => None,
         };
 
-        guild.lorax_state = LoraxState::TieBreaker {
+        let tiebreaker_state = LoraxState::TieBreaker {
             end_time,
             message_id: announcement_msg.id,
             thread_id,  // Preserve the thread
@@ -329,31 +1920,63 @@ pub fn start_tiebreaker(
             round,
             tiebreaker_duration,
             submissions,
+            timeline,
+            voting_method,
+            max_tiebreaker_rounds,
+            reminders_sent: reminders_to_skip(guild.lorax_reminders(), tiebreaker_duration),
+            ballots: HashMap::new(),
+            blind_voting,
+            eligibility: current.eligibility().cloned(),
+            campaign_posts: current.campaign_posts().cloned().unwrap_or_default(),
+            vote_times: HashMap::new(),
+            join_times: HashMap::new(),
+            // A voided voter stays out of the tiebreakers too
+            voided: match current {
+                LoraxState::Voting { voided, .. } | LoraxState::TieBreaker { voided, .. } => {
+                    voided.clone()
+                }
+                _ => Vec::new(),
+            },
         };
-        settings.save(&pool).await?;
+        {
+            let mut settings = data.settings.write().await;
+            let Some(guild) = settings
+                .guilds
+                .get_mut(&guild_id)
+                .filter(|guild| guild.lorax_event(event_id).is_some())
+            else {
+                // Cancelled in the meantime
+                warn!(
+                    "Event {} in guild {} ended while its tiebreaker was being opened",
+                    event_id, guild_id
+                );
+                return Ok(());
+            };
+            guild.lorax_events.insert(event_id, tiebreaker_state.clone());
+            settings.save(&pool).await?;
+        }
+        announcement::update(
+            &http,
+            channel_id,
+            event_id,
+            &tiebreaker_state,
+            false,
+            guild.lorax_locale,
+        )
+        .await;
 
         // If there's a thread, announce the tiebreaker there too
         if let Some(thread_id) = thread_id {
             let _ = thread_id.send_message(
                 http,
                 serenity::CreateMessage::new()
-                    .content(format!(
-                        "🎯 We're headed to tiebreaker round {}! The following names are tied:\n{}\n\nUse `/lorax vote` to help break the tie!",
-                        round,
-                        options
-                            .iter()
-                            .map(|name| format!("• `{}`", name))
-                            .collect::<Vec<_>>()
-                            .join("\n")
+                    .content(copy::render(
+                        phrases.thread_tiebreaker,
+                        &[("round", &round.to_string()), ("names", &tied_names)],
                     ))
             ).await;
         }
 
-        // Use OsRng for thread-safe random number generation
-        let mut rng = StdRng::from_rng(OsRng)?;
-        let _remove_idx = rng.gen_range(0..tied_options_clone.len());
-        // Note: The actual elimination logic should go here if needed
-
         Ok(())
     }.boxed()
 }
@@ -362,30 +1985,44 @@ pub async fn announce_winner(
     http: &Arc<serenity::Http>,
     data: &Data,
     guild_id: serenity::GuildId,
+    event_id: u64,
+) -> Result<(), Error> {
+    // The scheduler and /lorax force_end can both get here for one event
+    let key = (guild_id, event_id);
+    if !ADVANCING.lock().unwrap().insert(key) {
+        return Ok(());
+    }
+    let result = close_round(http, data, guild_id, event_id).await;
+    ADVANCING.lock().unwrap().remove(&key);
+    result
+}
+
+/// Tallies the round that just ended, then either starts a tiebreaker or ends
+/// the event. The results are posted once the settings are saved, without
+/// holding the lock.
+async fn close_round(
+    http: &Arc<serenity::Http>,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
 ) -> Result<(), Error> {
-    let mut settings = data.settings.write().await;
     let pool = Arc::clone(&data.pool);
-    let guild = settings.guilds.get_mut(&guild_id).unwrap();
+    let guild = data.settings.read().await.get_guild_settings(guild_id);
     let channel_id = guild.lorax_channel.unwrap();
-    let role_id = guild.lorax_role.unwrap();
-    let state = guild.lorax_state.clone();
-
-    // Track if we need to close a thread
-    let mut thread_to_close = None;
+    let role = format!("<@&{}>", guild.lorax_role.unwrap());
+    let winner_role = guild.lorax_winner_role;
+    let phrases = copy::phrases(guild.lorax_locale);
+    let Some(state) = guild.lorax_event(event_id).cloned() else {
+        return Ok(());
+    };
+    let current_round = match state {
+        LoraxState::TieBreaker { round, .. } => round,
+        _ => 0,
+    };
+    announcement::update(http, channel_id, event_id, &state, true, guild.lorax_locale).await;
 
-    match state {
-        LoraxState::Voting {
-            thread_id,
-            ..
-        }
-        | LoraxState::TieBreaker {
-            thread_id,
-            ..
-        } => {
-            thread_to_close = thread_id;
-        }
-        _ => {}
-    }
+    // Campaign threads and posts are closed once the event is over
+    let campaign_state = state.clone();
 
     match state {
         LoraxState::Voting {
@@ -394,6 +2031,9 @@ pub async fn announce_winner(
             location,
             tiebreaker_duration,
             submissions,
+            voting_method,
+            ballots,
+            max_tiebreaker_rounds,
             ..
         }
         | LoraxState::TieBreaker {
@@ -402,46 +2042,107 @@ pub async fn announce_winner(
             location,
             tiebreaker_duration,
             submissions,
+            voting_method,
+            ballots,
+            max_tiebreaker_rounds,
             ..
         } => {
-            let mut vote_counts: HashMap<usize, usize> = HashMap::new();
-            for &choice in votes.values() {
-                *vote_counts.entry(choice).or_insert(0) += 1;
+            let runoff = match voting_method {
+                VotingMethod::InstantRunoff if !ballots.is_empty() => {
+                    Some(voting::instant_runoff(options.len(), ballots.values()))
+                }
+                _ => None,
+            };
+            let vote_counts = match (&runoff, voting_method) {
+                (Some(runoff), _) => runoff.final_counts.clone(),
+                (None, VotingMethod::Approval) => voting::approval(ballots.values()),
+                (None, _) => voting::plurality(votes.values().copied()),
+            };
+
+            // Keep this round's tallies around for the history archive
+            let mut ended_state = campaign_state.clone();
+            if let LoraxState::Voting { timeline, .. } | LoraxState::TieBreaker { timeline, .. } =
+                &mut ended_state
+            {
+                timeline.rounds.push(LoraxRound {
+                    round: current_round,
+                    ended_at: Utc::now().timestamp(),
+                    tallies: options
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, name)| (name.clone(), *vote_counts.get(&idx).unwrap_or(&0)))
+                        .collect(),
+                    voters: votes
+                        .keys()
+                        .chain(ballots.keys())
+                        .copied()
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .collect(),
+                    ballots: anonymised_ballots(&options, &votes, &ballots),
+                });
             }
 
-            if vote_counts.is_empty() || options.len() <= 1 {
+            if options.len() <= 1 {
                 if let Some(winning_tree) = options.first() {
                     let submitter = submissions.iter()
-                        .find(|(_, name)| *name == winning_tree)
-                        .map(|(user_id, _)| *user_id)
+                        .find(|submission| submission.name == *winning_tree)
+                        .map(|submission| submission.submitter)
                         .unwrap();
 
+                    let winner = Some((winning_tree.as_str(), submitter));
+                    let ended = end_event(
+                        data,
+                        guild_id,
+                        event_id,
+                        &ended_state,
+                        LoraxOutcome::Winner,
+                        winner,
+                    )
+                    .await?;
+                    if !ended {
+                        return Ok(());
+                    }
                     channel_id
                         .say(
                             http,
-                            format!(
-                                "Hey <@&{}>! 🎉 The winning tree name is **{}** (submitted by <@{}>)! This will be the name for our new **{}** node.\n\nThank you all for participating!",
-                                role_id, winning_tree, submitter, location
+                            copy::message(
+                                &guild,
+                                LoraxMessage::Winner,
+                                &[
+                                    ("role", &role),
+                                    ("location", &location),
+                                    ("count", &submissions.len().to_string()),
+                                    ("winner", winning_tree),
+                                    ("submitter", &format!("<@{}>", submitter)),
+                                ],
                             ),
                         )
                         .await?;
-
-                    guild.lorax_state = LoraxState::Idle;
-                    settings.save(&pool).await?;
                 } else {
+                    let ended = end_event(
+                        data,
+                        guild_id,
+                        event_id,
+                        &ended_state,
+                        LoraxOutcome::NoWinner,
+                        None,
+                    )
+                    .await?;
+                    if !ended {
+                        return Ok(());
+                    }
                     channel_id
-                        .say(
-                            http,
-                            "No valid tree names remain. The event has ended without a winner.",
-                        )
+                        .say(http, phrases.no_names_left)
                         .await?;
-
-                    guild.lorax_state = LoraxState::Idle;
-                    settings.save(&pool).await?;
                 }
+                update_winner_role(http, &pool, guild_id, winner_role).await;
+                campaign::close_all(http, &campaign_state).await;
                 return Ok(());
             }
 
+            let runoff_embed = runoff.as_ref().map(|r| runoff_results_embed(&options, r));
+
             // Get sorted list of all entries by votes
             let mut options_with_votes: Vec<_> = options
                 .iter()
@@ -449,35 +2150,93 @@ pub async fn announce_winner(
                 .map(|(idx, name)| {
                     let vote_count = vote_counts.get(&idx).unwrap_or(&0);
                     let submitter = submissions.iter()
-                        .find(|(_, n)| *n == name)
-                        .map(|(user_id, _)| *user_id)
+                        .find(|submission| submission.name == *name)
+                        .map(|submission| submission.submitter)
                         .unwrap();
                     (name.clone(), *vote_count, submitter)
                 })
                 .collect();
             options_with_votes.sort_by(|a, b| b.1.cmp(&a.1));
 
-            let max_votes = options_with_votes.first().map(|(_, votes, _)| *votes).unwrap_or(0);
-            let tied_options: Vec<(usize, String)> = options
-                .iter()
-                .enumerate()
-                .filter(|(i, _)| vote_counts.get(i).unwrap_or(&0) == &max_votes)
-                .map(|(i, name)| (i, name.clone()))
-                .collect();
+            let mut tied_options: Vec<(usize, String)> = match &runoff {
+                Some(runoff) => runoff
+                    .remaining
+                    .iter()
+                    .map(|&i| (i, options[i].clone()))
+                    .collect(),
+                None => {
+                    let max_votes =
+                        options_with_votes.first().map(|(_, votes, _)| *votes).unwrap_or(0);
+                    options
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| vote_counts.get(i).unwrap_or(&0) == &max_votes)
+                        .map(|(i, name)| (i, name.clone()))
+                        .collect()
+                }
+            };
 
-            if tied_options.len() > 1 {
-                let round = match guild.lorax_state {
-                    LoraxState::TieBreaker { round, .. } => round + 1,
-                    _ => 1,
+            // Nobody voted, or the tie has dragged on long enough: draw a
+            // winner instead of running another round. The draw is posted
+            // with the winner, once the event is archived.
+            let mut draw = None;
+            if tied_options.len() > 1
+                && (vote_counts.values().all(|&votes| votes == 0)
+                    || current_round >= max_tiebreaker_rounds)
+            {
+                let seed = OsRng.next_u64();
+                let names: Vec<&str> = tied_options.iter().map(|(_, name)| name.as_str()).collect();
+                let drawn = voting::draw(&names, seed);
+                let mut sorted = names.clone();
+                sorted.sort_unstable();
+
+                let reason = if vote_counts.values().all(|&votes| votes == 0) {
+                    phrases.nobody_voted.to_string()
+                } else {
+                    copy::render(phrases.still_tied, &[("round", &current_round.to_string())])
                 };
+                draw = Some(copy::render(
+                    phrases.draw,
+                    &[
+                        ("reason", &reason),
+                        (
+                            "names",
+                            &sorted
+                                .iter()
+                                .enumerate()
+                                .map(|(i, name)| format!("{}. `{}`", i, name))
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        ),
+                        ("seed", &seed.to_string()),
+                        ("count", &sorted.len().to_string()),
+                        ("index", &(seed % sorted.len() as u64).to_string()),
+                        ("winner", names[drawn]),
+                    ],
+                ));
+
+                tied_options = vec![tied_options.swap_remove(drawn)];
+            }
+
+            // An IRV or drawn winner can finish level with others on votes,
+            // make sure they're listed first.
+            if let [(_, winner)] = tied_options.as_slice() {
+                options_with_votes.sort_by_key(|(name, _, _)| name != winner);
+            }
+
+            if tied_options.len() > 1 {
+                let round = current_round + 1;
 
                 let tied_names = tied_options.iter()
                     .map(|(_, name)| {
                         let submitter = submissions.iter()
-                            .find(|(_, n)| *n == name)
-                            .map(|(user_id, _)| *user_id)
+                            .find(|submission| submission.name == *name)
+                            .map(|submission| submission.submitter)
                             .unwrap();
-                        format!("`{}` (by <@{}>)", name, submitter)
+                        copy::render(
+                            phrases.tied_entry,
+                            &[("name", name), ("submitter", &format!("<@{}>", submitter))],
+                        )
                     })
                     .collect::<Vec<_>>()
                     .join(", ");
@@ -485,80 +2244,438 @@ pub async fn announce_winner(
                 channel_id
                     .say(
                         http,
-                        format!(
-                            "Hey <@&{}>! We've got a tie between {}! Time for tiebreaker round {}!\n\nUse `/lorax vote` to break the tie!",
-                            role_id, tied_names, round
+                        copy::render(
+                            phrases.tie_between,
+                            &[
+                                ("role", &role),
+                                ("names", &tied_names),
+                                ("round", &round.to_string()),
+                            ],
                         ),
                     )
                     .await?;
 
+                if let Some(embed) = runoff_embed {
+                    channel_id
+                        .send_message(http, serenity::CreateMessage::new().embed(embed))
+                        .await?;
+                }
+
                 start_tiebreaker(
                     http.clone(),
                     data.clone(),
                     guild_id,
+                    event_id,
                     tied_options,
                     location.clone(),
                     round,
                     tiebreaker_duration,
+                    ended_state.timeline().cloned().unwrap_or_default(),
                 )
                 .await?;
                 return Ok(());
             }
 
-            if let Some((winning_tree, winning_votes, submitter)) = options_with_votes.first() {
+            if let Some((winning_tree, _, submitter)) = options_with_votes.first() {
+                let entry = |(name, votes, user_id): &(String, usize, serenity::UserId)| {
+                    copy::render(
+                        phrases.entry,
+                        &[
+                            ("name", name),
+                            ("submitter", &format!("<@{}>", user_id)),
+                            ("count", &votes.to_string()),
+                        ],
+                    )
+                };
                 let top_entries = if options_with_votes.len() > 1 {
                     let runners_up = options_with_votes[1..].iter()
                         .take(2)
-                        .map(|(name, votes, user_id)| {
-                            format!("`{}` by <@{}> ({} votes)", name, user_id, votes)
-                        })
+                        .map(entry)
                         .collect::<Vec<_>>()
                         .join("\n");
                     
                     let remaining = if options_with_votes.len() > 3 {
-                        format!("\n\n...and {} more entries!", options_with_votes.len() - 3)
+                        format!(
+                            "\n\n{}",
+                            copy::render(
+                                phrases.more_entries,
+                                &[("count", &(options_with_votes.len() - 3).to_string())],
+                            )
+                        )
                     } else {
                         String::new()
                     };
 
                     format!(
-                        "\n\nTop entries:\n🥇 `{}` by <@{}> ({} votes)\n🥈 {}{}",
-                        winning_tree, submitter, winning_votes, runners_up, remaining
+                        "\n\n{}\n🥇 {}\n🥈 {}{}",
+                        phrases.top_entries,
+                        entry(&options_with_votes[0]),
+                        runners_up,
+                        remaining
                     )
                 } else {
                     String::new()
                 };
 
+                let winner = Some((winning_tree.as_str(), *submitter));
+                let ended = end_event(
+                    data,
+                    guild_id,
+                    event_id,
+                    &ended_state,
+                    LoraxOutcome::Winner,
+                    winner,
+                )
+                .await?;
+                if !ended {
+                    return Ok(());
+                }
+
+                if let Some(draw) = draw {
+                    channel_id.say(http, draw).await?;
+                }
+                let announcement = copy::message(
+                    &guild,
+                    LoraxMessage::Winner,
+                    &[
+                        ("role", &role),
+                        ("location", &location),
+                        ("count", &submissions.len().to_string()),
+                        ("winner", winning_tree),
+                        ("submitter", &format!("<@{}>", submitter)),
+                    ],
+                );
                 channel_id
-                    .say(
-                        http,
-                        format!(
-                            "Hey <@&{}>! 🎉 The winning tree name is **{}**! This will be the name for our new **{}** node.{}",
-                            role_id, winning_tree, location, top_entries
-                        ),
-                    )
+                    .say(http, format!("{}{}", announcement, top_entries))
                     .await?;
 
-                guild.lorax_state = LoraxState::Idle;
-                settings.save(&pool).await?;
+                if let Some(embed) = runoff_embed {
+                    channel_id
+                        .send_message(http, serenity::CreateMessage::new().embed(embed))
+                        .await?;
+                }
+
+                update_winner_role(http, &pool, guild_id, winner_role).await;
             }
         }
         _ => {}
     }
 
-    // After announcing winner or if no valid options remain, close the thread
-    if let Some(thread_id) = thread_to_close {
-        if let Err(e) = thread_id.edit_thread(
-            http,
-            serenity::EditThread::new().archived(true).locked(true),
-        ).await {
-            warn!("Failed to close campaign thread: {}", e);
+    // After announcing winner or if no valid options remain, close the
+    // campaign
+    campaign::close_all(http, &campaign_state).await;
+
+    Ok(())
+}
+
+/// Every ballot as a list of names. They're sorted so the order doesn't give
+/// away who voted when.
+fn anonymised_ballots(
+    options: &[String],
+    votes: &HashMap<serenity::UserId, usize>,
+    ballots: &HashMap<serenity::UserId, Vec<usize>>,
+) -> Vec<Vec<String>> {
+    let mut anonymised: Vec<Vec<String>> = votes
+        .values()
+        .map(|&choice| vec![options[choice].clone()])
+        .chain(
+            ballots
+                .values()
+                .map(|ballot| ballot.iter().map(|&choice| options[choice].clone()).collect()),
+        )
+        .collect();
+    anonymised.sort_unstable();
+    anonymised
+}
+
+/// Round-by-round breakdown of an instant-runoff count.
+fn runoff_results_embed(options: &[String], runoff: &RunoffResult) -> CreateEmbed {
+    let mut embed = CreateEmbed::default()
+        .title("🗳️ Instant-runoff results")
+        .color(Color::from_rgb(67, 160, 71));
+
+    // Embeds top out at 25 fields, the last rounds are the interesting ones
+    let skipped = runoff.rounds.len().saturating_sub(25);
+    for (i, round) in runoff.rounds.iter().enumerate().skip(skipped) {
+        let total: usize = round.counts.iter().map(|(_, votes)| votes).sum();
+        let mut lines = round
+            .counts
+            .iter()
+            .map(|(option, votes)| {
+                let percent = if total > 0 {
+                    *votes as f64 * 100.0 / total as f64
+                } else {
+                    0.0
+                };
+                format!("• `{}` — {} ({:.1}%)", options[*option], votes, percent)
+            })
+            .collect::<Vec<_>>();
+
+        if !round.eliminated.is_empty() {
+            lines.push(format!(
+                "❌ Eliminated: {}",
+                round
+                    .eliminated
+                    .iter()
+                    .map(|option| format!("`{}`", options[*option]))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
         }
+
+        embed = embed.field(
+            format!("Round {}", i + 1),
+            truncate_field(lines.join("\n")),
+            false,
+        );
+    }
+
+    let outcome = match runoff.remaining.as_slice() {
+        [winner] => format!("`{}` wins with a majority of the remaining votes.", options[*winner]),
+        tied => format!(
+            "{} are still tied, so it's down to a tiebreaker round.",
+            tied.iter()
+                .map(|option| format!("`{}`", options[*option]))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    embed.description(outcome)
+}
+
+/// Reminders that are further out than the whole phase would fire the moment
+/// it starts, so they're marked as sent up front.
+fn reminders_to_skip(reminders: &[u64], phase_duration: u64) -> Vec<u64> {
+    reminders
+        .iter()
+        .copied()
+        .filter(|&minutes| minutes >= phase_duration)
+        .collect()
+}
+
+fn format_days(days: u64) -> String {
+    if days == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", days)
+    }
+}
+
+fn format_minutes(minutes: u64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+/// Pings the Lorax role when a phase is about to close. Sent reminders are
+/// recorded in the state, so they survive restarts. If several are due at
+/// once (say the bot was down) only the most urgent one goes out.
+pub async fn send_due_reminders(
+    http: &serenity::Http,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+) -> Result<(), Error> {
+    let mut settings = data.settings.write().await;
+    let pool = Arc::clone(&data.pool);
+    let Some(guild) = settings.guilds.get_mut(&guild_id) else {
+        return Ok(());
+    };
+    let (Some(channel_id), Some(role_id)) = (guild.lorax_channel, guild.lorax_role) else {
+        return Ok(());
+    };
+    let reminders = guild.lorax_reminders().to_vec();
+
+    let now = Utc::now().timestamp();
+    let (end_time, location, thread_id, reminders_sent, is_submissions) =
+        match guild.lorax_event_mut(event_id) {
+            None | Some(LoraxState::Idle) => return Ok(()),
+            Some(LoraxState::Submissions {
+                end_time,
+                location,
+                reminders_sent,
+                ..
+            }) => (*end_time, location.clone(), None, reminders_sent, true),
+            Some(
+                LoraxState::Voting {
+                    end_time,
+                    location,
+                    thread_id,
+                    reminders_sent,
+                    ..
+                }
+                | LoraxState::TieBreaker {
+                    end_time,
+                    location,
+                    thread_id,
+                    reminders_sent,
+                    ..
+                },
+            ) => (*end_time, location.clone(), *thread_id, reminders_sent, false),
+        };
+
+    if now >= end_time {
+        return Ok(());
+    }
+
+    let due: Vec<u64> = reminders
+        .iter()
+        .copied()
+        .filter(|m| !reminders_sent.contains(m) && end_time - now <= (*m * 60) as i64)
+        .collect();
+    if due.is_empty() {
+        return Ok(());
+    }
+    reminders_sent.extend(&due);
+    let guild = settings.get_guild_settings(guild_id);
+    settings.save(&pool).await?;
+    drop(settings);
+
+    let closes = discord_timestamp(end_time, TimestampStyle::Relative);
+    let announcement = copy::message(
+        &guild,
+        if is_submissions {
+            LoraxMessage::SubmissionsReminder
+        } else {
+            LoraxMessage::VotingReminder
+        },
+        &[
+            ("role", &format!("<@&{}>", role_id)),
+            ("location", &location),
+            ("end", &closes),
+        ],
+    );
+    channel_id.say(http, announcement).await?;
+
+    if let Some(thread_id) = thread_id {
+        let phrases = copy::phrases(guild.lorax_locale);
+        let _ = thread_id
+            .say(http, copy::render(phrases.thread_reminder, &[("end", &closes)]))
+            .await;
     }
 
     Ok(())
 }
 
+/// Archives a finished event, adds its winner to the node inventory and takes
+/// it out of the settings. Returns false if it was cancelled while the results
+/// were being worked out, and an error with the event still running if it
+/// couldn't be archived.
+async fn end_event(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+    state: &LoraxState,
+    outcome: LoraxOutcome,
+    winner: Option<(&str, serenity::UserId)>,
+) -> Result<bool, Error> {
+    let mut settings = data.settings.write().await;
+    let Some(guild) = settings
+        .guilds
+        .get_mut(&guild_id)
+        .filter(|guild| guild.lorax_event(event_id).is_some())
+    else {
+        return Ok(false);
+    };
+    archive(&data.pool, guild_id, event_id, state, outcome, winner).await?;
+    if let (Some((name, _)), Some(location)) = (winner, state.location()) {
+        register_node(&data.pool, guild_id, name, location, event_id).await;
+    }
+    guild.end_lorax_event(event_id);
+    settings.save(&data.pool).await?;
+    Ok(true)
+}
+
+/// Events are only ended once they're archived, so a failed write leaves the
+/// event running and its row open for another try.
+async fn archive(
+    pool: &SqlitePool,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+    state: &LoraxState,
+    outcome: LoraxOutcome,
+    winner: Option<(&str, serenity::UserId)>,
+) -> Result<(), Error> {
+    let result = lorax_history::archive_state(pool, event_id as i64, state, outcome, winner).await;
+    if let Err(e) = &result {
+        error!("Failed to archive Lorax event {} for guild {}: {}", event_id, guild_id, e);
+    }
+    result
+}
+
+/// Adds a winning name to the node inventory as a planned node.
+async fn register_node(
+    pool: &SqlitePool,
+    guild_id: serenity::GuildId,
+    name: &str,
+    location: &str,
+    event_id: u64,
+) {
+    match nodes::add(pool, guild_id, name, location, Some(event_id as i64)).await {
+        Ok(true) => {}
+        // The name was already in the inventory, so this event isn't linked to it
+        Ok(false) => warn!(
+            "Node {} was already in the inventory for guild {}, not adding it for event {}",
+            name, guild_id, event_id
+        ),
+        Err(e) => {
+            error!("Failed to add node {} to the inventory for guild {}: {}", name, guild_id, e)
+        }
+    }
+}
+
+/// How many of the top namers on the leaderboard get the winner role.
+const TOP_NAMERS: usize = 3;
+
+/// Gives the winner role to the current top namers on the leaderboard and
+/// takes it back from anyone who dropped out of them. Best effort, a missing
+/// permission shouldn't hold up the event.
+async fn update_winner_role(
+    http: &serenity::Http,
+    pool: &SqlitePool,
+    guild_id: serenity::GuildId,
+    role: Option<RoleId>,
+) {
+    let Some(role) = role else {
+        return;
+    };
+    let (top, holders) = match tokio::try_join!(
+        lorax_stats::top_namers(pool, guild_id, TOP_NAMERS),
+        lorax_stats::role_holders(pool, guild_id),
+    ) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Failed to load the Lorax top namers for guild {}: {}", guild_id, e);
+            return;
+        }
+    };
+
+    for user_id in holders.iter().filter(|user_id| !top.contains(user_id)) {
+        let reason = Some("Dropped out of the Lorax top namers");
+        if let Err(e) = http.remove_member_role(guild_id, *user_id, role, reason).await {
+            warn!(
+                "Failed to take Lorax winner role from {} in guild {}: {}",
+                user_id, guild_id, e
+            );
+        }
+    }
+    for user_id in top.iter().filter(|user_id| !holders.contains(user_id)) {
+        let reason = Some("One of the Lorax top namers");
+        if let Err(e) = http.add_member_role(guild_id, *user_id, role, reason).await {
+            warn!(
+                "Failed to give Lorax winner role to {} in guild {}: {}",
+                user_id, guild_id, e
+            );
+        }
+    }
+
+    if let Err(e) = lorax_stats::set_role_holders(pool, guild_id, &top).await {
+        error!("Failed to save the Lorax top namers for guild {}: {}", guild_id, e);
+    }
+}
+
 fn discord_timestamp(time: i64, style: TimestampStyle) -> String {
     format!("<t:{}:{}>", time, style.as_str())
 }
@@ -577,10 +2694,6 @@ impl TimestampStyle {
     }
 }
 
-const RESERVED_NAMES: &[&str] = &[
-    "sakura", "cherry", "bamboo", "maple", "pine", "palm", "cedar",
-];
-
 fn validate_tree_name(name: &str) -> Result<(), &'static str> {
     if name.len() < 3 || name.len() > 20 {
         return Err("Tree name must be between 3 and 20 characters long.");
@@ -588,78 +2701,257 @@ fn validate_tree_name(name: &str) -> Result<(), &'static str> {
     if !name.chars().all(|c| c.is_ascii_lowercase()) {
         return Err("Only lowercase ASCII letters are allowed, with no spaces.");
     }
-    if RESERVED_NAMES.contains(&name) {
-        return Err("This tree name is reserved for future use.");
+    Ok(())
+}
+
+/// Checks a member against the event's eligibility rules, or the guild's if it
+/// doesn't have its own. The error is a reason that can be shown to them.
+async fn check_eligibility(
+    data: &Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+    member: Option<&serenity::Member>,
+) -> Result<(), String> {
+    let guild_settings = data.settings.read().await.get_guild_settings(guild_id);
+    let rules: LoraxEligibility = guild_settings
+        .lorax_event(event_id)
+        .and_then(LoraxState::eligibility)
+        .cloned()
+        .unwrap_or(guild_settings.lorax_eligibility);
+    if rules.is_unrestricted() {
+        return Ok(());
+    }
+    let Some(member) = member else {
+        return Err("I couldn't check whether you can take part, please try again in a moment.".to_string());
+    };
+
+    let now = Utc::now().timestamp();
+    let days_since = |time: i64| ((now - time).max(0) / (24 * 60 * 60)) as u64;
+
+    if days_since(member.user.id.created_at().unix_timestamp()) < rules.min_account_age_days {
+        return Err(format!(
+            "Sorry, your Discord account needs to be at least {} old to take part in Lorax events.",
+            format_days(rules.min_account_age_days)
+        ));
+    }
+    let joined_at = member.joined_at.map_or(now, |t| t.unix_timestamp());
+    if days_since(joined_at) < rules.min_member_age_days {
+        return Err(format!(
+            "Sorry, you need to have been in this server for at least {} to take part in Lorax events.",
+            format_days(rules.min_member_age_days)
+        ));
+    }
+    if let Some(role) = rules.required_role {
+        if !member.roles.contains(&role) {
+            return Err(format!(
+                "Sorry, you need the <@&{}> role to take part in Lorax events.",
+                role
+            ));
+        }
     }
     Ok(())
 }
 
-/// Cancels the current Lorax event.
+/// Which running events a command can act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventPhase {
+    Any,
+    Submissions,
+    Voting,
+}
+
+impl EventPhase {
+    fn matches(self, state: &LoraxState) -> bool {
+        match self {
+            EventPhase::Any => !matches!(state, LoraxState::Idle),
+            EventPhase::Submissions => matches!(state, LoraxState::Submissions { .. }),
+            EventPhase::Voting => {
+                matches!(state, LoraxState::Voting { .. } | LoraxState::TieBreaker { .. })
+            }
+        }
+    }
+}
+
+/// Picks the event a command is about: the one that was asked for, or the only
+/// running event in the right phase. The error can be shown to the user.
+fn resolve_event(
+    guild: &GuildSettings,
+    event: Option<u64>,
+    phase: EventPhase,
+) -> Result<u64, String> {
+    if let Some(event_id) = event {
+        return match guild.lorax_event(event_id) {
+            Some(state) if phase.matches(state) => Ok(event_id),
+            Some(state) => Err(format!(
+                "Lorax event #{} isn't at that stage, it's currently {}.",
+                event_id,
+                state.phase_name()
+            )),
+            None => Err(format!("There's no running Lorax event #{}.", event_id)),
+        };
+    }
+
+    let mut candidates = guild
+        .lorax_events
+        .iter()
+        .filter(|(_, state)| phase.matches(state))
+        .map(|(event_id, _)| *event_id);
+    match (candidates.next(), candidates.next()) {
+        (Some(event_id), None) => Ok(event_id),
+        (Some(_), Some(_)) => Err(
+            "There's more than one Lorax event running, pick one with the `event` option."
+                .to_string(),
+        ),
+        (None, _) => Err(match phase {
+            EventPhase::Any => "There's no active Lorax event right now.",
+            EventPhase::Submissions => "There's no Lorax event taking submissions right now.",
+            EventPhase::Voting => "There's no Lorax event open for voting right now.",
+        }
+        .to_string()),
+    }
+}
+
+async fn event_autocomplete(
+    ctx: Context<'_>,
+    partial: &str,
+) -> impl Iterator<Item = serenity::AutocompleteChoice> {
+    let guild_settings = match ctx.guild_id() {
+        Some(guild_id) => ctx.data().settings.read().await.get_guild_settings(guild_id),
+        None => GuildSettings::default(),
+    };
+    let partial = partial.to_lowercase();
+
+    guild_settings
+        .lorax_events
+        .iter()
+        .filter(|(_, state)| EventPhase::Any.matches(state))
+        .map(|(event_id, state)| {
+            let label = format!(
+                "#{} {} ({})",
+                event_id,
+                state.location().unwrap_or_default(),
+                state.phase_name()
+            );
+            (label, *event_id)
+        })
+        .filter(|(label, _)| label.to_lowercase().contains(&partial))
+        .map(|(label, event_id)| serenity::AutocompleteChoice::new(label, event_id))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Cancels a running Lorax event.
 #[poise::command(slash_command, required_permissions = "MANAGE_GUILD")]
-pub async fn cancel(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn cancel(
+    ctx: Context<'_>,
+    #[description = "Event to cancel, if more than one is running"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap();
-    let mut settings = ctx.data().settings.write().await;
     let pool = Arc::clone(&ctx.data().pool);
+    // Closing every campaign post can take a while
+    ctx.defer().await?;
 
-    match &settings.guilds.get(&guild_id).unwrap().lorax_state {
-        LoraxState::Idle => {
-            ctx.say("No active Lorax event to cancel.").await?;
-            return Ok(());
-        }
-        _ => {
-            let channel_id = settings
-                .guilds
-                .get(&guild_id)
-                .unwrap()
-                .lorax_channel
-                .unwrap();
-            channel_id
-                .say(
-                    &ctx,
-                    "🚫 The current Lorax event has been cancelled by an administrator.",
-                )
+    // Ended first, the announcements and campaign posts are tidied up
+    // without holding the lock
+    let (guild, channel_id, event_id, state) = {
+        let mut settings = ctx.data().settings.write().await;
+        let guild = settings.guilds.entry(guild_id).or_default();
+        let event_id = match resolve_event(guild, event, EventPhase::Any) {
+            Ok(event_id) => event_id,
+            Err(reason) => {
+                ctx.say(reason).await?;
+                return Ok(());
+            }
+        };
+        let Some(channel_id) = guild.lorax_channel else {
+            ctx.say("❌ There's no Lorax channel set, use `/lorax set_channel` first.")
                 .await?;
-            settings.guilds.get_mut(&guild_id).unwrap().lorax_state = LoraxState::Idle;
-            settings.save(&pool).await?;
-            ctx.say("Alright, the current Lorax event has been cancelled and reset. 🛑")
+            return Ok(());
+        };
+        let state = guild.lorax_events[&event_id].clone();
+        if archive(&pool, guild_id, event_id, &state, LoraxOutcome::Cancelled, None)
+            .await
+            .is_err()
+        {
+            ctx.say("❌ The event couldn't be archived, so it's still running. Try again in a bit.")
                 .await?;
+            return Ok(());
         }
-    }
+        guild.end_lorax_event(event_id);
+        let guild = guild.clone();
+        settings.save(&pool).await?;
+        (guild, channel_id, event_id, state)
+    };
+
+    channel_id
+        .say(
+            &ctx,
+            copy::message(
+                &guild,
+                LoraxMessage::Cancelled,
+                &[("location", state.location().unwrap_or_default())],
+            ),
+        )
+        .await?;
+    announcement::update(ctx.http(), channel_id, event_id, &state, true, guild.lorax_locale)
+        .await;
+    campaign::close_all(ctx.http(), &state).await;
+    ctx.say(format!(
+        "Alright, Lorax event #{} has been cancelled and reset. 🛑",
+        event_id
+    ))
+    .await?;
     Ok(())
 }
 
-/// Adjusts the duration of the current Lorax event phase.
+/// Adjusts the duration of a Lorax event's current phase.
 #[poise::command(slash_command, required_permissions = "MANAGE_GUILD")]
 pub async fn duration(
     ctx: Context<'_>,
     #[description = "Minutes to adjust (positive to extend, negative to reduce)"] minutes: i64,
+    #[description = "Event to adjust, if more than one is running"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
 ) -> Result<(), Error> {
-    duration_impl(ctx, minutes).await
+    duration_impl(ctx, minutes, event).await
 }
 
 async fn duration_impl(
     ctx: Context<'_>,
     minutes: i64,
+    event: Option<u64>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap();
     let mut settings = ctx.data().settings.write().await;
     let pool = Arc::clone(&ctx.data().pool);
-    let guild = settings.guilds.get_mut(&guild_id).unwrap();
+    let guild = settings.guilds.entry(guild_id).or_default();
+    let event_id = match resolve_event(guild, event, EventPhase::Any) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
 
     // Extract the needed data before match statement to avoid borrow conflicts
     let channel_id = guild.lorax_channel.unwrap();
-    let thread_id = match &guild.lorax_state {
-        LoraxState::Voting { thread_id, .. } | LoraxState::TieBreaker { thread_id, .. } => *thread_id,
+    let thread_id = match guild.lorax_event(event_id) {
+        Some(LoraxState::Voting { thread_id, .. } | LoraxState::TieBreaker { thread_id, .. }) => *thread_id,
         _ => None,
     };
 
-    match &mut guild.lorax_state {
-        LoraxState::Idle => {
+    match guild.lorax_event_mut(event_id) {
+        None | Some(LoraxState::Idle) => {
             ctx.say("No active Lorax event to modify.").await?;
         }
-        LoraxState::Submissions { end_time, message_id, .. }
-        | LoraxState::Voting { end_time, message_id, .. }
-        | LoraxState::TieBreaker { end_time, message_id, .. } => {
+        Some(
+            LoraxState::Submissions { end_time, reminders_sent, location, .. }
+            | LoraxState::Voting { end_time, reminders_sent, location, .. }
+            | LoraxState::TieBreaker { end_time, reminders_sent, location, .. },
+        ) => {
+            let location = location.clone();
             let current_time = Utc::now().timestamp();
             let new_end = *end_time + (minutes * 60);
 
@@ -671,60 +2963,43 @@ async fn duration_impl(
                 return Ok(());
             }
 
-            let msg_id = *message_id;
-            
             // Update the end time
             *end_time = new_end;
 
+            // Re-arm any reminders that are back in the future
+            reminders_sent.retain(|&minutes| new_end - current_time <= (minutes * 60) as i64);
+
             // Save state changes
+            let guild = settings.get_guild_settings(guild_id);
             settings.save(&pool).await?;
             drop(settings);  // Drop settings early to avoid borrowing conflicts
 
-            // Edit the original announcement message
-            if let Ok(mut msg) = channel_id.message(&ctx, msg_id).await {
-                let new_content = msg.content.clone()
-                    .replace(
-                        msg.content.rsplit("<t:").next().unwrap_or("").rsplit_once(">").map(|(_, rest)| rest).unwrap_or(""),
-                        &format!("\nVoting ends {}.", discord_timestamp(new_end, TimestampStyle::ShortDateTime))
-                    );
-                
-                if let Err(e) = msg.edit(&ctx, serenity::EditMessage::new().content(new_content)).await {
-                    warn!("Failed to edit announcement message: {}", e);
-                }
-            }
+            announcement::refresh(ctx.http(), ctx.data(), guild_id, event_id).await;
 
             // Send notification message
-            let message = if minutes > 0 {
-                format!(
-                    "⏰ The current phase has been extended by {} minutes! More time to get involved.",
-                    minutes
-                )
-            } else {
-                format!(
-                    "⏰ The current phase has been shortened by {} minutes. Don't miss out!",
-                    minutes.abs()
-                )
-            };
-
-            channel_id
-                .say(
-                    &ctx,
-                    format!(
-                        "{} New end time: {}",
-                        message,
-                        discord_timestamp(new_end, TimestampStyle::ShortDateTime)
-                    ),
-                )
-                .await?;
+            let new_end_time = discord_timestamp(new_end, TimestampStyle::ShortDateTime);
+            let message = copy::message(
+                &guild,
+                if minutes > 0 {
+                    LoraxMessage::Extended
+                } else {
+                    LoraxMessage::Shortened
+                },
+                &[
+                    ("location", location.as_str()),
+                    ("minutes", &minutes.abs().to_string()),
+                    ("end", &new_end_time),
+                ],
+            );
+            channel_id.say(&ctx, message).await?;
 
             // Also post in campaign thread if we're in voting phase
             if let Some(thread_id) = thread_id {
+                let phrases = copy::phrases(guild.lorax_locale);
                 let _ = thread_id.send_message(
                     &ctx,
-                    serenity::CreateMessage::new().content(format!(
-                        "⏰ Voting duration has been adjusted! New end time: {}",
-                        discord_timestamp(new_end, TimestampStyle::ShortDateTime)
-                    ))
+                    serenity::CreateMessage::new()
+                        .content(copy::render(phrases.thread_duration, &[("end", &new_end_time)]))
                 ).await;
             }
 
@@ -734,24 +3009,63 @@ async fn duration_impl(
     Ok(())
 }
 
-/// Submits a tree name suggestion.
+/// Submits a tree name suggestion, or changes yours.
 #[poise::command(slash_command, ephemeral)]
 pub async fn submit(
     ctx: Context<'_>,
     #[description = "Your tree name suggestion (lowercase letters only)"] name: String,
+    #[description = "What the name means, or why you picked it"]
+    #[max_length = 300]
+    meaning: Option<String>,
+    #[description = "Your pitch for the name, pinned on its campaign post"]
+    #[max_length = 1000]
+    statement: Option<String>,
+    #[description = "Event to submit to, if more than one is taking submissions"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
 ) -> Result<(), Error> {
     let pool = Arc::clone(&ctx.data().pool);
     let guild_id = ctx.guild_id().unwrap();
     let user_id = ctx.author().id;
     let tree_name = name.to_lowercase();
 
+    let guild_settings = ctx.data().settings.read().await.get_guild_settings(guild_id);
+    let event_id = match resolve_event(&guild_settings, event, EventPhase::Submissions) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
+
+    let member = ctx.author_member().await;
+    if let Err(reason) = check_eligibility(ctx.data(), guild_id, event_id, member.as_deref()).await {
+        ctx.say(reason).await?;
+        return Ok(());
+    }
+
     if let Err(msg) = validate_tree_name(&tree_name) {
         ctx.say(format!("Oops! {}", msg)).await?;
         return Ok(());
     }
 
-    let metrics_client = MetricsClient::new();
-    let existing_trees = metrics_client.fetch_existing_trees().await?;
+    if let Some(conflict) = lorax_names::conflict(&pool, guild_id, &tree_name).await? {
+        ctx.say(match conflict {
+            NameConflict::Reserved => "This tree name is reserved for future use.",
+            NameConflict::Blocked => "This tree name contains a blocked word.",
+            NameConflict::PastWinner => "This tree name already won an earlier Lorax event.",
+            NameConflict::ExistingNode => "This tree name is already in use by an existing node.",
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let existing_trees = ctx
+        .data()
+        .datasources
+        .default_client()
+        .fetch_existing_trees()
+        .await?;
     if existing_trees.contains(&tree_name) {
         ctx.say("This tree name is already in use by an existing node.")
             .await?;
@@ -760,29 +3074,83 @@ pub async fn submit(
 
     let mut settings = ctx.data().settings.write().await;
 
-    if let LoraxState::Submissions { submissions, .. } =
-        &mut settings.guilds.get_mut(&guild_id).unwrap().lorax_state
+    // A name can only be up for one node at a time
+    let taken_elsewhere = settings.guilds.get(&guild_id).is_some_and(|guild| {
+        guild.lorax_events.iter().any(|(id, event)| {
+            *id != event_id && event.submissions().iter().any(|s| s.name == tree_name)
+        })
+    });
+    if taken_elsewhere {
+        ctx.say("This tree name has already been submitted to another Lorax event.")
+            .await?;
+        return Ok(());
+    }
+
+    if let Some(LoraxState::Submissions {
+        submissions,
+        max_submissions_per_user,
+        end_time,
+        ..
+    }) = settings.guilds.get_mut(&guild_id).unwrap().lorax_event_mut(event_id)
     {
-        if submissions.values().any(|n| n == &tree_name) {
+        if *end_time <= Utc::now().timestamp() {
+            ctx.say(SUBMISSIONS_CLOSED).await?;
+            return Ok(());
+        }
+
+        if submissions
+            .iter()
+            .any(|s| s.name == tree_name && s.submitter != user_id)
+        {
             ctx.say("This tree name has already been submitted by someone else.")
                 .await?;
             return Ok(());
         }
 
-        let msg = match submissions.entry(user_id) {
-            Entry::Vacant(vacancy) => {
-                vacancy.insert(tree_name);
-                "Awesome! I've updated your submission. Good luck! 🌲"
-            }
-
-            Entry::Occupied(mut occupied) => {
-                occupied.insert(tree_name);
-                "Thanks for your submission! Good luck! 🌴"
+        let mut submission = Submission {
+            submitter: user_id,
+            name: tree_name,
+            meaning: meaning
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty()),
+            statement: statement
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+        };
+        let own_count = submissions.iter().filter(|s| s.submitter == user_id).count();
+        let existing = submissions
+            .iter()
+            .position(|s| s.submitter == user_id && s.name == submission.name)
+            // With one name each, submitting again swaps it out
+            .or_else(|| {
+                (*max_submissions_per_user <= 1)
+                    .then(|| submissions.iter().position(|s| s.submitter == user_id))
+                    .flatten()
+            });
+
+        let msg = if let Some(index) = existing {
+            // Resubmitting a name keeps its statement unless there's a new one
+            if submissions[index].name == submission.name && submission.statement.is_none() {
+                submission.statement = submissions[index].statement.take();
             }
+            submissions[index] = submission;
+            "Awesome! I've updated your submission. Good luck! 🌲"
+        } else if own_count < *max_submissions_per_user as usize {
+            submissions.push(submission);
+            "Thanks for your submission! Good luck! 🌴"
+        } else {
+            ctx.say(format!(
+                "You've already submitted {} names, which is the most for this event. Use `/lorax withdraw` to swap one out.",
+                max_submissions_per_user
+            ))
+            .await?;
+            return Ok(());
         };
 
         settings.save(&pool).await?;
+        drop(settings);
         ctx.say(msg).await?;
+        announcement::refresh(ctx.http(), ctx.data(), guild_id, event_id).await;
     } else {
         ctx.say("Submissions are not currently open.").await?;
     }
@@ -790,116 +3158,232 @@ pub async fn submit(
     Ok(())
 }
 
-/// Votes for a tree name.
+/// Sets the statement pinned on the campaign post of a name you submitted.
 #[poise::command(slash_command, ephemeral)]
-pub async fn vote(
+pub async fn statement(
     ctx: Context<'_>,
-    #[description = "Page number"] page: Option<u32>,
-    #[description = "Search for specific names"] search: Option<String>,
+    #[description = "Your pitch for the name, leave empty to remove it"]
+    #[max_length = 1000]
+    text: Option<String>,
+    #[description = "Which name it's for, if you submitted more than one"] name: Option<String>,
+    #[description = "Event the name is in, if more than one is running"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
 ) -> Result<(), Error> {
+    let pool = Arc::clone(&ctx.data().pool);
     let guild_id = ctx.guild_id().unwrap();
-    info!("Processing vote command for guild {}", guild_id);
     let user_id = ctx.author().id;
-    let settings = ctx.data().settings.read().await;
-    let page = page.unwrap_or(1).max(1);
-    const ITEMS_PER_PAGE: usize = 10;
 
-    if let Some(guild) = settings.guilds.get(&guild_id) {
-        if let LoraxState::Voting {
-            options,
-            votes,
-            end_time,
-            submissions,
-            ..
+    let mut settings = ctx.data().settings.write().await;
+    let guild = settings.guilds.entry(guild_id).or_default();
+    let locale = guild.lorax_locale;
+    let event_id = match resolve_event(guild, event, EventPhase::Any) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
         }
-        | LoraxState::TieBreaker {
-            options,
-            votes,
-            end_time,
-            submissions,
-            ..
-        } = &guild.lorax_state
-        {
-            if Utc::now().timestamp() > *end_time {
-                ctx.say("Voting period has ended.").await?;
+    };
+    let posts = guild
+        .lorax_event(event_id)
+        .and_then(LoraxState::campaign_posts)
+        .cloned()
+        .unwrap_or_default();
+    let Some(
+        LoraxState::Submissions { submissions, .. }
+        | LoraxState::Voting { submissions, .. }
+        | LoraxState::TieBreaker { submissions, .. },
+    ) = guild.lorax_event_mut(event_id)
+    else {
+        ctx.say("No active event.").await?;
+        return Ok(());
+    };
+
+    let own: Vec<usize> = submissions
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.submitter == user_id)
+        .map(|(i, _)| i)
+        .collect();
+    let index = match (name.map(|n| n.trim().to_lowercase()), own.as_slice()) {
+        (_, []) => {
+            ctx.say("You haven't submitted a name to this event.").await?;
+            return Ok(());
+        }
+        (None, [only]) => *only,
+        (None, _) => {
+            ctx.say(format!(
+                "You've submitted more than one name, which is the statement for? {}",
+                own.iter()
+                    .map(|&i| format!("`{}`", submissions[i].name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+            .await?;
+            return Ok(());
+        }
+        (Some(name), _) => match own.iter().find(|&&i| submissions[i].name == name) {
+            Some(&index) => index,
+            None => {
+                ctx.say(format!("You haven't submitted `{}`.", name)).await?;
                 return Ok(());
             }
+        },
+    };
 
-            let filtered_options: Vec<_> = if let Some(ref search) = search {
-                options
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, name)| name.contains(&search.to_lowercase()))
-                    .collect()
-            } else {
-                options.iter().enumerate().collect()
-            };
+    let submission = &mut submissions[index];
+    submission.statement = text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    let submission = submission.clone();
+    settings.save(&pool).await?;
+    drop(settings);
 
-            let total_pages = (filtered_options.len() + ITEMS_PER_PAGE - 1) / ITEMS_PER_PAGE;
-            let start_idx = (page as usize - 1) * ITEMS_PER_PAGE;
-            let end_idx = start_idx + ITEMS_PER_PAGE;
-            let page_options = &filtered_options
-                [start_idx.min(filtered_options.len())..end_idx.min(filtered_options.len())];
+    let post = posts.get(&submission.name).copied();
+    if let Some(post) = post {
+        if let Err(e) =
+            campaign::update_post(ctx.http(), post, copy::phrases(locale), &submission).await
+        {
+            warn!("Failed to update campaign post for `{}`: {}", submission.name, e);
+        }
+    }
 
-            let mut components = Vec::new();
+    let msg = match (&submission.statement, post) {
+        (None, _) => format!("Okay, I've removed your statement for `{}`.", submission.name),
+        (Some(_), Some(post)) => format!(
+            "📣 Your statement for `{}` is pinned on <#{}>.",
+            submission.name, post
+        ),
+        (Some(_), None) => format!(
+            "📣 Saved your statement for `{}`. It'll be pinned on the name's campaign post when voting starts.",
+            submission.name
+        ),
+    };
+    ctx.say(msg).await?;
 
-            let select_options = futures::future::join_all(page_options.iter().map(|(i, name)| {
-                let submissions = submissions.clone();
-                async move {
-                    let submitter_name = get_submitter_name(
-                        ctx.serenity_context(),
-                        *submissions.iter().find(|(_, n)| n == name).unwrap().0,
-                    )
-                    .await;
-                    CreateSelectMenuOption::new(
-                        format!("{} - Submitted by {}", name, submitter_name),
-                        i.to_string(),
-                    )
-                    .default_selection(votes.get(&user_id) == Some(i))
-                }
-            }))
-            .await;
+    Ok(())
+}
 
-            if !select_options.is_empty() {
-                let select_menu = CreateSelectMenu::new(
-                    "vote_select",
-                    CreateSelectMenuKind::String {
-                        options: select_options,
-                    },
-                )
-                .custom_id(format!("vote_select_{}", page))
-                .placeholder("Select a tree name to vote for")
-                .max_values(1);
+/// Withdraws one of your tree name suggestions.
+#[poise::command(slash_command, ephemeral)]
+pub async fn withdraw(
+    ctx: Context<'_>,
+    #[description = "Which name to withdraw, if you submitted more than one"] name: Option<String>,
+    #[description = "Event to withdraw from, if more than one is taking submissions"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+) -> Result<(), Error> {
+    let pool = Arc::clone(&ctx.data().pool);
+    let guild_id = ctx.guild_id().unwrap();
+    let user_id = ctx.author().id;
 
-                components.push(CreateActionRow::SelectMenu(select_menu));
-            }
+    let mut settings = ctx.data().settings.write().await;
+    let guild = settings.guilds.entry(guild_id).or_default();
+    let event_id = match resolve_event(guild, event, EventPhase::Any) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
 
-            if total_pages > 1 {
-                let mut nav_buttons = Vec::new();
-                if page > 1 {
-                    nav_buttons.push(
-                        CreateButton::new(format!("vote_page_{}", page - 1))
-                            .label("Previous")
-                            .style(ButtonStyle::Secondary),
-                    );
-                }
-                if page < total_pages as u32 {
-                    nav_buttons.push(
-                        CreateButton::new(format!("vote_page_{}", page + 1))
-                            .label("Next")
-                            .style(ButtonStyle::Secondary),
-                    );
+    match guild.lorax_event_mut(event_id) {
+        Some(LoraxState::Submissions { end_time, .. }) if *end_time <= Utc::now().timestamp() => {
+            ctx.say(SUBMISSIONS_CLOSED).await?;
+        }
+        Some(LoraxState::Submissions { submissions, .. }) => {
+            let own: Vec<usize> = submissions
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.submitter == user_id)
+                .map(|(i, _)| i)
+                .collect();
+            let index = match (name.map(|n| n.trim().to_lowercase()), own.as_slice()) {
+                (_, []) => {
+                    ctx.say("You haven't submitted a name to withdraw.").await?;
+                    return Ok(());
                 }
-                if !nav_buttons.is_empty() {
-                    components.push(CreateActionRow::Buttons(nav_buttons));
+                (None, [only]) => *only,
+                (None, _) => {
+                    ctx.say(format!(
+                        "You've submitted more than one name, which should I withdraw? {}",
+                        own.iter()
+                            .map(|&i| format!("`{}`", submissions[i].name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                    .await?;
+                    return Ok(());
                 }
-            }
+                (Some(name), _) => match own.iter().find(|&&i| submissions[i].name == name) {
+                    Some(&index) => index,
+                    None => {
+                        ctx.say(format!("You haven't submitted `{}`.", name)).await?;
+                        return Ok(());
+                    }
+                },
+            };
+            let withdrawn = submissions.remove(index);
 
-            let header = format!(
-                "🗳️ **Voting is now open!**\n\nSelect your favorite tree name from the menu below.\n\n_Voting ends {}_\n",
-                discord_timestamp(*end_time, TimestampStyle::Relative)
-            );
+            settings.save(&pool).await?;
+            drop(settings);
+            ctx.say(format!(
+                "Okay, I've withdrawn `{}`. You can still submit another name while submissions are open.",
+                withdrawn.name
+            ))
+            .await?;
+            announcement::refresh(ctx.http(), ctx.data(), guild_id, event_id).await;
+        }
+        None | Some(LoraxState::Idle) => {
+            ctx.say("Submissions are not currently open.").await?;
+        }
+        Some(_) => {
+            ctx.say("Voting has already started, so submissions can't be withdrawn anymore. Ask a moderator if your name needs to come off the ballot.")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Votes for a tree name.
+#[poise::command(slash_command, ephemeral)]
+pub async fn vote(
+    ctx: Context<'_>,
+    #[description = "Page number"] page: Option<u32>,
+    #[description = "Search for specific names"] search: Option<String>,
+    #[description = "Event to vote in, if more than one is open for voting"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    info!("Processing vote command for guild {}", guild_id);
+    let user_id = ctx.author().id;
+
+    let guild_settings = ctx.data().settings.read().await.get_guild_settings(guild_id);
+    let event_id = match resolve_event(&guild_settings, event, EventPhase::Voting) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
+
+    let member = ctx.author_member().await;
+    if let Err(reason) = check_eligibility(ctx.data(), guild_id, event_id, member.as_deref()).await {
+        ctx.say(reason).await?;
+        return Ok(());
+    }
 
+    match render_ballot(
+        ctx.serenity_context(),
+        event_id,
+        &guild_settings.lorax_events[&event_id],
+        user_id,
+        page.unwrap_or(1),
+        search.as_deref(),
+    )
+    .await
+    {
+        Ok((header, components)) => {
             ctx.send(
                 CreateReply::default()
                     .content(header)
@@ -907,12 +3391,310 @@ pub async fn vote(
                     .ephemeral(true),
             )
             .await?;
-        } else {
-            ctx.say("⚠️ Voting is not currently open. Stay tuned!")
-                .await?;
         }
+        Err(msg) => {
+            ctx.say(msg).await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Voting opens a little after the submission deadline, names are fixed from
+// the deadline on
+const SUBMISSIONS_CLOSED: &str = "Submissions for this event have closed, voting opens shortly.";
+const VOTES_PER_PAGE: usize = 10;
+const VOIDED_BALLOT: &str = "Your ballot in this event was voided by a moderator.";
+// Custom IDs are capped at 100 characters, leave room for the prefix, event
+// and page
+const MAX_SEARCH_LEN: usize = 60;
+
+/// Builds one page of the ballot. Shared by `/lorax vote` and the ballot
+/// components, which carry the event, page and search filter in their custom
+/// ID as `<prefix><event>:<page>:<search>`.
+async fn render_ballot(
+    ctx: &serenity::Context,
+    event_id: u64,
+    state: &LoraxState,
+    user_id: serenity::UserId,
+    page: u32,
+    search: Option<&str>,
+) -> Result<(String, Vec<CreateActionRow>), &'static str> {
+    let (LoraxState::Voting {
+        options,
+        votes,
+        end_time,
+        submissions,
+        voting_method,
+        ballots,
+        campaign_posts,
+        voided,
+        ..
+    }
+    | LoraxState::TieBreaker {
+        options,
+        votes,
+        end_time,
+        submissions,
+        voting_method,
+        ballots,
+        campaign_posts,
+        voided,
+        ..
+    }) = state
+    else {
+        return Err("⚠️ Voting is not currently open. Stay tuned!");
+    };
+
+    if Utc::now().timestamp() > *end_time {
+        return Err("Voting period has ended.");
+    }
+    if voided.contains(&user_id) {
+        return Err(VOIDED_BALLOT);
+    }
+
+    let ballot = ballots.get(&user_id).map(Vec::as_slice).unwrap_or_default();
+    let BallotPage {
+        options: page_options,
+        page,
+        total_pages,
+        search,
+        matches,
+    } = ballot_page(options, *voting_method, ballot, page, search);
+    let ballot_id = |prefix: &str, page: usize| {
+        format!(
+            "{}{}:{}:{}",
+            prefix,
+            event_id,
+            page,
+            search.as_deref().unwrap_or_default()
+        )
+    };
+
+    let mut components = Vec::new();
+
+    let select_options = futures::future::join_all(page_options.iter().map(|&(i, name)| async move {
+        let submission = submissions
+            .iter()
+            .find(|submission| submission.name == *name)
+            .unwrap();
+        let submitter_name = get_submitter_name(ctx, submission.submitter).await;
+        let option = CreateSelectMenuOption::new(
+            format!("{} - Submitted by {}", name, submitter_name),
+            i.to_string(),
+        )
+        .default_selection(votes.get(&user_id) == Some(&i) || ballot.contains(&i));
+        // Option descriptions are capped at 100 characters
+        match &submission.meaning {
+            Some(meaning) => option.description(truncate(meaning.clone(), 100)),
+            None => option,
+        }
+    }))
+    .await;
+
+    if !select_options.is_empty() {
+        let option_count = select_options.len() as u8;
+        let select_menu = CreateSelectMenu::new(
+            ballot_id("vote_select_", page),
+            CreateSelectMenuKind::String {
+                options: select_options,
+            },
+        );
+        let select_menu = match voting_method {
+            VotingMethod::Single => select_menu
+                .placeholder("Select a tree name to vote for")
+                .max_values(1),
+            VotingMethod::InstantRunoff => select_menu
+                .placeholder(format!("Pick your #{} choice", ballot.len() + 1))
+                .max_values(1),
+            // Leaving the menu empty withdraws approval for this page
+            VotingMethod::Approval => select_menu
+                .placeholder("Select every tree name you approve of")
+                .min_values(0)
+                .max_values(option_count),
+        };
+
+        components.push(CreateActionRow::SelectMenu(select_menu));
+    }
+
+    let mut buttons = Vec::new();
+    if page > 1 {
+        buttons.push(
+            CreateButton::new(ballot_id("vote_page_", page - 1))
+                .label("Previous")
+                .style(ButtonStyle::Secondary),
+        );
+    }
+    if page < total_pages {
+        buttons.push(
+            CreateButton::new(ballot_id("vote_page_", page + 1))
+                .label("Next")
+                .style(ButtonStyle::Secondary),
+        );
+    }
+    if !ballot.is_empty() {
+        let label = match voting_method {
+            VotingMethod::Approval => "Clear approvals",
+            _ => "Clear ranking",
+        };
+        buttons.push(
+            CreateButton::new(ballot_id("vote_clear_", page))
+                .label(label)
+                .style(ButtonStyle::Danger),
+        );
+    }
+    if !buttons.is_empty() {
+        components.push(CreateActionRow::Buttons(buttons));
+    }
+
+    let instructions = match voting_method {
+        VotingMethod::Single => "Select your favorite tree name from the menu below.",
+        VotingMethod::InstantRunoff => {
+            "Rank the tree names in order of preference, picking one at a time from the menu below. \
+            Rank as many or as few as you like!"
+        }
+        VotingMethod::Approval => {
+            "Select every tree name you'd be happy with from the menu below. \
+            The name approved by the most people wins!"
+        }
+    };
+    let mut header = format!(
+        "🗳️ **Voting is now open for our new {} node!**\n\n{}\n\n_Voting ends {}_\n",
+        state.location().unwrap_or_default(),
+        instructions,
+        discord_timestamp(*end_time, TimestampStyle::Relative)
+    );
+    if !ballot.is_empty() {
+        match voting_method {
+            VotingMethod::Approval => {
+                header.push_str(&format!(
+                    "\n**You approve of:** {}\n",
+                    ballot
+                        .iter()
+                        .map(|option| format!("`{}`", options[*option]))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            _ => {
+                header.push_str("\n**Your ranking:**\n");
+                for (rank, option) in ballot.iter().enumerate() {
+                    header.push_str(&format!("{}. `{}`\n", rank + 1, options[*option]));
+                }
+            }
+        }
+    }
+    // Select menus can't hold links, so the names' posts go above them
+    let post_links: Vec<_> = page_options
+        .iter()
+        .filter_map(|(_, name)| Some(format!("`{}` <#{}>", name, campaign_posts.get(*name)?)))
+        .collect();
+    if !post_links.is_empty() {
+        header.push_str(&format!("\n💬 **Campaign posts:** {}\n", post_links.join(" • ")));
+    }
+    if total_pages > 1 {
+        header.push_str(&format!("\nPage {}/{}", page, total_pages));
+    }
+    if let Some(search) = search {
+        header.push_str(&format!("\nShowing names matching `{}`", search));
+    } else if matches == 0 && ballot.is_empty() {
+        header.push_str("\nNo names to vote on.");
+    }
+
+    Ok((header, components))
+}
+
+struct BallotPage<'a> {
+    options: Vec<(usize, &'a String)>,
+    page: usize,
+    total_pages: usize,
+    search: Option<String>,
+    /// Options matching the search across every page.
+    matches: usize,
+}
+
+/// Works out which options are on a page of the ballot. The select handler
+/// needs the same answer as `render_ballot` to know which approvals a
+/// submitted menu replaces.
+fn ballot_page<'a>(
+    options: &'a [String],
+    voting_method: VotingMethod,
+    ballot: &[usize],
+    page: u32,
+    search: Option<&str>,
+) -> BallotPage<'a> {
+    let search: Option<String> = search
+        .map(|s| s.to_lowercase().chars().take(MAX_SEARCH_LEN).collect())
+        .filter(|s: &String| !s.is_empty());
+
+    // Names that are already ranked drop out of the menu, the next pick
+    // always goes to the bottom of the ranking.
+    let filtered_options: Vec<_> = options
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| search.as_ref().map_or(true, |search| name.contains(search.as_str())))
+        .filter(|(i, _)| voting_method != VotingMethod::InstantRunoff || !ballot.contains(i))
+        .collect();
+
+    let total_pages = ((filtered_options.len() + VOTES_PER_PAGE - 1) / VOTES_PER_PAGE).max(1);
+    let page = (page.max(1) as usize).min(total_pages);
+    let start_idx = (page - 1) * VOTES_PER_PAGE;
+    let end_idx = (start_idx + VOTES_PER_PAGE).min(filtered_options.len());
+
+    BallotPage {
+        matches: filtered_options.len(),
+        options: filtered_options[start_idx..end_idx].to_vec(),
+        page,
+        total_pages,
+        search,
     }
+}
+
+/// Splits the `<event>:<page>:<search>` suffix of a ballot component's custom
+/// ID.
+fn parse_ballot_id(id: &str) -> (u64, u32, &str) {
+    let (event_id, rest) = id.split_once(':').unwrap_or((id, ""));
+    let (page, search) = rest.split_once(':').unwrap_or((rest, ""));
+    (event_id.parse().unwrap_or_default(), page.parse().unwrap_or(1), search)
+}
+
+/// Re-renders the ballot in place, optionally with a notice above it.
+async fn update_ballot(
+    ctx: &serenity::Context,
+    component: &ComponentInteraction,
+    data: &Data,
+    event_id: u64,
+    page: u32,
+    search: &str,
+    notice: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = component.guild_id.unwrap();
+    let state = data
+        .settings
+        .read()
+        .await
+        .get_guild_settings(guild_id)
+        .lorax_event(event_id)
+        .cloned()
+        .unwrap_or_default();
+
+    let message = match render_ballot(ctx, event_id, &state, component.user.id, page, Some(search))
+        .await
+    {
+        Ok((header, components)) => CreateInteractionResponseMessage::default()
+            .content(match notice {
+                Some(notice) => format!("{}\n\n{}", notice, header),
+                None => header,
+            })
+            .components(components),
+        Err(msg) => CreateInteractionResponseMessage::default()
+            .content(msg)
+            .components(vec![]),
+    };
 
+    component
+        .create_response(ctx, serenity::CreateInteractionResponse::UpdateMessage(message))
+        .await?;
     Ok(())
 }
 
@@ -934,37 +3716,123 @@ pub async fn handle_button(
     let pool = Arc::clone(&data.pool);
     debug!("Handling button interaction: {}", component.data.custom_id);
 
-    if component.data.custom_id.starts_with("vote_page_") {
-        if let Ok(_page) = component.data.custom_id[10..].parse::<u32>() {
-            let builder = serenity::CreateInteractionResponse::UpdateMessage(
-                CreateInteractionResponseMessage::default()
-                    .content("Loading...".to_string())
-                    .ephemeral(true),
-            );
-            component.create_response(ctx, builder).await?;
+    if let Some(event_id) = announcement::vote_button_event(&component.data.custom_id) {
+        let guild_id = component.guild_id.unwrap();
+        let state = data
+            .settings
+            .read()
+            .await
+            .get_guild_settings(guild_id)
+            .lorax_event(event_id)
+            .cloned()
+            .unwrap_or_default();
+        let message = match check_eligibility(&data, guild_id, event_id, component.member.as_ref()).await {
+            Err(reason) => CreateInteractionResponseMessage::default().content(reason),
+            Ok(()) => match render_ballot(ctx, event_id, &state, component.user.id, 1, None).await {
+                Ok((header, components)) => CreateInteractionResponseMessage::default()
+                    .content(header)
+                    .components(components),
+                Err(msg) => CreateInteractionResponseMessage::default().content(msg),
+            },
+        };
+        component
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::Message(message.ephemeral(true)),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    if let Some(ballot_id) = component.data.custom_id.strip_prefix("vote_page_") {
+        let (event_id, page, search) = parse_ballot_id(ballot_id);
+        return update_ballot(ctx, component, &data, event_id, page, search, None).await;
+    }
+
+    if let Some(ballot_id) = component.data.custom_id.strip_prefix("vote_clear_") {
+        let (event_id, page, search) = parse_ballot_id(ballot_id);
+        let guild_id = component.guild_id.unwrap();
+        let mut voting_method = VotingMethod::default();
+        {
+            let mut settings = data.settings.write().await;
+            if let Some(guild) = settings.guilds.get_mut(&guild_id) {
+                if let Some(
+                    LoraxState::Voting {
+                        ballots,
+                        vote_times,
+                        join_times,
+                        voting_method: method,
+                        ..
+                    }
+                    | LoraxState::TieBreaker {
+                        ballots,
+                        vote_times,
+                        join_times,
+                        voting_method: method,
+                        ..
+                    },
+                ) = guild.lorax_event_mut(event_id)
+                {
+                    voting_method = *method;
+                    if ballots.remove(&component.user.id).is_some() {
+                        vote_times.remove(&component.user.id);
+                        join_times.remove(&component.user.id);
+                        settings.save(&pool).await?;
+                    }
+                }
+            }
+        }
+        let notice = match voting_method {
+            VotingMethod::Approval => "Your approvals have been cleared.",
+            _ => "Your ranking has been cleared.",
         }
+        .to_string();
+        update_ballot(ctx, component, &data, event_id, page, search, Some(notice)).await?;
+        announcement::refresh_soon(Arc::clone(&ctx.http), data, guild_id, event_id);
         return Ok(());
     }
 
-    if component.data.custom_id.starts_with("vote_select_") {
-        let choice = match &component.data.kind {
+    if let Some(ballot_id) = component.data.custom_id.strip_prefix("vote_select_") {
+        let (event_id, page, search) = parse_ballot_id(ballot_id);
+        let choices: Vec<usize> = match &component.data.kind {
             serenity::ComponentInteractionDataKind::StringSelect { values } => {
-                values.first().and_then(|v| v.parse::<usize>().ok())
+                values.iter().filter_map(|v| v.parse::<usize>().ok()).collect()
             }
-            _ => None,
+            _ => return Ok(()),
         };
 
-        if let Some(choice) = choice {
-            let guild_id = component.guild_id.unwrap();
-            let user_id = component.user.id;
+        let guild_id = component.guild_id.unwrap();
+        let user_id = component.user.id;
+        let joined_at = component
+            .member
+            .as_ref()
+            .and_then(|member| member.joined_at)
+            .map(|joined_at| joined_at.unix_timestamp());
+        let mut keep_ballot_open = false;
+
+        if let Err(reason) = check_eligibility(&data, guild_id, event_id, component.member.as_ref()).await {
+            let builder = serenity::CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::default()
+                    .content(reason)
+                    .ephemeral(true),
+            );
+            component.create_response(ctx, builder).await?;
+            return Ok(());
+        }
 
-            let mut settings = data.settings.write().await;
-            let response = if let Some(guild) = settings.guilds.get_mut(&guild_id) {
-                if let LoraxState::Voting {
+        let mut settings = data.settings.write().await;
+        let response = if let Some(guild) = settings.guilds.get_mut(&guild_id) {
+            if let Some(
+                LoraxState::Voting {
                     votes,
                     options,
                     submissions,
                     end_time,
+                    voting_method,
+                    ballots,
+                    vote_times,
+                    join_times,
+                    voided,
                     ..
                 }
                 | LoraxState::TieBreaker {
@@ -972,36 +3840,95 @@ pub async fn handle_button(
                     options,
                     submissions,
                     end_time,
+                    voting_method,
+                    ballots,
+                    vote_times,
+                    join_times,
+                    voided,
                     ..
-                } = &mut guild.lorax_state
-                {
-                    if Utc::now().timestamp() > *end_time {
-                        "Voting period has ended.".to_string()
-                    } else if let Some(selected_tree) = options.get(choice).cloned() {
-                        if let Some((submitter_id, _)) = submissions
-                            .iter()
-                            .find(|(_, tree)| **tree == selected_tree)
-                        {
-                            if *submitter_id == user_id {
-                                "You can't vote for your own submission!".to_string()
-                            } else {
-                                votes.insert(user_id, choice);
-                                settings.save(&pool).await?;
-                                format!("Your vote for `{}` has been recorded!", selected_tree)
-                            }
+                },
+            ) = guild.lorax_event_mut(event_id)
+            {
+                keep_ballot_open = *voting_method != VotingMethod::Single;
+                let is_own_submission = |choice: usize| {
+                    submissions
+                        .iter()
+                        .any(|s| s.submitter == user_id && s.name == options[choice])
+                };
+
+                let now = Utc::now().timestamp();
+                if now > *end_time {
+                    "Voting period has ended.".to_string()
+                } else if voided.contains(&user_id) {
+                    VOIDED_BALLOT.to_string()
+                } else if choices.iter().any(|&choice| choice >= options.len()) {
+                    "Invalid selection".to_string()
+                } else if choices.iter().any(|&choice| is_own_submission(choice)) {
+                    "You can't vote for your own submission!".to_string()
+                } else if *voting_method == VotingMethod::Approval {
+                    // The menu only covers one page, so only replace the
+                    // approvals for the options shown on it.
+                    let ballot = ballots.entry(user_id).or_default();
+                    let shown: Vec<usize> = ballot_page(options, *voting_method, ballot, page, Some(search))
+                        .options
+                        .into_iter()
+                        .map(|(i, _)| i)
+                        .collect();
+                    ballot.retain(|option| !shown.contains(option));
+                    ballot.extend(choices.iter().filter(|choice| shown.contains(choice)));
+                    ballot.sort_unstable();
+                    let approved = ballot.len();
+                    if approved == 0 {
+                        ballots.remove(&user_id);
+                        vote_times.remove(&user_id);
+                        join_times.remove(&user_id);
+                    } else {
+                        vote_times.entry(user_id).or_insert(now);
+                        join_times.extend(joined_at.map(|joined_at| (user_id, joined_at)));
+                    }
+                    settings.save(&pool).await?;
+                    match approved {
+                        0 => "You haven't approved any names yet.".to_string(),
+                        1 => "You've approved 1 name!".to_string(),
+                        n => format!("You've approved {} names!", n),
+                    }
+                } else if let Some(&choice) = choices.first() {
+                    let selected_tree = options[choice].clone();
+                    if *voting_method == VotingMethod::InstantRunoff {
+                        let ranking = ballots.entry(user_id).or_default();
+                        if ranking.contains(&choice) {
+                            format!("You've already ranked `{}`.", selected_tree)
                         } else {
-                            "Invalid selection".to_string()
+                            ranking.push(choice);
+                            let rank = ranking.len();
+                            vote_times.entry(user_id).or_insert(now);
+                            join_times.extend(joined_at.map(|joined_at| (user_id, joined_at)));
+                            settings.save(&pool).await?;
+                            format!("Added `{}` as your #{} choice!", selected_tree, rank)
                         }
                     } else {
-                        "Invalid selection".to_string()
+                        votes.insert(user_id, choice);
+                        vote_times.entry(user_id).or_insert(now);
+                        join_times.extend(joined_at.map(|joined_at| (user_id, joined_at)));
+                        settings.save(&pool).await?;
+                        format!("Your vote for `{}` has been recorded!", selected_tree)
                     }
                 } else {
-                    "Voting is not currently active".to_string()
+                    "Invalid selection".to_string()
                 }
             } else {
-                "Server not found".to_string()
-            };
+                "Voting is not currently active".to_string()
+            }
+        } else {
+            "Server not found".to_string()
+        };
+        drop(settings);
 
+        // Ranked and approval ballots are built up over several picks, so
+        // keep the ballot open instead of replying with a new message.
+        if keep_ballot_open {
+            update_ballot(ctx, component, &data, event_id, page, search, Some(response)).await?;
+        } else {
             let builder = serenity::CreateInteractionResponse::Message(
                 CreateInteractionResponseMessage::default()
                     .content(response)
@@ -1009,6 +3936,9 @@ pub async fn handle_button(
             );
             component.create_response(ctx, builder).await?;
         }
+        // Only once the voter has their answer, the interaction has to be
+        // answered within 3 seconds
+        announcement::refresh_soon(Arc::clone(&ctx.http), data, guild_id, event_id);
     }
 
     Ok(())
@@ -1016,7 +3946,12 @@ pub async fn handle_button(
 
 /// Lists the current submissions or voting status.
 #[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
-pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Event to list, if more than one is running"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap();
     let settings = ctx.data().settings.read().await;
 
@@ -1028,7 +3963,14 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
         .manage_guild();
 
     if let Some(guild) = settings.guilds.get(&guild_id) {
-        match &guild.lorax_state {
+        let event_id = match resolve_event(guild, event, EventPhase::Any) {
+            Ok(event_id) => event_id,
+            Err(reason) => {
+                ctx.say(reason).await?;
+                return Ok(());
+            }
+        };
+        match &guild.lorax_events[&event_id] {
             LoraxState::Submissions {
                 submissions,
                 end_time,
@@ -1043,11 +3985,18 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
                 }
 
                 let submission_list =
-                    futures::future::join_all(submissions.iter().map(|(user_id, name)| {
+                    futures::future::join_all(submissions.iter().map(|submission| {
                         async move {
                             let submitter_name =
-                                get_submitter_name(ctx.serenity_context(), *user_id).await;
-                            format!("• `{}` (by {})", name, submitter_name)
+                                get_submitter_name(ctx.serenity_context(), submission.submitter)
+                                    .await;
+                            match &submission.meaning {
+                                Some(meaning) => format!(
+                                    "• `{}` (by {}): {}",
+                                    submission.name, submitter_name, meaning
+                                ),
+                                None => format!("• `{}` (by {})", submission.name, submitter_name),
+                            }
                         }
                     }))
                     .await
@@ -1061,7 +4010,7 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
                                 .description(if submission_list.is_empty() {
                                     "No submissions yet.".to_string()
                                 } else {
-                                    submission_list
+                                    truncate_description(submission_list)
                                 })
                                 .footer(CreateEmbedFooter::new(format!(
                                     "Submissions close {}",
@@ -1073,18 +4022,12 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
                 )
                 .await?;
             }
-            LoraxState::Voting {
-                options,
-                votes,
-                end_time,
-                ..
+            state @ (LoraxState::Voting {
+                options, end_time, ..
             }
             | LoraxState::TieBreaker {
-                options,
-                votes,
-                end_time,
-                ..
-            } => {
+                options, end_time, ..
+            }) => {
                 if !is_admin {
                     let options_list = options
                         .iter()
@@ -1107,10 +4050,8 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
                     )
                     .await?;
                 } else {
-                    let mut vote_counts: HashMap<usize, usize> = HashMap::new();
-                    for &choice in votes.values() {
-                        *vote_counts.entry(choice).or_insert(0) += 1;
-                    }
+                    // Ranked ballots are shown by first preference
+                    let vote_counts = state.live_tally();
 
                     let mut options_with_votes: Vec<_> = options
                         .iter()
@@ -1141,7 +4082,7 @@ pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
                                 .footer(CreateEmbedFooter::new(format!(
                                     "Voting closes {} • Total votes: {}",
                                     discord_timestamp(*end_time, TimestampStyle::ShortDateTime),
-                                    votes.len()
+                                    state.voter_count()
                                 )))
                                 .color(Color::from_rgb(67, 160, 71)),
                         ),
@@ -1164,17 +4105,32 @@ pub async fn remove(
     ctx: Context<'_>,
     #[description = "Tree name to remove"] tree_name: String,
     #[description = "Reason for removal"] reason: Option<String>,
+    #[description = "Event to remove it from, if more than one is running"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap();
+    // Names are stored the way `submit` saves them
+    let tree_name = tree_name.trim().to_lowercase();
     let mut settings = ctx.data().settings.write().await;
     let pool = Arc::clone(&ctx.data().pool);
-    let guild = settings.guilds.get_mut(&guild_id).unwrap();
+    let guild = settings.guilds.entry(guild_id).or_default();
+    let event_id = match resolve_event(guild, event, EventPhase::Any) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
 
-    match &mut guild.lorax_state {
-        LoraxState::Submissions { submissions, .. } => {
-            if let Some((user_id, _)) = submissions.iter().find(|(_, name)| name == &&tree_name) {
-                let user_id = *user_id;
-                submissions.remove(&user_id);
+    let mut removed_post = None;
+    match guild.lorax_event_mut(event_id) {
+        Some(LoraxState::Submissions { end_time, .. }) if *end_time <= Utc::now().timestamp() => {
+            ctx.say(format!("❌ {}", SUBMISSIONS_CLOSED)).await?;
+        }
+        Some(LoraxState::Submissions { submissions, .. }) => {
+            if let Some(index) = submissions.iter().position(|s| s.name == tree_name) {
+                submissions.remove(index);
                 settings.save(&pool).await?;
 
                 let msg = if let Some(reason) = reason {
@@ -1190,14 +4146,17 @@ pub async fn remove(
                 ctx.say("❌ Submission not found.").await?;
             }
         }
-        LoraxState::Voting {
+        Some(LoraxState::Voting {
             options,
             votes,
             submissions,
+            ballots,
+            campaign_posts,
             ..
-        } => {
+        }) => {
             if let Some(index) = options.iter().position(|name| name == &tree_name) {
                 options.remove(index);
+                removed_post = campaign_posts.remove(&tree_name);
 
                 votes.retain(|_, &mut vote_idx| vote_idx != index);
 
@@ -1206,8 +4165,9 @@ pub async fn remove(
                         *vote_idx -= 1;
                     }
                 }
+                remove_ballot_option(ballots, index);
 
-                submissions.retain(|_, name| name != &tree_name);
+                submissions.retain(|s| s.name != tree_name);
                 settings.save(&pool).await?;
 
                 let msg = if let Some(reason) = reason {
@@ -1223,15 +4183,26 @@ pub async fn remove(
                 ctx.say("❌ Submission not found.").await?;
             }
         }
-        LoraxState::TieBreaker { options, votes, .. } => {
+        Some(LoraxState::TieBreaker {
+            options,
+            votes,
+            submissions,
+            ballots,
+            campaign_posts,
+            ..
+        }) => {
             if let Some(index) = options.iter().position(|name| name == &tree_name) {
                 options.remove(index);
+                removed_post = campaign_posts.remove(&tree_name);
                 votes.retain(|_, &mut vote_idx| vote_idx != index);
                 for vote_idx in votes.values_mut() {
                     if *vote_idx > index {
                         *vote_idx -= 1;
                     }
                 }
+                remove_ballot_option(ballots, index);
+
+                submissions.retain(|s| s.name != tree_name);
                 settings.save(&pool).await?;
 
                 let msg = if let Some(reason) = reason {
@@ -1247,29 +4218,56 @@ pub async fn remove(
                 ctx.say("❌ Submission not found.").await?;
             }
         }
-        LoraxState::Idle => {
+        None | Some(LoraxState::Idle) => {
             ctx.say("No active event.").await?;
         }
     }
+    drop(settings);
+    announcement::refresh(ctx.http(), ctx.data(), guild_id, event_id).await;
+    if let Some(post) = removed_post {
+        campaign::close(ctx.http(), post).await;
+    }
 
     Ok(())
 }
 
-/// Forces the current Lorax event phase to end early.
+/// Drops a removed option from every ranked ballot and shifts the indices
+/// after it down to match.
+fn remove_ballot_option(ballots: &mut HashMap<serenity::UserId, Vec<usize>>, index: usize) {
+    for ranking in ballots.values_mut() {
+        ranking.retain(|&option| option != index);
+        for option in ranking.iter_mut() {
+            if *option > index {
+                *option -= 1;
+            }
+        }
+    }
+    ballots.retain(|_, ranking| !ranking.is_empty());
+}
+
+/// Forces a Lorax event's current phase to end early.
 #[poise::command(slash_command, required_permissions = "MANAGE_GUILD")]
 pub async fn force_end(
     ctx: Context<'_>,
     #[description = "Reason for ending early"] reason: Option<String>,
+    #[description = "Event to end, if more than one is running"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap();
     let data = ctx.data().clone();
     let serenity_ctx = ctx.serenity_context().clone();
 
-    let settings = data.settings.write().await;
-    let state = settings.guilds.get(&guild_id).unwrap().lorax_state.clone();
-    drop(settings);
+    let guild_settings = data.settings.read().await.get_guild_settings(guild_id);
+    let event_id = match resolve_event(&guild_settings, event, EventPhase::Any) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
 
-    match state {
+    match &guild_settings.lorax_events[&event_id] {
         LoraxState::Submissions { .. } => {
             let msg = if let Some(reason) = reason {
                 format!("🚨 Force ending submission phase! ({})", reason)
@@ -1278,8 +4276,11 @@ pub async fn force_end(
             };
             ctx.say(&msg).await?;
 
-            info!("Force ending submission phase for guild {}", guild_id);
-            start_voting(&serenity_ctx, &data, guild_id).await?;
+            info!(
+                "Force ending submission phase of event {} for guild {}",
+                event_id, guild_id
+            );
+            start_voting(&serenity_ctx, &data, guild_id, event_id).await?;
         }
         LoraxState::Voting { .. } | LoraxState::TieBreaker { .. } => {
             let msg = if let Some(reason) = reason {
@@ -1289,8 +4290,11 @@ pub async fn force_end(
             };
             ctx.say(&msg).await?;
 
-            info!("Force ending voting phase for guild {}", guild_id);
-            announce_winner(&serenity_ctx.http, &data, guild_id).await?;
+            info!(
+                "Force ending voting phase of event {} for guild {}",
+                event_id, guild_id
+            );
+            announce_winner(&serenity_ctx.http, &data, guild_id, event_id).await?;
         }
         LoraxState::Idle => {
             ctx.say("No active event to end.").await?;
@@ -1300,17 +4304,50 @@ pub async fn force_end(
     Ok(())
 }
 
-/// Displays the current status of the Lorax event.
+/// Displays the status of the running Lorax events.
 #[poise::command(slash_command)]
-pub async fn status(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn status(
+    ctx: Context<'_>,
+    #[description = "Only show this event"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().unwrap();
-    let settings = ctx.data().settings.read().await;
-    let guild_settings = settings.get_guild_settings(guild_id);
+    let guild_settings = ctx.data().settings.read().await.get_guild_settings(guild_id);
 
-    let status_msg = match &guild_settings.lorax_state {
-        LoraxState::Idle => {
-            "🌳 No naming event running right now. Start one with `/lorax start` and let's find a name for our next node!".to_string()
-        }
+    let shown: Vec<(u64, &LoraxState)> = match event {
+        Some(_) => match resolve_event(&guild_settings, event, EventPhase::Any) {
+            Ok(event_id) => vec![(event_id, &guild_settings.lorax_events[&event_id])],
+            Err(reason) => {
+                ctx.say(reason).await?;
+                return Ok(());
+            }
+        },
+        None => guild_settings
+            .lorax_events
+            .iter()
+            .filter(|(_, state)| EventPhase::Any.matches(state))
+            .map(|(event_id, state)| (*event_id, state))
+            .collect(),
+    };
+    if shown.is_empty() {
+        ctx.say("🌳 No naming event running right now. Start one with `/lorax start` and let's find a name for our next node!")
+            .await?;
+        return Ok(());
+    }
+
+    let status_msg = shown
+        .into_iter()
+        .map(|(event_id, state)| status_message(event_id, state))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    ctx.say(truncate(status_msg, 2000)).await?;
+    Ok(())
+}
+
+fn status_message(event_id: u64, state: &LoraxState) -> String {
+    let status = match state {
+        LoraxState::Idle => String::new(),
         LoraxState::Submissions {
             end_time,
             location,
@@ -1325,7 +4362,6 @@ pub async fn status(ctx: Context<'_>) -> Result<(), Error> {
         LoraxState::Voting {
             end_time,
             options,
-            votes,
             location,
             ..
         } => {
@@ -1333,22 +4369,616 @@ pub async fn status(ctx: Context<'_>) -> Result<(), Error> {
                 "🗳️ Voting is underway for our **{}** node's name!\n\nWe've got {} great options, and {} votes so far.\n\nUse `/lorax vote` to have your say before {}!",
                 location,
                 options.len(),
-                votes.len(),
+                state.voter_count(),
                 discord_timestamp(*end_time, TimestampStyle::Relative),
             )
         }
-        LoraxState::TieBreaker { end_time, options, votes, location, round, .. } => {
+        LoraxState::TieBreaker { end_time, options, location, round, .. } => {
             format!(
                 "🎯 Tiebreaker Round {} is underway for our **{}** node!\n\n{} options remain, with {} votes cast.\n\nUse `/lorax vote` to break the tie before {}!",
                 round,
                 location,
                 options.len(),
-                votes.len(),
+                state.voter_count(),
                 discord_timestamp(*end_time, TimestampStyle::Relative),
             )
         }
     };
+    format!("**Event #{}**: {}", event_id, status)
+}
+
+/// Browses past Lorax events, or shows the full results of one.
+#[poise::command(slash_command, ephemeral)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "Event ID to show the full results for"] event: Option<i64>,
+    #[description = "Page number"] page: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    const EVENTS_PER_PAGE: i64 = 10;
+
+    if let Some(event_id) = event {
+        let Some(event) = lorax_history::get_event(&pool, guild_id, event_id).await? else {
+            let running = ctx
+                .data()
+                .settings
+                .read()
+                .await
+                .get_guild_settings(guild_id)
+                .lorax_event(event_id as u64)
+                .is_some();
+            ctx.say(if running {
+                "That event is still running, see `/lorax status` for how it's going."
+            } else {
+                "❌ No past event with that ID."
+            })
+            .await?;
+            return Ok(());
+        };
+
+        let mut embed = CreateEmbed::default()
+            .title(format!("🌳 Lorax event #{} — {}", event.id, event.location))
+            .color(Color::from_rgb(67, 160, 71))
+            .field("Outcome", event.outcome.describe(), true);
+
+        if let Some(winner) = &event.winner {
+            let submitter = event
+                .winner_submitter
+                .map(|id| format!(" (by <@{}>)", id))
+                .unwrap_or_default();
+            embed = embed.field("Winner", format!("**{}**{}", winner, submitter), true);
+        }
+
+        let mut timeline = format!(
+            "Started {}",
+            discord_timestamp(event.started_at, TimestampStyle::ShortDateTime)
+        );
+        if let Some(voting_started_at) = event.voting_started_at {
+            timeline.push_str(&format!(
+                "\nVoting opened {}",
+                discord_timestamp(voting_started_at, TimestampStyle::ShortDateTime)
+            ));
+        }
+        timeline.push_str(&format!(
+            "\nEnded {}",
+            discord_timestamp(event.ended_at, TimestampStyle::ShortDateTime)
+        ));
+        embed = embed.field("Timeline", timeline, false);
+
+        let submission_list = if event.submissions.is_empty() {
+            "No submissions.".to_string()
+        } else {
+            event
+                .submissions
+                .iter()
+                .map(|submission| format!("• `{}` by <@{}>", submission.name, submission.submitter))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        embed = embed.field(
+            format!("Submissions ({})", event.submissions.len()),
+            truncate_field(submission_list),
+            false,
+        );
+
+        let mut rounds: Vec<u32> = event.tallies.iter().map(|t| t.round).collect();
+        rounds.dedup();
+        for round in rounds {
+            let tallies: Vec<_> = event.tallies.iter().filter(|t| t.round == round).collect();
+            let ended_at = tallies.first().map(|t| t.ended_at).unwrap_or(event.ended_at);
+            let results = tallies
+                .iter()
+                .map(|t| format!("• `{}` — {} votes", t.name, t.votes))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let title = if round == 0 {
+                "Final vote".to_string()
+            } else {
+                format!("Tiebreaker round {}", round)
+            };
+            embed = embed.field(
+                title,
+                truncate_field(format!(
+                    "{}\n_Closed {}_",
+                    results,
+                    discord_timestamp(ended_at, TimestampStyle::ShortDateTime)
+                )),
+                false,
+            );
+        }
+
+        ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let total = lorax_history::count_events(&pool, guild_id).await?;
+    if total == 0 {
+        ctx.say("🌱 No Lorax events have finished yet.").await?;
+        return Ok(());
+    }
+
+    let total_pages = (total + EVENTS_PER_PAGE - 1) / EVENTS_PER_PAGE;
+    let page = (page.unwrap_or(1).max(1) as i64).min(total_pages);
+    let events =
+        lorax_history::list_events(&pool, guild_id, EVENTS_PER_PAGE, (page - 1) * EVENTS_PER_PAGE)
+            .await?;
+
+    let event_list = events
+        .iter()
+        .map(|e| {
+            let result = match &e.winner {
+                Some(winner) => format!("**{}**", winner),
+                None => e.outcome.describe().to_string(),
+            };
+            format!(
+                "`#{}` **{}** — {} ({})",
+                e.id,
+                e.location,
+                result,
+                discord_timestamp(e.ended_at, TimestampStyle::ShortDateTime)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::default()
+                    .title("📜 Past Lorax Events")
+                    .description(event_list)
+                    .footer(CreateEmbedFooter::new(format!(
+                        "Page {}/{} • Use /lorax history event:<id> for full results",
+                        page, total_pages
+                    )))
+                    .color(Color::from_rgb(67, 160, 71)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Exports a running or past event's results as a file.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "Event to export, running or past (see /lorax history)"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+    #[description = "File format (defaults to JSON)"] format: Option<ExportFormat>,
+    #[description = "Include every ballot, without who cast them"] ballots: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let with_ballots = ballots.unwrap_or(false);
+    let guild_settings = ctx.data().settings.read().await.get_guild_settings(guild_id);
+
+    // Anything that isn't running anymore has been archived
+    let export = match event.filter(|event_id| guild_settings.lorax_event(*event_id).is_none()) {
+        Some(event_id) => {
+            let Some(archived) = lorax_history::get_event(&pool, guild_id, event_id as i64).await?
+            else {
+                ctx.say("❌ No Lorax event with that ID.").await?;
+                return Ok(());
+            };
+            EventExport::from_archived(&archived, with_ballots)
+        }
+        None => {
+            let event_id = match resolve_event(&guild_settings, event, EventPhase::Any) {
+                Ok(event_id) => event_id,
+                Err(reason) => {
+                    ctx.say(reason).await?;
+                    return Ok(());
+                }
+            };
+            let Some(export) = EventExport::from_running(
+                event_id,
+                &guild_settings.lorax_events[&event_id],
+                with_ballots,
+            ) else {
+                ctx.say("No active Lorax event to export.").await?;
+                return Ok(());
+            };
+            export
+        }
+    };
+
+    let mut reply = CreateReply::default()
+        .content(format!(
+            "📦 Here are the results for the **{}** event.",
+            export.location
+        ))
+        .ephemeral(true);
+    for attachment in export.attachments(format.unwrap_or(ExportFormat::Json))? {
+        reply = reply.attachment(attachment);
+    }
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+/// Shows when votes came in, new voters and bursts of votes, to spot brigading.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn audit(
+    ctx: Context<'_>,
+    #[description = "Event to audit, if more than one is open for voting"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+) -> Result<(), Error> {
+    const HISTOGRAM_BUCKETS: usize = 12;
+    const FLAGGED_SHOWN: usize = 15;
+    const BURSTS_SHOWN: usize = 10;
+
+    ctx.defer_ephemeral().await?;
+    let guild_id = ctx.guild_id().unwrap();
+    let guild_settings = ctx.data().settings.read().await.get_guild_settings(guild_id);
+    let event_id = match resolve_event(&guild_settings, event, EventPhase::Voting) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
+    let state = &guild_settings.lorax_events[&event_id];
+    let (LoraxState::Voting {
+        options,
+        end_time,
+        voided,
+        timeline,
+        ..
+    }
+    | LoraxState::TieBreaker {
+        options,
+        end_time,
+        voided,
+        timeline,
+        ..
+    }) = state
+    else {
+        ctx.say("⚠️ That event isn't open for voting.").await?;
+        return Ok(());
+    };
+
+    // A tiebreaker round starts when the round before it ended
+    let window_start = timeline
+        .rounds
+        .last()
+        .map(|round| round.ended_at)
+        .or(timeline.voting_started_at)
+        .unwrap_or(timeline.started_at);
+    let window_end = Utc::now().timestamp().min(*end_time).max(window_start + 1);
+    let ballots = analytics::ballots(state);
+    let backed_names = |backs: &[usize]| {
+        backs
+            .iter()
+            .map(|&option| format!("`{}`", options[option]))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let width = analytics::bucket_width(window_start, window_end, HISTOGRAM_BUCKETS);
+    let histogram = analytics::histogram(&ballots, window_start, window_end, HISTOGRAM_BUCKETS);
+    let untimed = ballots.iter().filter(|ballot| ballot.cast_at.is_none()).count();
+
+    // Older ballots didn't keep the join time, the cache is the best we can
+    // do for those
+    let cached_join_times: HashMap<serenity::UserId, i64> = ctx
+        .guild()
+        .map(|guild| {
+            ballots
+                .iter()
+                .filter(|ballot| ballot.joined_at.is_none())
+                .filter_map(|ballot| {
+                    let joined_at = guild.members.get(&ballot.user_id)?.joined_at?;
+                    Some((ballot.user_id, joined_at.unix_timestamp()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut flagged = Vec::new();
+    for ballot in &ballots {
+        let created_at = ballot.user_id.created_at().unix_timestamp();
+        let joined_at = ballot
+            .joined_at
+            .or_else(|| cached_join_times.get(&ballot.user_id).copied());
+
+        let mut reasons = Vec::new();
+        if created_at >= window_start {
+            reasons.push(format!(
+                "account created {}",
+                discord_timestamp(created_at, TimestampStyle::Relative)
+            ));
+        }
+        if let Some(joined_at) = joined_at.filter(|&joined_at| joined_at >= window_start) {
+            reasons.push(format!(
+                "joined {}",
+                discord_timestamp(joined_at, TimestampStyle::Relative)
+            ));
+        }
+        if !reasons.is_empty() {
+            flagged.push(format!(
+                "<@{}> — {}, backs {}",
+                ballot.user_id,
+                reasons.join(", "),
+                backed_names(&ballot.backs)
+            ));
+        }
+    }
+    let flagged_count = flagged.len();
+    let mut flagged_list = flagged
+        .into_iter()
+        .take(FLAGGED_SHOWN)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if flagged_count > FLAGGED_SHOWN {
+        flagged_list.push_str(&format!("\n...and {} more", flagged_count - FLAGGED_SHOWN));
+    }
+
+    let bursts = analytics::bursts(&ballots, options.len());
+    let burst_list = bursts
+        .iter()
+        .take(BURSTS_SHOWN)
+        .map(|burst| {
+            format!(
+                "`{}` — {} votes in {}s, from {}",
+                options[burst.option],
+                burst.votes,
+                burst.end - burst.start,
+                discord_timestamp(burst.start, TimestampStyle::ShortDateTime)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut summary = format!(
+        "{} ballots since {}",
+        ballots.len(),
+        discord_timestamp(window_start, TimestampStyle::ShortDateTime)
+    );
+    if untimed > 0 {
+        summary.push_str(&format!(", {} cast before vote times were kept", untimed));
+    }
+    if !voided.is_empty() {
+        summary.push_str(&format!(
+            "\n🚫 Voided: {}",
+            voided
+                .iter()
+                .map(|user_id| format!("<@{}>", user_id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::default()
+                    .title(format!(
+                        "🔍 Lorax Audit — {}",
+                        state.location().unwrap_or_default()
+                    ))
+                    .description(truncate_description(summary))
+                    .field(
+                        "Vote timing (UTC)",
+                        truncate_field(format!(
+                            "```\n{}\n```",
+                            analytics::histogram_chart(&histogram, window_start, width)
+                        )),
+                        false,
+                    )
+                    .field(
+                        format!("New accounts and members ({})", flagged_count),
+                        truncate_field(if flagged_list.is_empty() {
+                            "Nobody who voted is newer than this round.".to_string()
+                        } else {
+                            flagged_list
+                        }),
+                        false,
+                    )
+                    .field(
+                        format!("Vote bursts ({})", bursts.len()),
+                        truncate_field(if burst_list.is_empty() {
+                            "No bursts of votes for a single name.".to_string()
+                        } else {
+                            burst_list
+                        }),
+                        false,
+                    )
+                    .footer(CreateEmbedFooter::new(format!(
+                        "Event #{} • Throw out a ballot with /lorax void_ballot",
+                        event_id
+                    )))
+                    .color(Color::from_rgb(67, 160, 71)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Throws out someone's ballot in the current round. They can't vote again
+/// in the event.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn void_ballot(
+    ctx: Context<'_>,
+    #[description = "Voter whose ballot to throw out"] voter: serenity::UserId,
+    #[description = "Why it's being thrown out, for the logs"] reason: Option<String>,
+    #[description = "Event to void it in, if more than one is open for voting"]
+    #[autocomplete = event_autocomplete]
+    event: Option<u64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let mut settings = ctx.data().settings.write().await;
+    let guild = settings.guilds.entry(guild_id).or_default();
+    let event_id = match resolve_event(guild, event, EventPhase::Voting) {
+        Ok(event_id) => event_id,
+        Err(reason) => {
+            ctx.say(reason).await?;
+            return Ok(());
+        }
+    };
+    let Some(
+        LoraxState::Voting {
+            votes,
+            ballots,
+            vote_times,
+            join_times,
+            voided,
+            ..
+        }
+        | LoraxState::TieBreaker {
+            votes,
+            ballots,
+            vote_times,
+            join_times,
+            voided,
+            ..
+        },
+    ) = guild.lorax_event_mut(event_id)
+    else {
+        ctx.say("⚠️ That event isn't open for voting.").await?;
+        return Ok(());
+    };
+
+    if voided.contains(&voter) {
+        ctx.say(format!("<@{}>'s ballot has already been voided.", voter))
+            .await?;
+        return Ok(());
+    }
+    let had_ballot = votes.remove(&voter).is_some() | ballots.remove(&voter).is_some();
+    vote_times.remove(&voter);
+    join_times.remove(&voter);
+    voided.push(voter);
+    settings.save(&pool).await?;
+    drop(settings);
+
+    info!(
+        "{} voided the ballot of {} in Lorax event {} of guild {} ({})",
+        ctx.author().id,
+        voter,
+        event_id,
+        guild_id,
+        reason.as_deref().unwrap_or("no reason given")
+    );
+    announcement::refresh(ctx.http(), ctx.data(), guild_id, event_id).await;
+
+    ctx.say(if had_ballot {
+        format!(
+            "🚫 Voided <@{}>'s ballot. They can't vote again in this event.",
+            voter
+        )
+    } else {
+        format!(
+            "🚫 <@{}> hadn't voted yet, but they won't be able to in this event.",
+            voter
+        )
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Shows who has won and taken part in the most Lorax events.
+#[poise::command(slash_command)]
+pub async fn leaderboard(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    const LEADERBOARD_SIZE: usize = 10;
+
+    let mut participants = lorax_stats::participants(&pool, guild_id).await?;
+    if participants.is_empty() {
+        ctx.say("🌱 Nobody has taken part in a Lorax event yet.").await?;
+        return Ok(());
+    }
+    lorax_stats::rank(&mut participants);
+
+    let medals = ["🥇", "🥈", "🥉"];
+    let leaderboard = participants
+        .iter()
+        .take(LEADERBOARD_SIZE)
+        .enumerate()
+        .map(|(i, user)| {
+            let place = medals
+                .get(i)
+                .map(|medal| medal.to_string())
+                .unwrap_or_else(|| format!("{}.", i + 1));
+            let mut line = format!(
+                "{} <@{}> — 🏆 {} • 🥈 {} • 🌳 {} events",
+                place, user.user_id, user.wins, user.runner_ups, user.participated
+            );
+            if user.current_streak > 1 {
+                line.push_str(&format!(" • 🔥 {}", user.current_streak));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::default()
+                .title("🏆 Lorax Leaderboard")
+                .description(leaderboard)
+                .footer(CreateEmbedFooter::new(
+                    "🏆 wins • 🥈 runner-up finishes • 🔥 events in a row • Use /lorax me for your own stats",
+                ))
+                .color(Color::from_rgb(67, 160, 71)),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Shows how you've done in Lorax events.
+#[poise::command(slash_command, ephemeral)]
+pub async fn me(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let user_id = ctx.author().id;
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let Some(stats) = lorax_stats::participants(&pool, guild_id)
+        .await?
+        .into_iter()
+        .find(|user| user.user_id == user_id)
+    else {
+        ctx.say("🌱 You haven't taken part in a Lorax event yet. Keep an eye out for the next one!")
+            .await?;
+        return Ok(());
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::default()
+                    .title(format!("🌳 Lorax stats for {}", ctx.author().name))
+                    .field("Submitted to", format!("{} events", stats.submitted), true)
+                    .field("Voted in", format!("{} events", stats.voted), true)
+                    .field("Took part in", format!("{} events", stats.participated), true)
+                    .field("Wins", stats.wins.to_string(), true)
+                    .field("Runner-up", stats.runner_ups.to_string(), true)
+                    .field(
+                        "Streak",
+                        format!(
+                            "{} in a row (best {})",
+                            stats.current_streak, stats.best_streak
+                        ),
+                        true,
+                    )
+                    .color(Color::from_rgb(67, 160, 71)),
+            )
+            .ephemeral(true),
+    )
+    .await?;
 
-    ctx.say(status_msg).await?;
     Ok(())
 }
+
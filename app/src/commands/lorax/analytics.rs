@@ -0,0 +1,221 @@
+use crate::settings::{LoraxState, VotingMethod};
+use poise::serenity_prelude::UserId;
+
+/// Votes for one name less than this far apart chain into a burst.
+const BURST_WINDOW_SECS: i64 = 10;
+const BURST_MIN_VOTES: usize = 4;
+
+/// A ballot in the current round, as the audit sees it.
+#[derive(Debug, Clone)]
+pub struct AuditBallot {
+    pub user_id: UserId,
+    /// Ballots cast before vote times were kept don't have one.
+    pub cast_at: Option<i64>,
+    /// When the voter joined the server, if it was known when they voted.
+    pub joined_at: Option<i64>,
+    /// The options it backs: the vote, the first choice of a ranking or
+    /// every approval.
+    pub backs: Vec<usize>,
+}
+
+/// Several votes for one name within a few seconds of each other.
+#[derive(Debug, Clone)]
+pub struct Burst {
+    pub option: usize,
+    pub votes: usize,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Every ballot in the current round of a voting or tiebreaker state.
+pub fn ballots(state: &LoraxState) -> Vec<AuditBallot> {
+    let (LoraxState::Voting {
+        votes,
+        ballots,
+        vote_times,
+        join_times,
+        voting_method,
+        ..
+    }
+    | LoraxState::TieBreaker {
+        votes,
+        ballots,
+        vote_times,
+        join_times,
+        voting_method,
+        ..
+    }) = state
+    else {
+        return Vec::new();
+    };
+
+    let mut audited: Vec<AuditBallot> = votes
+        .iter()
+        .map(|(user_id, &choice)| (*user_id, vec![choice]))
+        .chain(ballots.iter().map(|(user_id, ballot)| {
+            let backs = match voting_method {
+                VotingMethod::Approval => ballot.clone(),
+                _ => ballot.iter().take(1).copied().collect(),
+            };
+            (*user_id, backs)
+        }))
+        .map(|(user_id, backs)| AuditBallot {
+            user_id,
+            cast_at: vote_times.get(&user_id).copied(),
+            joined_at: join_times.get(&user_id).copied(),
+            backs,
+        })
+        .collect();
+    audited.sort_by_key(|ballot| (ballot.cast_at, ballot.user_id));
+    audited
+}
+
+/// How many ballots were cast in each of `buckets` equal slices of the
+/// window.
+pub fn histogram(ballots: &[AuditBallot], start: i64, end: i64, buckets: usize) -> Vec<usize> {
+    let width = bucket_width(start, end, buckets);
+    let mut counts = vec![0; buckets];
+    for cast_at in ballots.iter().filter_map(|ballot| ballot.cast_at) {
+        let bucket = ((cast_at - start).max(0) / width) as usize;
+        counts[bucket.min(buckets - 1)] += 1;
+    }
+    counts
+}
+
+pub fn bucket_width(start: i64, end: i64, buckets: usize) -> i64 {
+    ((end - start) / buckets as i64).max(1)
+}
+
+/// Runs of votes for the same name that came in faster than people usually
+/// vote.
+pub fn bursts(ballots: &[AuditBallot], option_count: usize) -> Vec<Burst> {
+    let mut bursts = Vec::new();
+
+    for option in 0..option_count {
+        let times: Vec<i64> = ballots
+            .iter()
+            .filter(|ballot| ballot.backs.contains(&option))
+            .filter_map(|ballot| ballot.cast_at)
+            .collect();
+
+        let mut first = 0;
+        while first < times.len() {
+            let mut last = first;
+            while last + 1 < times.len() && times[last + 1] - times[last] <= BURST_WINDOW_SECS {
+                last += 1;
+            }
+            if last - first + 1 >= BURST_MIN_VOTES {
+                bursts.push(Burst {
+                    option,
+                    votes: last - first + 1,
+                    start: times[first],
+                    end: times[last],
+                });
+            }
+            first = last + 1;
+        }
+    }
+
+    bursts.sort_by(|a, b| b.votes.cmp(&a.votes).then(a.start.cmp(&b.start)));
+    bursts
+}
+
+/// A text bar chart of the histogram, one line per bucket.
+pub fn histogram_chart(counts: &[usize], start: i64, width: i64) -> String {
+    const BAR_WIDTH: usize = 16;
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let label = chrono::DateTime::from_timestamp(start + i as i64 * width, 0)
+                .map(|time| time.format("%H:%M").to_string())
+                .unwrap_or_default();
+            let filled = (count * BAR_WIDTH).div_ceil(max);
+            format!(
+                "{} {:<width$} {}",
+                label,
+                "█".repeat(filled),
+                count,
+                width = BAR_WIDTH
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ballot(id: u64, cast_at: Option<i64>, backs: &[usize]) -> AuditBallot {
+        AuditBallot {
+            user_id: UserId::new(id),
+            cast_at,
+            joined_at: None,
+            backs: backs.to_vec(),
+        }
+    }
+
+    #[test]
+    fn histogram_buckets_by_time() {
+        let ballots = [
+            ballot(1, None, &[0]),
+            ballot(2, Some(-5), &[0]),
+            ballot(3, Some(0), &[0]),
+            ballot(4, Some(24), &[0]),
+            ballot(5, Some(25), &[0]),
+            ballot(6, Some(99), &[0]),
+            ballot(7, Some(100), &[0]),
+            ballot(8, Some(150), &[0]),
+        ];
+        assert_eq!(histogram(&ballots, 0, 100, 4), vec![3, 1, 0, 3]);
+    }
+
+    #[test]
+    fn bucket_width_is_at_least_a_second() {
+        assert_eq!(bucket_width(0, 100, 4), 25);
+        assert_eq!(bucket_width(0, 2, 10), 1);
+    }
+
+    #[test]
+    fn quick_votes_for_one_name_are_a_burst() {
+        let ballots = [
+            ballot(1, Some(0), &[0]),
+            ballot(2, Some(0), &[1]),
+            ballot(3, Some(5), &[0]),
+            ballot(4, Some(10), &[0]),
+            ballot(5, Some(20), &[0]),
+            ballot(6, Some(20), &[1]),
+            ballot(7, Some(40), &[1]),
+            ballot(8, Some(100), &[0]),
+        ];
+        let bursts = bursts(&ballots, 2);
+        assert_eq!(bursts.len(), 1);
+        assert_eq!(bursts[0].option, 0);
+        assert_eq!(bursts[0].votes, 4);
+        assert_eq!((bursts[0].start, bursts[0].end), (0, 20));
+    }
+
+    #[test]
+    fn bursts_need_enough_votes() {
+        let ballots = [
+            ballot(1, Some(0), &[0]),
+            ballot(2, Some(1), &[0]),
+            ballot(3, Some(2), &[0]),
+        ];
+        assert!(bursts(&ballots, 1).is_empty());
+    }
+
+    #[test]
+    fn approval_ballots_count_towards_every_name() {
+        let ballots: Vec<_> = (0..4)
+            .map(|i| ballot(i + 1, Some(i as i64), &[0, 1]))
+            .collect();
+        let bursts = bursts(&ballots, 2);
+        assert_eq!(bursts.len(), 2);
+        assert_eq!(bursts[0].option, 0);
+        assert_eq!(bursts[1].option, 1);
+    }
+}
@@ -0,0 +1,244 @@
+use super::copy::{self, Phrases};
+use super::{discord_timestamp, TimestampStyle};
+use crate::settings::{LoraxLocale, LoraxState, VotingMethod};
+use crate::util::truncate_field;
+use crate::Data;
+use poise::serenity_prelude::{
+    self as serenity, ButtonStyle, ChannelId, Color, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedFooter, EditMessage,
+};
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::warn;
+
+/// Custom ID prefix of the announcement's "Vote" button, which opens the
+/// ballot. The event ID follows it.
+pub const VOTE_BUTTON_PREFIX: &str = "vote_open_";
+
+const RESULTS_SHOWN: usize = 10;
+const BAR_WIDTH: usize = 10;
+
+/// Votes edit the announcement at most this often, the edits share a channel
+/// rate limit with everything else posted there.
+const REFRESH_DELAY: Duration = Duration::from_secs(5);
+
+/// Events with a refresh waiting in `refresh_soon`.
+static PENDING_REFRESHES: Mutex<BTreeSet<(serenity::GuildId, u64)>> = Mutex::new(BTreeSet::new());
+
+/// The announcement embed for the current phase, and the Vote button while
+/// voting is open. Closed announcements always show the results, blind or not.
+pub fn build(
+    state: &LoraxState,
+    event_id: u64,
+    closed: bool,
+    locale: LoraxLocale,
+) -> Option<EditMessage> {
+    let phrases = copy::phrases(locale);
+    let footer = copy::render(phrases.event_footer, &[("event", &event_id.to_string())]);
+    let (embed, votable) = match state {
+        LoraxState::Idle => return None,
+        LoraxState::Submissions {
+            end_time,
+            submissions,
+            location,
+            max_submissions_per_user,
+            ..
+        } => {
+            let mut embed = CreateEmbed::default()
+                .title(copy::render(phrases.submissions_title, &[("location", location)]))
+                .description(if closed {
+                    phrases.submissions_closed_description
+                } else {
+                    phrases.submissions_description
+                })
+                .field(phrases.submissions_field, submissions.len().to_string(), true)
+                .field(
+                    if closed { phrases.closed_field } else { phrases.closes_field },
+                    discord_timestamp(*end_time, TimestampStyle::Relative),
+                    true,
+                )
+                .footer(CreateEmbedFooter::new(footer));
+            if *max_submissions_per_user > 1 && !closed {
+                embed = embed.field(
+                    phrases.names_per_person_field,
+                    max_submissions_per_user.to_string(),
+                    true,
+                );
+            }
+            (embed, false)
+        }
+        LoraxState::Voting {
+            end_time,
+            options,
+            location,
+            voting_method,
+            blind_voting,
+            ..
+        }
+        | LoraxState::TieBreaker {
+            end_time,
+            options,
+            location,
+            voting_method,
+            blind_voting,
+            ..
+        } => {
+            let title = match state {
+                LoraxState::TieBreaker { round, .. } => copy::render(
+                    phrases.tiebreaker_title,
+                    &[("round", &round.to_string()), ("location", location)],
+                ),
+                _ => copy::render(phrases.voting_title, &[("location", location)]),
+            };
+            let (description, method) = match voting_method {
+                VotingMethod::Single => (phrases.single_description, phrases.single_method),
+                VotingMethod::InstantRunoff => (phrases.ranked_description, phrases.ranked_method),
+                VotingMethod::Approval => (phrases.approval_description, phrases.approval_method),
+            };
+            let mut footer = format!("{} • {}", footer, method);
+            if *blind_voting && !closed {
+                footer = format!("{} • {}", footer, phrases.blind_footer);
+            }
+
+            let mut embed = CreateEmbed::default()
+                .title(title)
+                .description(if closed { phrases.voting_closed_description } else { description })
+                .field(phrases.names_field, options.len().to_string(), true)
+                .field(phrases.votes_field, state.voter_count().to_string(), true)
+                .field(
+                    if closed { phrases.closed_field } else { phrases.closes_field },
+                    discord_timestamp(*end_time, TimestampStyle::Relative),
+                    true,
+                )
+                .footer(CreateEmbedFooter::new(footer));
+
+            if closed || !*blind_voting {
+                let results_title = match (closed, voting_method) {
+                    (_, VotingMethod::InstantRunoff) => phrases.first_preferences_field,
+                    (true, _) => phrases.results_field,
+                    (false, _) => phrases.live_results_field,
+                };
+                embed = embed.field(results_title, results(state, options, phrases), false);
+            }
+            (embed, !closed)
+        }
+    };
+
+    let color = if closed {
+        Color::from_rgb(117, 117, 117)
+    } else {
+        Color::from_rgb(67, 160, 71)
+    };
+    let components = if votable {
+        vec![CreateActionRow::Buttons(vec![CreateButton::new(format!(
+            "{}{}",
+            VOTE_BUTTON_PREFIX, event_id
+        ))
+            .label(phrases.vote_button)
+            .emoji('🗳')
+            .style(ButtonStyle::Primary)])]
+    } else {
+        Vec::new()
+    };
+
+    Some(EditMessage::new().embed(embed.color(color)).components(components))
+}
+
+/// The event a Vote button belongs to, if the custom ID is one.
+pub fn vote_button_event(custom_id: &str) -> Option<u64> {
+    custom_id.strip_prefix(VOTE_BUTTON_PREFIX)?.parse().ok()
+}
+
+/// Each option's share of the voters, best first.
+fn results(state: &LoraxState, options: &[String], phrases: &Phrases) -> String {
+    let tally = state.live_tally();
+    let voters = state.voter_count();
+
+    let mut standings: Vec<(&String, usize)> = options
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name, tally.get(&i).copied().unwrap_or(0)))
+        .collect();
+    standings.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut lines: Vec<String> = standings
+        .iter()
+        .take(RESULTS_SHOWN)
+        .map(|(name, votes)| {
+            let share = if voters == 0 {
+                0.0
+            } else {
+                *votes as f64 / voters as f64
+            };
+            let filled = (share * BAR_WIDTH as f64).round() as usize;
+            format!(
+                "`{}{}` {:>3.0}% `{}`",
+                "█".repeat(filled),
+                "░".repeat(BAR_WIDTH - filled),
+                share * 100.0,
+                name
+            )
+        })
+        .collect();
+    if standings.len() > RESULTS_SHOWN {
+        lines.push(copy::render(
+            phrases.more_results,
+            &[("count", &(standings.len() - RESULTS_SHOWN).to_string())],
+        ));
+    }
+
+    truncate_field(lines.join("\n"))
+}
+
+/// Redraws the announcement for the event's current phase. Takes the settings
+/// lock, so callers holding it should use [`update`] instead.
+pub async fn refresh(http: &serenity::Http, data: &Data, guild_id: serenity::GuildId, event_id: u64) {
+    let guild_settings = data.settings.read().await.get_guild_settings(guild_id);
+    if let (Some(channel_id), Some(state)) =
+        (guild_settings.lorax_channel, guild_settings.lorax_event(event_id))
+    {
+        update(http, channel_id, event_id, state, false, guild_settings.lorax_locale).await;
+    }
+}
+
+/// Refreshes the announcement in the background after a short delay. Calls
+/// made while a refresh is waiting are folded into it, so a burst of votes
+/// edits the message once.
+pub fn refresh_soon(
+    http: Arc<serenity::Http>,
+    data: Data,
+    guild_id: serenity::GuildId,
+    event_id: u64,
+) {
+    let key = (guild_id, event_id);
+    if !PENDING_REFRESHES.lock().unwrap().insert(key) {
+        return;
+    }
+    tokio::spawn(async move {
+        tokio::time::sleep(REFRESH_DELAY).await;
+        // Cleared before reading the state, so a vote cast during the edit
+        // schedules another refresh instead of being missed
+        PENDING_REFRESHES.lock().unwrap().remove(&key);
+        refresh(&http, &data, guild_id, event_id).await;
+    });
+}
+
+/// Best effort, the event carries on fine with a stale announcement.
+pub async fn update(
+    http: &serenity::Http,
+    channel_id: ChannelId,
+    event_id: u64,
+    state: &LoraxState,
+    closed: bool,
+    locale: LoraxLocale,
+) {
+    let (Some(message_id), Some(edit)) =
+        (state.message_id(), build(state, event_id, closed, locale))
+    else {
+        return;
+    };
+    if let Err(e) = channel_id.edit_message(http, message_id, edit).await {
+        warn!("Failed to update Lorax announcement: {}", e);
+    }
+}
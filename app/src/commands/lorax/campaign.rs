@@ -0,0 +1,119 @@
+use super::copy::{self, Phrases};
+use crate::settings::{LoraxState, Submission};
+use crate::util::truncate;
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, CreateForumPost, CreateMessage, EditMessage, EditThread, MessageId,
+};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+// Forum post titles are capped at 100 characters
+const MAX_TITLE_LEN: usize = 100;
+
+/// Opens a forum post for every name, with the submitter's statement pinned
+/// at the top. Names whose post couldn't be created are left out.
+pub async fn open_posts(
+    http: &serenity::Http,
+    forum: ChannelId,
+    phrases: &Phrases,
+    submissions: &[Submission],
+) -> BTreeMap<String, ChannelId> {
+    let mut posts = BTreeMap::new();
+
+    for submission in submissions {
+        let title = truncate(format!("🌳 {}", submission.name), MAX_TITLE_LEN);
+        let post = forum
+            .create_forum_post(
+                http,
+                CreateForumPost::new(
+                    title,
+                    CreateMessage::new().content(post_body(phrases, submission)),
+                ),
+            )
+            .await;
+
+        match post {
+            Ok(post) => {
+                // The starter message of a forum post shares its ID
+                if let Err(e) = post.id.pin(http, MessageId::new(post.id.get())).await {
+                    warn!(
+                        "Failed to pin campaign post for `{}`: {}",
+                        submission.name, e
+                    );
+                }
+                posts.insert(submission.name.clone(), post.id);
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to open campaign post for `{}`: {}",
+                    submission.name, e
+                );
+            }
+        }
+    }
+
+    posts
+}
+
+/// The pinned message of a name's post: who submitted it, what it means and
+/// their statement.
+pub fn post_body(phrases: &Phrases, submission: &Submission) -> String {
+    let submitter = format!("<@{}>", submission.submitter);
+    let mut body = copy::render(
+        phrases.candidate_post,
+        &[("name", &submission.name), ("submitter", &submitter)],
+    );
+    if let Some(meaning) = &submission.meaning {
+        body.push_str(&format!("\n\n📖 {}", meaning));
+    }
+    body.push_str("\n\n");
+    body.push_str(&match &submission.statement {
+        Some(statement) => copy::render(phrases.statement, &[("statement", statement)]),
+        None => copy::render(phrases.no_statement, &[("submitter", &submitter)]),
+    });
+    body
+}
+
+/// Rewrites the pinned message after the submitter changes their statement.
+pub async fn update_post(
+    http: &serenity::Http,
+    post: ChannelId,
+    phrases: &Phrases,
+    submission: &Submission,
+) -> Result<(), serenity::Error> {
+    post.edit_message(
+        http,
+        MessageId::new(post.get()),
+        EditMessage::new().content(post_body(phrases, submission)),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Archives and locks a campaign thread or post.
+pub async fn close(http: &serenity::Http, thread: ChannelId) {
+    if let Err(e) = thread
+        .edit_thread(http, EditThread::new().archived(true).locked(true))
+        .await
+    {
+        warn!("Failed to close campaign thread {}: {}", thread, e);
+    }
+}
+
+/// Closes the campaign thread and every campaign post of an event.
+pub async fn close_all(http: &serenity::Http, state: &LoraxState) {
+    let thread = match state {
+        LoraxState::Voting { thread_id, .. } | LoraxState::TieBreaker { thread_id, .. } => {
+            *thread_id
+        }
+        _ => None,
+    };
+    let posts = state
+        .campaign_posts()
+        .into_iter()
+        .flat_map(|posts| posts.values());
+
+    for channel in thread.iter().chain(posts) {
+        close(http, *channel).await;
+    }
+}
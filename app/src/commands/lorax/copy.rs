@@ -0,0 +1,539 @@
+use crate::settings::{GuildSettings, LoraxLocale, LoraxMessage};
+
+/// Longest copy a guild can save, leaving room for the lists that are added
+/// after it in the same message.
+pub const MAX_MESSAGE_LEN: usize = 1500;
+
+/// The guild's own copy for a message, or the built-in copy for its locale.
+pub fn template(guild: &GuildSettings, message: LoraxMessage) -> &str {
+    guild
+        .lorax_messages
+        .get(&message)
+        .map(String::as_str)
+        .unwrap_or_else(|| built_in(guild.lorax_locale, message))
+}
+
+/// The guild's copy for a message with the placeholders filled in.
+pub fn message(guild: &GuildSettings, message: LoraxMessage, values: &[(&str, &str)]) -> String {
+    render(template(guild, message), values)
+}
+
+/// Fills in `{placeholder}`s. Ones without a value are left as they are.
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (placeholder, value)| {
+            text.replace(&format!("{{{}}}", placeholder), value)
+        })
+}
+
+/// Checks copy before it's saved. The error can be shown to whoever wrote it.
+pub fn validate(message: LoraxMessage, text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("❌ The message can't be empty.".to_string());
+    }
+    if text.chars().count() > MAX_MESSAGE_LEN {
+        return Err(format!(
+            "❌ The message can be at most {} characters long.",
+            MAX_MESSAGE_LEN
+        ));
+    }
+
+    let allowed = message.placeholders();
+    let mut rest = text;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err("❌ There's a `}` without a `{` before it.".to_string());
+        }
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            return Err("❌ There's a `{` that's never closed.".to_string());
+        };
+        let placeholder = &after[..end];
+        if !allowed.contains(&placeholder) {
+            return Err(format!(
+                "❌ `{{{}}}` isn't a placeholder for this message. You can use {}.",
+                placeholder,
+                placeholder_list(message)
+            ));
+        }
+        rest = &after[end + 1..];
+    }
+
+    Ok(())
+}
+
+/// The placeholders of a message, formatted for Discord.
+pub fn placeholder_list(message: LoraxMessage) -> String {
+    message
+        .placeholders()
+        .iter()
+        .map(|placeholder| format!("`{{{}}}`", placeholder))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Lists, labels and notes posted around the customisable copy. They use the
+/// same placeholders, filled in with `render`.
+pub struct Phrases {
+    /// Heading for the list of tied names.
+    pub tied_names: &'static str,
+    pub last_round: &'static str,
+    pub draw_if_no_votes: &'static str,
+    /// `{round}`, `{names}`
+    pub thread_tiebreaker: &'static str,
+    /// `{end}`
+    pub thread_reminder: &'static str,
+    /// `{end}`
+    pub thread_duration: &'static str,
+    /// `{role}`, `{names}`, `{round}`
+    pub tie_between: &'static str,
+    /// `{name}`, `{submitter}`
+    pub tied_entry: &'static str,
+    pub no_names_left: &'static str,
+    pub nobody_voted: &'static str,
+    /// `{round}`
+    pub still_tied: &'static str,
+    /// `{reason}`, `{names}`, `{seed}`, `{count}`, `{index}`, `{winner}`
+    pub draw: &'static str,
+    pub top_entries: &'static str,
+    /// `{name}`, `{submitter}`, `{count}`
+    pub entry: &'static str,
+    /// `{count}`
+    pub more_entries: &'static str,
+    /// `{location}`
+    pub thread_name: &'static str,
+    /// `{names}`
+    pub speakers: &'static str,
+    /// `{submitter}`, `{name}`
+    pub representing: &'static str,
+    pub meanings: &'static str,
+    /// `{name}`, `{submitter}`
+    pub candidate_post: &'static str,
+    /// `{statement}`
+    pub statement: &'static str,
+    /// `{submitter}`
+    pub no_statement: &'static str,
+
+    // The announcement embed
+    /// `{location}`
+    pub submissions_title: &'static str,
+    pub submissions_description: &'static str,
+    pub submissions_closed_description: &'static str,
+    pub submissions_field: &'static str,
+    pub names_per_person_field: &'static str,
+    /// `{location}`
+    pub voting_title: &'static str,
+    /// `{round}`, `{location}`
+    pub tiebreaker_title: &'static str,
+    pub single_description: &'static str,
+    pub ranked_description: &'static str,
+    pub approval_description: &'static str,
+    pub voting_closed_description: &'static str,
+    pub names_field: &'static str,
+    pub votes_field: &'static str,
+    pub closes_field: &'static str,
+    pub closed_field: &'static str,
+    pub live_results_field: &'static str,
+    pub results_field: &'static str,
+    pub first_preferences_field: &'static str,
+    /// `{count}`
+    pub more_results: &'static str,
+    /// `{event}`
+    pub event_footer: &'static str,
+    pub single_method: &'static str,
+    pub ranked_method: &'static str,
+    pub approval_method: &'static str,
+    pub blind_footer: &'static str,
+    pub vote_button: &'static str,
+}
+
+pub fn phrases(locale: LoraxLocale) -> &'static Phrases {
+    match locale {
+        LoraxLocale::English => &ENGLISH,
+        LoraxLocale::German => &GERMAN,
+        LoraxLocale::Spanish => &SPANISH,
+        LoraxLocale::French => &FRENCH,
+        LoraxLocale::Dutch => &DUTCH,
+    }
+}
+
+pub fn built_in(locale: LoraxLocale, message: LoraxMessage) -> &'static str {
+    match (locale, message) {
+        (LoraxLocale::English, LoraxMessage::Teaser) => "Hey {role}! 🌱 We're getting a new node ready in **{location}**, and it'll need a name!\n\nSubmissions open {start}, so start thinking up your best tree names now.",
+        (LoraxLocale::English, LoraxMessage::SubmissionsOpen) => "Hey {role}! We're launching a new node in **{location}**, and we need your help to name it! 🌳",
+        (LoraxLocale::English, LoraxMessage::NoSubmissions) => "🌳 Hmm, looks like we didn't get any submissions this time. :(",
+        (LoraxLocale::English, LoraxMessage::VotingOpen) => "Hey {role}! It's voting time for our new **{location}** node! 🗳️ We've got {count} awesome name suggestions to choose from.",
+        (LoraxLocale::English, LoraxMessage::CampaignThread) => "🌳 Welcome to the Tree Name Campaign Thread!\n\nHere, submitters can advocate for their tree names and voters can discuss the options.",
+        (LoraxLocale::English, LoraxMessage::Tiebreaker) => "🎯 Hey {role}! We've got a tie for our new **{location}** node! Time for tiebreaker round {round}!",
+        (LoraxLocale::English, LoraxMessage::Winner) => "Hey {role}! 🎉 The winning tree name is **{winner}** (submitted by {submitter})! This will be the name for our new **{location}** node.\n\nThank you all for participating!",
+        (LoraxLocale::English, LoraxMessage::SubmissionsReminder) => "⏰ Hey {role}! Submissions for our new **{location}** node close {end}. Get your tree name ideas in with `/lorax submit`!",
+        (LoraxLocale::English, LoraxMessage::VotingReminder) => "⏰ Hey {role}! Voting for our **{location}** node's name closes {end}. Make sure you've had your say with `/lorax vote`!",
+        (LoraxLocale::English, LoraxMessage::Extended) => "⏰ The current phase has been extended by {minutes} minutes! More time to get involved. New end time: {end}",
+        (LoraxLocale::English, LoraxMessage::Shortened) => "⏰ The current phase has been shortened by {minutes} minutes. Don't miss out! New end time: {end}",
+        (LoraxLocale::English, LoraxMessage::Cancelled) => "🚫 The Lorax event for our new {location} node has been cancelled by an administrator.",
+
+        (LoraxLocale::German, LoraxMessage::Teaser) => "Hey {role}! 🌱 Wir bereiten einen neuen Node in **{location}** vor, und er braucht einen Namen!\n\nEinreichungen öffnen {start}, also überlegt euch schon mal eure besten Baumnamen.",
+        (LoraxLocale::German, LoraxMessage::SubmissionsOpen) => "Hey {role}! Wir starten einen neuen Node in **{location}** und brauchen eure Hilfe bei der Namenssuche! 🌳",
+        (LoraxLocale::German, LoraxMessage::NoSubmissions) => "🌳 Hm, diesmal sind leider keine Vorschläge eingegangen. :(",
+        (LoraxLocale::German, LoraxMessage::VotingOpen) => "Hey {role}! Zeit zum Abstimmen für unseren neuen Node in **{location}**! 🗳️ Es stehen {count} tolle Namensvorschläge zur Wahl.",
+        (LoraxLocale::German, LoraxMessage::CampaignThread) => "🌳 Willkommen im Wahlkampf-Thread!\n\nHier können Einreichende für ihre Baumnamen werben und alle über die Vorschläge diskutieren.",
+        (LoraxLocale::German, LoraxMessage::Tiebreaker) => "🎯 Hey {role}! Für unseren neuen Node in **{location}** gibt es einen Gleichstand! Zeit für Stichwahlrunde {round}!",
+        (LoraxLocale::German, LoraxMessage::Winner) => "Hey {role}! 🎉 Der Gewinnername ist **{winner}** (eingereicht von {submitter})! So wird unser neuer Node in **{location}** heißen.\n\nDanke an alle fürs Mitmachen!",
+        (LoraxLocale::German, LoraxMessage::SubmissionsReminder) => "⏰ Hey {role}! Die Einreichungen für unseren neuen Node in **{location}** schließen {end}. Reicht eure Baumnamen mit `/lorax submit` ein!",
+        (LoraxLocale::German, LoraxMessage::VotingReminder) => "⏰ Hey {role}! Die Abstimmung über den Namen unseres Nodes in **{location}** endet {end}. Gebt eure Stimme mit `/lorax vote` ab!",
+        (LoraxLocale::German, LoraxMessage::Extended) => "⏰ Die aktuelle Phase wurde um {minutes} Minuten verlängert! Mehr Zeit zum Mitmachen. Neues Ende: {end}",
+        (LoraxLocale::German, LoraxMessage::Shortened) => "⏰ Die aktuelle Phase wurde um {minutes} Minuten verkürzt. Verpasst es nicht! Neues Ende: {end}",
+        (LoraxLocale::German, LoraxMessage::Cancelled) => "🚫 Das Lorax-Event für unseren neuen Node in {location} wurde von einem Admin abgesagt.",
+
+        (LoraxLocale::Spanish, LoraxMessage::Teaser) => "¡Hola {role}! 🌱 Estamos preparando un nuevo nodo en **{location}** ¡y necesitará un nombre!\n\nLas propuestas se abren {start}, así que id pensando en vuestros mejores nombres de árboles.",
+        (LoraxLocale::Spanish, LoraxMessage::SubmissionsOpen) => "¡Hola {role}! Vamos a lanzar un nuevo nodo en **{location}** ¡y necesitamos vuestra ayuda para ponerle nombre! 🌳",
+        (LoraxLocale::Spanish, LoraxMessage::NoSubmissions) => "🌳 Vaya, parece que esta vez no hemos recibido ninguna propuesta. :(",
+        (LoraxLocale::Spanish, LoraxMessage::VotingOpen) => "¡Hola {role}! ¡Es hora de votar el nombre de nuestro nuevo nodo en **{location}**! 🗳️ Tenemos {count} propuestas geniales para elegir.",
+        (LoraxLocale::Spanish, LoraxMessage::CampaignThread) => "🌳 ¡Bienvenidos al hilo de campaña!\n\nAquí quienes han propuesto nombres pueden defenderlos y todos pueden debatir las opciones.",
+        (LoraxLocale::Spanish, LoraxMessage::Tiebreaker) => "🎯 ¡Hola {role}! ¡Tenemos un empate para nuestro nuevo nodo en **{location}**! ¡Es hora de la ronda de desempate {round}!",
+        (LoraxLocale::Spanish, LoraxMessage::Winner) => "¡Hola {role}! 🎉 El nombre ganador es **{winner}** (propuesto por {submitter}). Así se llamará nuestro nuevo nodo en **{location}**.\n\n¡Gracias a todos por participar!",
+        (LoraxLocale::Spanish, LoraxMessage::SubmissionsReminder) => "⏰ ¡Hola {role}! Las propuestas para nuestro nuevo nodo en **{location}** se cierran {end}. ¡Enviad vuestros nombres de árboles con `/lorax submit`!",
+        (LoraxLocale::Spanish, LoraxMessage::VotingReminder) => "⏰ ¡Hola {role}! La votación del nombre de nuestro nodo en **{location}** se cierra {end}. ¡Asegúrate de votar con `/lorax vote`!",
+        (LoraxLocale::Spanish, LoraxMessage::Extended) => "⏰ ¡La fase actual se ha alargado {minutes} minutos! Más tiempo para participar. Nuevo cierre: {end}",
+        (LoraxLocale::Spanish, LoraxMessage::Shortened) => "⏰ La fase actual se ha acortado {minutes} minutos. ¡No te lo pierdas! Nuevo cierre: {end}",
+        (LoraxLocale::Spanish, LoraxMessage::Cancelled) => "🚫 Un administrador ha cancelado el evento Lorax de nuestro nuevo nodo en {location}.",
+
+        (LoraxLocale::French, LoraxMessage::Teaser) => "Salut {role} ! 🌱 Nous préparons un nouveau nœud à **{location}**, et il lui faudra un nom !\n\nLes propositions ouvrent {start}, alors commencez dès maintenant à chercher vos meilleurs noms d'arbres.",
+        (LoraxLocale::French, LoraxMessage::SubmissionsOpen) => "Salut {role} ! Nous lançons un nouveau nœud à **{location}**, et nous avons besoin de vous pour lui trouver un nom ! 🌳",
+        (LoraxLocale::French, LoraxMessage::NoSubmissions) => "🌳 Hmm, on dirait que nous n'avons reçu aucune proposition cette fois-ci. :(",
+        (LoraxLocale::French, LoraxMessage::VotingOpen) => "Salut {role} ! C'est l'heure de voter pour notre nouveau nœud à **{location}** ! 🗳️ Nous avons {count} super propositions de noms.",
+        (LoraxLocale::French, LoraxMessage::CampaignThread) => "🌳 Bienvenue dans le fil de campagne !\n\nIci, les auteurs des propositions peuvent défendre leurs noms d'arbres et chacun peut discuter des options.",
+        (LoraxLocale::French, LoraxMessage::Tiebreaker) => "🎯 Salut {role} ! Il y a égalité pour notre nouveau nœud à **{location}** ! Place au tour de départage {round} !",
+        (LoraxLocale::French, LoraxMessage::Winner) => "Salut {role} ! 🎉 Le nom gagnant est **{winner}** (proposé par {submitter}) ! Ce sera le nom de notre nouveau nœud à **{location}**.\n\nMerci à tous d'avoir participé !",
+        (LoraxLocale::French, LoraxMessage::SubmissionsReminder) => "⏰ Salut {role} ! Les propositions pour notre nouveau nœud à **{location}** ferment {end}. Envoyez vos noms d'arbres avec `/lorax submit` !",
+        (LoraxLocale::French, LoraxMessage::VotingReminder) => "⏰ Salut {role} ! Le vote pour le nom de notre nœud à **{location}** se termine {end}. Donnez votre avis avec `/lorax vote` !",
+        (LoraxLocale::French, LoraxMessage::Extended) => "⏰ La phase en cours a été prolongée de {minutes} minutes ! Plus de temps pour participer. Nouvelle fin : {end}",
+        (LoraxLocale::French, LoraxMessage::Shortened) => "⏰ La phase en cours a été raccourcie de {minutes} minutes. Ne la manquez pas ! Nouvelle fin : {end}",
+        (LoraxLocale::French, LoraxMessage::Cancelled) => "🚫 L'événement Lorax pour notre nouveau nœud à {location} a été annulé par un administrateur.",
+
+        (LoraxLocale::Dutch, LoraxMessage::Teaser) => "Hoi {role}! 🌱 We maken een nieuwe node klaar in **{location}**, en die heeft een naam nodig!\n\nInzendingen openen {start}, dus begin alvast na te denken over je beste boomnamen.",
+        (LoraxLocale::Dutch, LoraxMessage::SubmissionsOpen) => "Hoi {role}! We lanceren een nieuwe node in **{location}** en we hebben jullie hulp nodig om hem een naam te geven! 🌳",
+        (LoraxLocale::Dutch, LoraxMessage::NoSubmissions) => "🌳 Hmm, het lijkt erop dat we deze keer geen inzendingen hebben gekregen. :(",
+        (LoraxLocale::Dutch, LoraxMessage::VotingOpen) => "Hoi {role}! Tijd om te stemmen voor onze nieuwe node in **{location}**! 🗳️ Er zijn {count} geweldige naamsuggesties om uit te kiezen.",
+        (LoraxLocale::Dutch, LoraxMessage::CampaignThread) => "🌳 Welkom in de campagnethread!\n\nHier kunnen inzenders campagne voeren voor hun boomnamen en kan iedereen over de opties discussiëren.",
+        (LoraxLocale::Dutch, LoraxMessage::Tiebreaker) => "🎯 Hoi {role}! Het staat gelijk voor onze nieuwe node in **{location}**! Tijd voor beslissingsronde {round}!",
+        (LoraxLocale::Dutch, LoraxMessage::Winner) => "Hoi {role}! 🎉 De winnende boomnaam is **{winner}** (ingezonden door {submitter})! Zo gaat onze nieuwe node in **{location}** heten.\n\nBedankt allemaal voor het meedoen!",
+        (LoraxLocale::Dutch, LoraxMessage::SubmissionsReminder) => "⏰ Hoi {role}! De inzendingen voor onze nieuwe node in **{location}** sluiten {end}. Zend je boomnamen in met `/lorax submit`!",
+        (LoraxLocale::Dutch, LoraxMessage::VotingReminder) => "⏰ Hoi {role}! De stemming over de naam van onze node in **{location}** sluit {end}. Breng je stem uit met `/lorax vote`!",
+        (LoraxLocale::Dutch, LoraxMessage::Extended) => "⏰ De huidige fase is met {minutes} minuten verlengd! Meer tijd om mee te doen. Nieuwe eindtijd: {end}",
+        (LoraxLocale::Dutch, LoraxMessage::Shortened) => "⏰ De huidige fase is met {minutes} minuten ingekort. Mis het niet! Nieuwe eindtijd: {end}",
+        (LoraxLocale::Dutch, LoraxMessage::Cancelled) => "🚫 Het Lorax-evenement voor onze nieuwe node in {location} is door een beheerder geannuleerd.",
+    }
+}
+
+static ENGLISH: Phrases = Phrases {
+    tied_names: "The following names are tied:",
+    last_round: "This is the last round, if it's still tied the winner will be drawn at random. 🎲",
+    draw_if_no_votes: "If nobody votes, the winner will be drawn at random. 🎲",
+    thread_tiebreaker: "🎯 We're headed to tiebreaker round {round}! The following names are tied:\n{names}\n\nUse `/lorax vote` to help break the tie!",
+    thread_reminder: "⏰ Voting closes {end}! Last chance to make your case. 🌳",
+    thread_duration: "⏰ Voting duration has been adjusted! New end time: {end}",
+    tie_between: "Hey {role}! We've got a tie between {names}! Time for tiebreaker round {round}!\n\nUse `/lorax vote` to break the tie!",
+    tied_entry: "`{name}` (by {submitter})",
+    no_names_left: "No valid tree names remain. The event has ended without a winner.",
+    nobody_voted: "Nobody voted this round",
+    still_tied: "We're still tied after {round} tiebreaker rounds",
+    draw: "🎲 {reason}, so the winner is drawn at random!\n\nCandidates, sorted alphabetically:\n{names}\n\nSeed: `{seed}`\n`{seed} mod {count} = {index}`, so the winner is **{winner}**.",
+    top_entries: "Top entries:",
+    entry: "`{name}` by {submitter} ({count} votes)",
+    more_entries: "...and {count} more entries!",
+    thread_name: "🗳️ {location} Tree Name Campaign",
+    speakers: "Some of our candidates speaking today:\n{names}\n\nMay the best tree win! 🎉",
+    representing: "🗣️ {submitter} representing `{name}`",
+    meanings: "📖 **What the names mean**",
+    candidate_post: "🌳 **{name}**, submitted by {submitter}. Make the case for it and talk it over here!",
+    statement: "📣 **In their own words**\n{statement}",
+    no_statement: "_No statement yet, {submitter} can pin one here with `/lorax statement`._",
+    submissions_title: "🌱 Name our new {location} node",
+    submissions_description: "Got a cool tree name in mind? Submit it with `/lorax submit`! Just make sure it's all lowercase letters, like `oak` or `willow`.",
+    submissions_closed_description: "Submissions have closed, thanks for all the ideas!",
+    submissions_field: "Submissions",
+    names_per_person_field: "Names per person",
+    voting_title: "🗳️ Vote for the name of our new {location} node",
+    tiebreaker_title: "🎯 Tiebreaker round {round} for our new {location} node",
+    single_description: "Press **Vote** or use `/lorax vote` to pick your favorite!",
+    ranked_description: "This is a ranked vote, press **Vote** or use `/lorax vote` to rank as many names as you like, favorite first!",
+    approval_description: "This is an approval vote, press **Vote** or use `/lorax vote` to pick every name you'd be happy with!",
+    voting_closed_description: "Voting has closed, thanks to everyone who took part!",
+    names_field: "Names",
+    votes_field: "Votes",
+    closes_field: "Closes",
+    closed_field: "Closed",
+    live_results_field: "Live results",
+    results_field: "Results",
+    first_preferences_field: "First preferences",
+    more_results: "...and {count} more",
+    event_footer: "Event #{event}",
+    single_method: "Single choice",
+    ranked_method: "Ranked choice (instant-runoff)",
+    approval_method: "Approval (pick every name you like)",
+    blind_footer: "Blind vote, results are revealed when voting closes",
+    vote_button: "Vote",
+};
+
+static GERMAN: Phrases = Phrases {
+    tied_names: "Diese Namen liegen gleichauf:",
+    last_round: "Das ist die letzte Runde. Steht es danach immer noch unentschieden, wird der Gewinner ausgelost. 🎲",
+    draw_if_no_votes: "Wenn niemand abstimmt, wird der Gewinner ausgelost. 🎲",
+    thread_tiebreaker: "🎯 Weiter geht's mit Stichwahlrunde {round}! Diese Namen liegen gleichauf:\n{names}\n\nStimmt mit `/lorax vote` ab, um den Gleichstand aufzulösen!",
+    thread_reminder: "⏰ Die Abstimmung endet {end}! Letzte Chance, für euren Namen zu werben. 🌳",
+    thread_duration: "⏰ Die Abstimmungsdauer wurde angepasst! Neues Ende: {end}",
+    tie_between: "Hey {role}! Gleichstand zwischen {names}! Zeit für Stichwahlrunde {round}!\n\nStimmt mit `/lorax vote` ab, um den Gleichstand aufzulösen!",
+    tied_entry: "`{name}` (von {submitter})",
+    no_names_left: "Es sind keine gültigen Baumnamen mehr übrig. Das Event endet ohne Gewinner.",
+    nobody_voted: "Diese Runde hat niemand abgestimmt",
+    still_tied: "Nach {round} Stichwahlrunden steht es immer noch unentschieden",
+    draw: "🎲 {reason}, daher wird der Gewinner ausgelost!\n\nKandidaten, alphabetisch sortiert:\n{names}\n\nSeed: `{seed}`\n`{seed} mod {count} = {index}`, also gewinnt **{winner}**.",
+    top_entries: "Die besten Einreichungen:",
+    entry: "`{name}` von {submitter} ({count} Stimmen)",
+    more_entries: "...und {count} weitere Einreichungen!",
+    thread_name: "🗳️ Namenswahlkampf {location}",
+    speakers: "Einige unserer Kandidaten sprechen heute:\n{names}\n\nMöge der beste Baum gewinnen! 🎉",
+    representing: "🗣️ {submitter} für `{name}`",
+    meanings: "📖 **Was die Namen bedeuten**",
+    candidate_post: "🌳 **{name}**, eingereicht von {submitter}. Hier könnt ihr dafür werben und darüber diskutieren!",
+    statement: "📣 **In eigenen Worten**\n{statement}",
+    no_statement: "_Noch kein Statement, {submitter} kann mit `/lorax statement` eins anheften._",
+    submissions_title: "🌱 Benennt unseren neuen Node in {location}",
+    submissions_description: "Du hast einen coolen Baumnamen im Kopf? Reich ihn mit `/lorax submit` ein! Achte nur darauf, dass er nur aus Kleinbuchstaben besteht, wie `oak` oder `willow`.",
+    submissions_closed_description: "Die Einreichungen sind geschlossen, danke für all die Ideen!",
+    submissions_field: "Einreichungen",
+    names_per_person_field: "Namen pro Person",
+    voting_title: "🗳️ Stimmt über den Namen unseres neuen Nodes in {location} ab",
+    tiebreaker_title: "🎯 Stichwahlrunde {round} für unseren neuen Node in {location}",
+    single_description: "Drück auf **Abstimmen** oder nutze `/lorax vote`, um deinen Favoriten zu wählen!",
+    ranked_description: "Das ist eine Rangwahl. Drück auf **Abstimmen** oder nutze `/lorax vote`, um so viele Namen zu ordnen, wie du möchtest, deinen Favoriten zuerst!",
+    approval_description: "Das ist eine Zustimmungswahl. Drück auf **Abstimmen** oder nutze `/lorax vote`, um jeden Namen zu wählen, mit dem du zufrieden wärst!",
+    voting_closed_description: "Die Abstimmung ist beendet, danke an alle, die mitgemacht haben!",
+    names_field: "Namen",
+    votes_field: "Stimmen",
+    closes_field: "Endet",
+    closed_field: "Beendet",
+    live_results_field: "Aktueller Stand",
+    results_field: "Ergebnisse",
+    first_preferences_field: "Erstpräferenzen",
+    more_results: "...und {count} weitere",
+    event_footer: "Event #{event}",
+    single_method: "Einfache Wahl",
+    ranked_method: "Rangwahl (Instant-Runoff)",
+    approval_method: "Zustimmungswahl (wähle jeden Namen, der dir gefällt)",
+    blind_footer: "Verdeckte Abstimmung, die Ergebnisse gibt es zum Schluss",
+    vote_button: "Abstimmen",
+};
+
+static SPANISH: Phrases = Phrases {
+    tied_names: "Estos nombres están empatados:",
+    last_round: "Esta es la última ronda; si sigue habiendo empate, el ganador se sorteará. 🎲",
+    draw_if_no_votes: "Si nadie vota, el ganador se sorteará. 🎲",
+    thread_tiebreaker: "🎯 ¡Vamos a la ronda de desempate {round}! Estos nombres están empatados:\n{names}\n\n¡Usa `/lorax vote` para ayudar a deshacer el empate!",
+    thread_reminder: "⏰ ¡La votación se cierra {end}! Última oportunidad para defender tu nombre. 🌳",
+    thread_duration: "⏰ ¡Se ha ajustado la duración de la votación! Nuevo cierre: {end}",
+    tie_between: "¡Hola {role}! ¡Hay un empate entre {names}! ¡Es hora de la ronda de desempate {round}!\n\n¡Usa `/lorax vote` para deshacer el empate!",
+    tied_entry: "`{name}` (de {submitter})",
+    no_names_left: "No queda ningún nombre válido. El evento termina sin ganador.",
+    nobody_voted: "Nadie ha votado en esta ronda",
+    still_tied: "Seguimos empatados después de {round} rondas de desempate",
+    draw: "🎲 {reason}, así que el ganador se sortea.\n\nCandidatos, en orden alfabético:\n{names}\n\nSemilla: `{seed}`\n`{seed} mod {count} = {index}`, así que el ganador es **{winner}**.",
+    top_entries: "Mejores propuestas:",
+    entry: "`{name}` de {submitter} ({count} votos)",
+    more_entries: "...¡y {count} propuestas más!",
+    thread_name: "🗳️ Campaña de nombres de {location}",
+    speakers: "Algunos de nuestros candidatos que hablan hoy:\n{names}\n\n¡Que gane el mejor árbol! 🎉",
+    representing: "🗣️ {submitter} representa a `{name}`",
+    meanings: "📖 **Qué significan los nombres**",
+    candidate_post: "🌳 **{name}**, propuesto por {submitter}. ¡Defendedlo y comentadlo aquí!",
+    statement: "📣 **En sus propias palabras**\n{statement}",
+    no_statement: "_Todavía no hay declaración, {submitter} puede fijar una con `/lorax statement`._",
+    submissions_title: "🌱 Ponle nombre a nuestro nuevo nodo en {location}",
+    submissions_description: "¿Tienes en mente un buen nombre de árbol? ¡Envíalo con `/lorax submit`! Solo asegúrate de que esté todo en minúsculas, como `oak` o `willow`.",
+    submissions_closed_description: "Las propuestas se han cerrado, ¡gracias por todas las ideas!",
+    submissions_field: "Propuestas",
+    names_per_person_field: "Nombres por persona",
+    voting_title: "🗳️ Vota el nombre de nuestro nuevo nodo en {location}",
+    tiebreaker_title: "🎯 Ronda de desempate {round} para nuestro nuevo nodo en {location}",
+    single_description: "¡Pulsa **Votar** o usa `/lorax vote` para elegir tu favorito!",
+    ranked_description: "Es una votación por orden de preferencia, ¡pulsa **Votar** o usa `/lorax vote` para ordenar tantos nombres como quieras, tu favorito primero!",
+    approval_description: "Es una votación por aprobación, ¡pulsa **Votar** o usa `/lorax vote` para elegir todos los nombres que te gusten!",
+    voting_closed_description: "La votación se ha cerrado, ¡gracias a todos los que habéis participado!",
+    names_field: "Nombres",
+    votes_field: "Votos",
+    closes_field: "Cierra",
+    closed_field: "Cerrado",
+    live_results_field: "Resultados en directo",
+    results_field: "Resultados",
+    first_preferences_field: "Primeras preferencias",
+    more_results: "...y {count} más",
+    event_footer: "Evento #{event}",
+    single_method: "Opción única",
+    ranked_method: "Por orden de preferencia (segunda vuelta instantánea)",
+    approval_method: "Por aprobación (elige todos los nombres que te gusten)",
+    blind_footer: "Votación a ciegas, los resultados se revelan al cerrar",
+    vote_button: "Votar",
+};
+
+static FRENCH: Phrases = Phrases {
+    tied_names: "Ces noms sont à égalité :",
+    last_round: "C'est le dernier tour : en cas de nouvelle égalité, le gagnant sera tiré au sort. 🎲",
+    draw_if_no_votes: "Si personne ne vote, le gagnant sera tiré au sort. 🎲",
+    thread_tiebreaker: "🎯 Direction le tour de départage {round} ! Ces noms sont à égalité :\n{names}\n\nUtilisez `/lorax vote` pour aider à les départager !",
+    thread_reminder: "⏰ Le vote se termine {end} ! Dernière chance de défendre votre nom. 🌳",
+    thread_duration: "⏰ La durée du vote a été modifiée ! Nouvelle fin : {end}",
+    tie_between: "Salut {role} ! Égalité entre {names} ! Place au tour de départage {round} !\n\nUtilisez `/lorax vote` pour les départager !",
+    tied_entry: "`{name}` (par {submitter})",
+    no_names_left: "Il ne reste aucun nom valide. L'événement se termine sans gagnant.",
+    nobody_voted: "Personne n'a voté à ce tour",
+    still_tied: "Toujours à égalité après {round} tours de départage",
+    draw: "🎲 {reason}, le gagnant est donc tiré au sort !\n\nCandidats, par ordre alphabétique :\n{names}\n\nGraine : `{seed}`\n`{seed} mod {count} = {index}`, le gagnant est donc **{winner}**.",
+    top_entries: "Meilleures propositions :",
+    entry: "`{name}` par {submitter} ({count} votes)",
+    more_entries: "...et {count} autres propositions !",
+    thread_name: "🗳️ Campagne de noms {location}",
+    speakers: "Quelques-uns de nos candidats prennent la parole aujourd'hui :\n{names}\n\nQue le meilleur arbre gagne ! 🎉",
+    representing: "🗣️ {submitter} pour `{name}`",
+    meanings: "📖 **Ce que signifient les noms**",
+    candidate_post: "🌳 **{name}**, proposé par {submitter}. Défendez-le et discutez-en ici !",
+    statement: "📣 **Dans ses propres mots**\n{statement}",
+    no_statement: "_Pas encore de déclaration, {submitter} peut en épingler une avec `/lorax statement`._",
+    submissions_title: "🌱 Nommez notre nouveau nœud à {location}",
+    submissions_description: "Vous avez un super nom d'arbre en tête ? Proposez-le avec `/lorax submit` ! Veillez simplement à n'utiliser que des lettres minuscules, comme `oak` ou `willow`.",
+    submissions_closed_description: "Les propositions sont closes, merci pour toutes vos idées !",
+    submissions_field: "Propositions",
+    names_per_person_field: "Noms par personne",
+    voting_title: "🗳️ Votez pour le nom de notre nouveau nœud à {location}",
+    tiebreaker_title: "🎯 Tour de départage {round} pour notre nouveau nœud à {location}",
+    single_description: "Appuyez sur **Voter** ou utilisez `/lorax vote` pour choisir votre favori !",
+    ranked_description: "C'est un vote par classement, appuyez sur **Voter** ou utilisez `/lorax vote` pour classer autant de noms que vous voulez, votre favori en premier !",
+    approval_description: "C'est un vote par approbation, appuyez sur **Voter** ou utilisez `/lorax vote` pour choisir tous les noms qui vous conviennent !",
+    voting_closed_description: "Le vote est clos, merci à tous ceux qui ont participé !",
+    names_field: "Noms",
+    votes_field: "Votes",
+    closes_field: "Fin",
+    closed_field: "Terminé",
+    live_results_field: "Résultats en direct",
+    results_field: "Résultats",
+    first_preferences_field: "Premiers choix",
+    more_results: "...et {count} de plus",
+    event_footer: "Événement #{event}",
+    single_method: "Choix unique",
+    ranked_method: "Vote par classement (second tour instantané)",
+    approval_method: "Approbation (choisissez tous les noms qui vous plaisent)",
+    blind_footer: "Vote à l'aveugle, les résultats sont révélés à la clôture",
+    vote_button: "Voter",
+};
+
+static DUTCH: Phrases = Phrases {
+    tied_names: "Deze namen staan gelijk:",
+    last_round: "Dit is de laatste ronde; staat het daarna nog gelijk, dan wordt de winnaar geloot. 🎲",
+    draw_if_no_votes: "Als niemand stemt, wordt de winnaar geloot. 🎲",
+    thread_tiebreaker: "🎯 Op naar beslissingsronde {round}! Deze namen staan gelijk:\n{names}\n\nGebruik `/lorax vote` om de knoop door te hakken!",
+    thread_reminder: "⏰ De stemming sluit {end}! Laatste kans om campagne te voeren. 🌳",
+    thread_duration: "⏰ De stemduur is aangepast! Nieuwe eindtijd: {end}",
+    tie_between: "Hoi {role}! Het staat gelijk tussen {names}! Tijd voor beslissingsronde {round}!\n\nGebruik `/lorax vote` om de knoop door te hakken!",
+    tied_entry: "`{name}` (door {submitter})",
+    no_names_left: "Er zijn geen geldige boomnamen meer over. Het evenement eindigt zonder winnaar.",
+    nobody_voted: "Niemand heeft deze ronde gestemd",
+    still_tied: "Het staat nog steeds gelijk na {round} beslissingsrondes",
+    draw: "🎲 {reason}, dus de winnaar wordt geloot!\n\nKandidaten, op alfabetische volgorde:\n{names}\n\nSeed: `{seed}`\n`{seed} mod {count} = {index}`, dus de winnaar is **{winner}**.",
+    top_entries: "Beste inzendingen:",
+    entry: "`{name}` door {submitter} ({count} stemmen)",
+    more_entries: "...en nog {count} inzendingen!",
+    thread_name: "🗳️ Naamcampagne {location}",
+    speakers: "Enkele van onze kandidaten die vandaag spreken:\n{names}\n\nMoge de beste boom winnen! 🎉",
+    representing: "🗣️ {submitter} namens `{name}`",
+    meanings: "📖 **Wat de namen betekenen**",
+    candidate_post: "🌳 **{name}**, ingezonden door {submitter}. Voer er hier campagne voor en bespreek het!",
+    statement: "📣 **In eigen woorden**\n{statement}",
+    no_statement: "_Nog geen verklaring, {submitter} kan er een vastzetten met `/lorax statement`._",
+    submissions_title: "🌱 Geef onze nieuwe node in {location} een naam",
+    submissions_description: "Heb je een toffe boomnaam in gedachten? Zend hem in met `/lorax submit`! Zorg wel dat hij alleen uit kleine letters bestaat, zoals `oak` of `willow`.",
+    submissions_closed_description: "De inzendingen zijn gesloten, bedankt voor alle ideeën!",
+    submissions_field: "Inzendingen",
+    names_per_person_field: "Namen per persoon",
+    voting_title: "🗳️ Stem op de naam van onze nieuwe node in {location}",
+    tiebreaker_title: "🎯 Beslissingsronde {round} voor onze nieuwe node in {location}",
+    single_description: "Druk op **Stemmen** of gebruik `/lorax vote` om je favoriet te kiezen!",
+    ranked_description: "Dit is een rangschikkingsstemming, druk op **Stemmen** of gebruik `/lorax vote` om zoveel namen te rangschikken als je wilt, je favoriet eerst!",
+    approval_description: "Dit is een goedkeuringsstemming, druk op **Stemmen** of gebruik `/lorax vote` om elke naam te kiezen waar je blij mee zou zijn!",
+    voting_closed_description: "De stemming is gesloten, bedankt aan iedereen die heeft meegedaan!",
+    names_field: "Namen",
+    votes_field: "Stemmen",
+    closes_field: "Sluit",
+    closed_field: "Gesloten",
+    live_results_field: "Tussenstand",
+    results_field: "Uitslag",
+    first_preferences_field: "Eerste voorkeuren",
+    more_results: "...en nog {count}",
+    event_footer: "Evenement #{event}",
+    single_method: "Eén keuze",
+    ranked_method: "Rangschikking (instant-runoff)",
+    approval_method: "Goedkeuring (kies elke naam die je leuk vindt)",
+    blind_footer: "Blinde stemming, de uitslag wordt bekend als de stemming sluit",
+    vote_button: "Stemmen",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_known_placeholders() {
+        assert!(validate(LoraxMessage::Winner, "{role} {winner} won in {location}!").is_ok());
+        assert!(validate(LoraxMessage::Teaser, "No placeholders at all").is_ok());
+    }
+
+    #[test]
+    fn rejects_placeholders_of_other_messages() {
+        let error = validate(LoraxMessage::Teaser, "{winner} won").unwrap_err();
+        assert!(error.contains("`{winner}`"));
+        assert!(error.contains("`{start}`"));
+    }
+
+    #[test]
+    fn rejects_end_in_announcements() {
+        assert!(validate(LoraxMessage::SubmissionsOpen, "Closes {end}").is_err());
+        assert!(validate(LoraxMessage::VotingOpen, "Closes {end}").is_err());
+        assert!(validate(LoraxMessage::Tiebreaker, "Closes {end}").is_err());
+        assert!(validate(LoraxMessage::VotingReminder, "Closes {end}").is_ok());
+    }
+
+    #[test]
+    fn rejects_unbalanced_braces() {
+        assert!(validate(LoraxMessage::Teaser, "Starts {start").is_err());
+        assert!(validate(LoraxMessage::Teaser, "Starts start}").is_err());
+        assert!(validate(LoraxMessage::Teaser, "} {start}").is_err());
+    }
+
+    #[test]
+    fn rejects_empty_and_long_messages() {
+        assert!(validate(LoraxMessage::Teaser, "  \n").is_err());
+        assert!(validate(LoraxMessage::Teaser, &"a".repeat(MAX_MESSAGE_LEN)).is_ok());
+        assert!(validate(LoraxMessage::Teaser, &"a".repeat(MAX_MESSAGE_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn built_in_copy_only_uses_its_placeholders() {
+        use poise::ChoiceParameter;
+
+        for locale in (0..).map_while(LoraxLocale::from_index) {
+            for message in (0..).map_while(LoraxMessage::from_index) {
+                let text = built_in(locale, message);
+                assert!(validate(message, text).is_ok(), "{:?} {:?}", locale, message);
+            }
+        }
+    }
+
+    #[test]
+    fn render_fills_in_placeholders() {
+        let text = render(
+            "{role} {location} {start}",
+            &[("role", "@Lorax"), ("location", "Oak")],
+        );
+        assert_eq!(text, "@Lorax Oak {start}");
+    }
+}
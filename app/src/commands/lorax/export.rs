@@ -0,0 +1,290 @@
+use super::anonymised_ballots;
+use crate::db::lorax_history::ArchivedEvent;
+use crate::settings::LoraxState;
+use crate::util::csv_row;
+use poise::serenity_prelude::CreateAttachment;
+use poise::ChoiceParameter;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum ExportFormat {
+    #[name = "JSON"]
+    Json,
+    #[name = "CSV"]
+    Csv,
+}
+
+/// Everything published about an event, running or finished.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventExport {
+    pub event_id: u64,
+    /// Running events don't have placements or a winner yet.
+    pub finished: bool,
+    pub location: String,
+    /// The outcome of a finished event, or the phase of a running one.
+    pub status: String,
+    /// Only known while the event is running.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voting_method: Option<String>,
+    pub winner: Option<String>,
+    pub started_at: i64,
+    pub voting_started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+    pub submissions: Vec<ExportSubmission>,
+    pub rounds: Vec<ExportRound>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSubmission {
+    pub name: String,
+    /// A string, since Discord IDs don't fit in a JavaScript number.
+    pub submitter_id: String,
+    pub meaning: Option<String>,
+    pub placement: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportRound {
+    /// 0 is the main vote, anything after that is a tiebreaker.
+    pub round: u32,
+    /// Not set while the round is still open.
+    pub ended_at: Option<i64>,
+    pub tallies: Vec<ExportTally>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ballots: Option<Vec<Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportTally {
+    pub name: String,
+    pub votes: usize,
+}
+
+impl EventExport {
+    /// Idle states have nothing to export and return `None`.
+    pub fn from_running(event_id: u64, state: &LoraxState, with_ballots: bool) -> Option<Self> {
+        let (location, submissions, timeline, voting_method) = match state {
+            LoraxState::Idle => return None,
+            LoraxState::Submissions {
+                location,
+                submissions,
+                timeline,
+                voting_method,
+                ..
+            }
+            | LoraxState::Voting {
+                location,
+                submissions,
+                timeline,
+                voting_method,
+                ..
+            }
+            | LoraxState::TieBreaker {
+                location,
+                submissions,
+                timeline,
+                voting_method,
+                ..
+            } => (location, submissions, timeline, voting_method),
+        };
+
+        let mut rounds: Vec<ExportRound> = timeline
+            .rounds
+            .iter()
+            .map(|round| ExportRound {
+                round: round.round,
+                ended_at: Some(round.ended_at),
+                tallies: round
+                    .tallies
+                    .iter()
+                    .map(|(name, votes)| ExportTally {
+                        name: name.clone(),
+                        votes: *votes,
+                    })
+                    .collect(),
+                ballots: with_ballots.then(|| round.ballots.clone()),
+            })
+            .collect();
+
+        if let LoraxState::Voting {
+            options,
+            votes,
+            ballots,
+            ..
+        }
+        | LoraxState::TieBreaker {
+            options,
+            votes,
+            ballots,
+            ..
+        } = state
+        {
+            let tally = state.live_tally();
+            rounds.push(ExportRound {
+                round: match state {
+                    LoraxState::TieBreaker { round, .. } => *round,
+                    _ => 0,
+                },
+                ended_at: None,
+                tallies: options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| ExportTally {
+                        name: name.clone(),
+                        votes: tally.get(&i).copied().unwrap_or(0),
+                    })
+                    .collect(),
+                ballots: with_ballots.then(|| anonymised_ballots(options, votes, ballots)),
+            });
+        }
+
+        Some(Self {
+            event_id,
+            finished: false,
+            location: location.clone(),
+            status: state.phase_name().to_string(),
+            voting_method: Some(voting_method.name().to_string()),
+            winner: None,
+            started_at: timeline.started_at,
+            voting_started_at: timeline.voting_started_at,
+            ended_at: None,
+            submissions: submissions
+                .iter()
+                .map(|submission| ExportSubmission {
+                    name: submission.name.clone(),
+                    submitter_id: submission.submitter.to_string(),
+                    meaning: submission.meaning.clone(),
+                    placement: None,
+                })
+                .collect(),
+            rounds,
+        })
+    }
+
+    pub fn from_archived(event: &ArchivedEvent, with_ballots: bool) -> Self {
+        let mut round_numbers: Vec<u32> = event.tallies.iter().map(|t| t.round).collect();
+        round_numbers.dedup();
+
+        let rounds = round_numbers
+            .into_iter()
+            .map(|round| {
+                let tallies: Vec<_> = event.tallies.iter().filter(|t| t.round == round).collect();
+                ExportRound {
+                    round,
+                    ended_at: tallies.first().map(|t| t.ended_at),
+                    tallies: tallies
+                        .iter()
+                        .map(|t| ExportTally {
+                            name: t.name.clone(),
+                            votes: t.votes,
+                        })
+                        .collect(),
+                    ballots: with_ballots.then(|| {
+                        event
+                            .ballots
+                            .iter()
+                            .filter(|b| b.round == round)
+                            .map(|b| b.choices.clone())
+                            .collect()
+                    }),
+                }
+            })
+            .collect();
+
+        Self {
+            event_id: event.id as u64,
+            finished: true,
+            location: event.location.clone(),
+            status: event.outcome.as_str().to_string(),
+            voting_method: None,
+            winner: event.winner.clone(),
+            started_at: event.started_at,
+            voting_started_at: event.voting_started_at,
+            ended_at: Some(event.ended_at),
+            submissions: event
+                .submissions
+                .iter()
+                .map(|submission| ExportSubmission {
+                    name: submission.name.clone(),
+                    submitter_id: submission.submitter.to_string(),
+                    meaning: submission.meaning.clone(),
+                    placement: submission.placement,
+                })
+                .collect(),
+            rounds,
+        }
+    }
+
+    /// One JSON file, or a CSV file each for submissions, votes and ballots.
+    pub fn attachments(&self, format: ExportFormat) -> Result<Vec<CreateAttachment>, crate::Error> {
+        let prefix = format!(
+            "lorax-{}-{}",
+            if self.finished { "history" } else { "event" },
+            self.event_id
+        );
+
+        if format == ExportFormat::Json {
+            let json = serde_json::to_vec_pretty(self)?;
+            return Ok(vec![CreateAttachment::bytes(
+                json,
+                format!("{}.json", prefix),
+            )]);
+        }
+
+        let mut submissions = csv_row(["name", "submitter_id", "meaning", "placement"]);
+        for submission in &self.submissions {
+            submissions.push_str(&csv_row([
+                submission.name.as_str(),
+                &submission.submitter_id,
+                submission.meaning.as_deref().unwrap_or_default(),
+                &submission
+                    .placement
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+            ]));
+        }
+
+        let mut votes = csv_row(["round", "name", "votes", "final"]);
+        for round in &self.rounds {
+            for tally in &round.tallies {
+                votes.push_str(&csv_row([
+                    round.round.to_string().as_str(),
+                    &tally.name,
+                    &tally.votes.to_string(),
+                    if round.ended_at.is_some() {
+                        "true"
+                    } else {
+                        "false"
+                    },
+                ]));
+            }
+        }
+
+        let mut attachments = vec![
+            CreateAttachment::bytes(submissions, format!("{}-submissions.csv", prefix)),
+            CreateAttachment::bytes(votes, format!("{}-votes.csv", prefix)),
+        ];
+
+        if self.rounds.iter().any(|round| round.ballots.is_some()) {
+            let mut ballots = csv_row(["round", "ballot", "rank", "name"]);
+            for round in &self.rounds {
+                for (i, ballot) in round.ballots.iter().flatten().enumerate() {
+                    for (rank, name) in ballot.iter().enumerate() {
+                        ballots.push_str(&csv_row([
+                            round.round.to_string().as_str(),
+                            &(i + 1).to_string(),
+                            &(rank + 1).to_string(),
+                            name,
+                        ]));
+                    }
+                }
+            }
+            attachments.push(CreateAttachment::bytes(
+                ballots,
+                format!("{}-ballots.csv", prefix),
+            ));
+        }
+
+        Ok(attachments)
+    }
+}
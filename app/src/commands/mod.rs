@@ -1,4 +1,8 @@
 pub mod network;
 pub mod query;
 pub mod modrinth;
-pub mod lorax;
\ No newline at end of file
+pub mod lorax;
+pub mod nodes;
+pub mod stats;
+pub mod graph;
+pub mod alerts;
@@ -1,3 +1,4 @@
+use crate::db::stat_channels::{self, StatChannel, StatFormat, VALUE_PLACEHOLDER};
 use crate::{Context, Error};
 use poise::serenity_prelude::{
     self as serenity, ButtonStyle, Channel, ChannelType, CreateActionRow, CreateButton,
@@ -5,6 +6,35 @@ use poise::serenity_prelude::{
 };
 use std::{sync::Arc, vec};
 
+/// What `/setup_stats` creates. More can be added with `/stats add`.
+const DEFAULT_STATS: &[(&str, &str, StatFormat)] = &[
+    (
+        "🖥️ Active Nodes {value}",
+        "count(up{job=\"node\"} == 1)",
+        StatFormat::Count,
+    ),
+    (
+        "🧠 Mem {value}",
+        "sum(node_memory_MemTotal_bytes - node_memory_MemAvailable_bytes) or vector(0)",
+        StatFormat::Bytes,
+    ),
+    (
+        "💾 Disk {value}",
+        "sum(node_filesystem_size_bytes{mountpoint=\"/\"} - node_filesystem_free_bytes{mountpoint=\"/\"})",
+        StatFormat::Bytes,
+    ),
+    (
+        "🌐 Net {value}",
+        "sum(rate(node_network_receive_bytes_total[5m]) + rate(node_network_transmit_bytes_total[5m])) or vector(0)",
+        StatFormat::Rate,
+    ),
+    (
+        "📊 7d Total {value}",
+        "sum(increase(node_network_receive_bytes_total[7d]) + increase(node_network_transmit_bytes_total[7d])) or vector(0)",
+        StatFormat::Bytes,
+    ),
+];
+
 async fn create_stat_channel(
     ctx: &Context<'_>,
     guild_id: serenity::GuildId,
@@ -14,7 +44,7 @@ async fn create_stat_channel(
     let guild_channel = guild_id
         .create_channel(
             &ctx.serenity_context().http,
-            CreateChannel::new(format!("{} Loading...", name))
+            CreateChannel::new(name.replace(VALUE_PLACEHOLDER, "Loading..."))
                 .kind(ChannelType::Voice)
                 .category(category_id),
         )
@@ -80,6 +110,7 @@ pub async fn setup_stats(
                 "yes" => {
                     for channel in existing_channels {
                         channel.delete(&ctx.serenity_context().http).await?;
+                        stat_channels::remove(&pool, guild_id, channel.id).await?;
                     }
                 }
                 "no" => {
@@ -102,44 +133,35 @@ pub async fn setup_stats(
         }
     }
 
-    let channel_configs = vec![
-        ("🖥️ Nodes", "nodes_channel"),
-        ("🧠 Memory", "memory_channel"),
-        ("💾 Storage", "storage_channel"),
-        ("🌐 Network", "network_channel"),
-        ("📊 Bandwidth", "network_total_channel"),
-    ];
-
     let mut created_channels = Vec::new();
-    for (name, _) in &channel_configs {
-        match create_stat_channel(&ctx, guild_id, name, channel_id).await {
-            Ok(new_channel) => created_channels.push((name, new_channel)),
+    for (label, _, _) in DEFAULT_STATS {
+        match create_stat_channel(&ctx, guild_id, label, channel_id).await {
+            Ok(new_channel) => created_channels.push(new_channel),
             Err(e) => {
                 // Cleanup on error
-                for (_, ch) in created_channels {
+                for ch in created_channels {
                     let _ = ch.delete(&ctx.serenity_context().http).await;
                 }
-                return Err(format!("Failed to create {} channel: {}", name, e).into());
+                return Err(format!("Failed to create {} channel: {}", label, e).into());
             }
         }
     }
 
+    for (channel, (label, query, format)) in created_channels.iter().zip(DEFAULT_STATS) {
+        let stat = StatChannel {
+            channel_id: channel.id(),
+            query: query.to_string(),
+            label: label.to_string(),
+            format: *format,
+            datasource: None,
+        };
+        stat_channels::add(&pool, guild_id, &stat).await?;
+    }
+
     {
         let mut settings = ctx.data().settings.write().await;
         let mut guild_settings = settings.get_guild_settings(guild_id);
         guild_settings.stats_category = Some(channel_id);
-
-        for ((_, channel), (_, field_name)) in created_channels.iter().zip(channel_configs.iter()) {
-            match *field_name {
-                "nodes_channel" => guild_settings.nodes_channel = Some(channel.id()),
-                "network_channel" => guild_settings.network_channel = Some(channel.id()),
-                "storage_channel" => guild_settings.storage_channel = Some(channel.id()),
-                "memory_channel" => guild_settings.memory_channel = Some(channel.id()),
-                "network_total_channel" => guild_settings.network_total_channel = Some(channel.id()),
-                _ => {}
-            }
-        }
-
         settings.set_guild_settings(guild_id, guild_settings);
         settings.save(&pool).await?;
     }
@@ -0,0 +1,93 @@
+use crate::db::nodes::{self, NodeStatus};
+use crate::util::truncate_description;
+use crate::{Context, Error};
+use poise::serenity_prelude::{Color, CreateEmbed, CreateEmbedFooter};
+use poise::CreateReply;
+use std::sync::Arc;
+
+/// The node inventory. Winning Lorax names are added as planned nodes.
+#[poise::command(slash_command, subcommands("list", "set_status"))]
+pub async fn nodes(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Lists the nodes in the inventory.
+#[poise::command(slash_command)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "Only show nodes with this status"] status: Option<NodeStatus>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let nodes = nodes::list(&pool, guild_id, status).await?;
+    if nodes.is_empty() {
+        ctx.say("🌱 No nodes in the inventory yet. Winning Lorax names are added here automatically.")
+            .await?;
+        return Ok(());
+    }
+
+    let node_list = nodes
+        .iter()
+        .map(|node| {
+            let event = node
+                .event_id
+                .map(|id| format!(" (Lorax event `#{}`)", id))
+                .unwrap_or_default();
+            format!(
+                "{} **{}** — {}{}",
+                node.status.emoji(),
+                node.name,
+                node.location,
+                event
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let count = |status: NodeStatus| nodes.iter().filter(|n| n.status == status).count();
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::default()
+                .title("🌳 Node Inventory")
+                .description(truncate_description(node_list))
+                .footer(CreateEmbedFooter::new(format!(
+                    "🌱 {} planned • 🌳 {} live • 🍂 {} retired",
+                    count(NodeStatus::Planned),
+                    count(NodeStatus::Live),
+                    count(NodeStatus::Retired)
+                )))
+                .color(Color::from_rgb(67, 160, 71)),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Marks a node as planned, live or retired.
+#[poise::command(slash_command, required_permissions = "MANAGE_GUILD", ephemeral)]
+pub async fn set_status(
+    ctx: Context<'_>,
+    #[description = "Node name"] name: String,
+    #[description = "New status"] status: NodeStatus,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let name = name.trim().to_lowercase();
+
+    if nodes::set_status(&pool, guild_id, &name, status).await? {
+        ctx.say(format!(
+            "{} `{}` is now {}.",
+            status.emoji(),
+            name,
+            status.as_str()
+        ))
+        .await?;
+    } else {
+        ctx.say(format!("❌ There's no node called `{}` in the inventory.", name))
+            .await?;
+    }
+
+    Ok(())
+}
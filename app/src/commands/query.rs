@@ -1,15 +1,284 @@
-use crate::metrics::MetricsClient;
+use crate::metrics::{series_name, QueryResult, SampleValue, DEFAULT_DATASOURCE};
+use crate::util::{csv_row, truncate, truncate_field};
 use crate::{Context, Error};
 use chrono::Utc;
-use poise::serenity_prelude::{Color, CreateEmbed};
+use poise::serenity_prelude::{
+    ButtonStyle, Color, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed,
+    CreateEmbedFooter,
+};
 use poise::CreateReply;
-use std::time::Instant;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+pub async fn datasource_autocomplete<'a>(
+    ctx: Context<'a>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let partial = partial.to_lowercase();
+    ctx.data()
+        .datasources
+        .names()
+        .filter(|name| name.contains(&partial))
+        .map(str::to_string)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Checks a datasource picked in a command. The default datasource comes back
+/// as `None`.
+pub fn resolve_datasource(
+    ctx: Context<'_>,
+    datasource: Option<String>,
+) -> Result<Option<String>, String> {
+    let Some(name) = datasource.map(|d| d.trim().to_lowercase()) else {
+        return Ok(None);
+    };
+    let datasources = &ctx.data().datasources;
+    if name == DEFAULT_DATASOURCE {
+        Ok(None)
+    } else if datasources.get(Some(&name)).is_some() {
+        Ok(Some(name))
+    } else {
+        Err(format!(
+            "There's no datasource called `{}`. Pick one of: {}",
+            name,
+            datasources
+                .names()
+                .map(|n| format!("`{}`", n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
+
+/// Runs a query once, so a broken one is caught before it's saved. Callers
+/// check the result has the shape they need.
+pub async fn check_query(
+    ctx: Context<'_>,
+    datasource: Option<&str>,
+    query: &str,
+) -> Result<QueryResult, String> {
+    let client = ctx
+        .data()
+        .datasources
+        .get(datasource)
+        .ok_or_else(|| "That datasource is no longer configured.".to_string())?;
+    client
+        .fetch(query)
+        .await
+        .map_err(|e| format!("That query failed: {}", e))
+}
+
+const PAGE_SIZE: usize = 10;
+// Keeps a page of the table well inside the embed description limit
+const MAX_CELL_LEN: usize = 60;
+const PAGE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Vector and matrix results, one row per series.
+struct Table {
+    headers: &'static [&'static str],
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn from_result(result: &QueryResult) -> Option<Self> {
+        match result {
+            QueryResult::Vector(samples) => Some(Table {
+                headers: &["Series", "Value"],
+                rows: samples
+                    .iter()
+                    .map(|s| vec![series_name(&s.metric), s.value.1.clone()])
+                    .collect(),
+            }),
+            QueryResult::Matrix(series) => Some(Table {
+                headers: &["Series", "Samples", "Latest"],
+                rows: series
+                    .iter()
+                    .map(|s| {
+                        vec![
+                            series_name(&s.metric),
+                            s.values.len().to_string(),
+                            s.values.last().map(|v| v.1.clone()).unwrap_or_default(),
+                        ]
+                    })
+                    .collect(),
+            }),
+            QueryResult::Scalar(_) | QueryResult::String(_) => None,
+        }
+    }
+
+    fn total_pages(&self) -> usize {
+        self.rows.len().div_ceil(PAGE_SIZE).max(1)
+    }
+
+    /// A page as a monospaced table in a code block.
+    fn render_page(&self, page: usize) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .skip(page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| truncate(cell.clone(), MAX_CELL_LEN))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let widths = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                rows.iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([header.chars().count()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let line = |cells: &[String]| {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+        let headers = self
+            .headers
+            .iter()
+            .map(|h| h.to_string())
+            .collect::<Vec<_>>();
+        let separator = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+
+        let mut table = vec![line(&headers), line(&separator)];
+        table.extend(rows.iter().map(|row| line(row)));
+        format!("```\n{}\n```", table.join("\n"))
+    }
+}
+
+/// Every sample of a vector or matrix, one column per label.
+fn to_csv(result: &QueryResult) -> Option<String> {
+    let series: Vec<(&BTreeMap<String, String>, Vec<&SampleValue>)> = match result {
+        QueryResult::Vector(samples) => samples
+            .iter()
+            .map(|s| (&s.metric, vec![&s.value]))
+            .collect(),
+        QueryResult::Matrix(series) => series
+            .iter()
+            .map(|s| (&s.metric, s.values.iter().collect()))
+            .collect(),
+        QueryResult::Scalar(_) | QueryResult::String(_) => return None,
+    };
+    let labels = series
+        .iter()
+        .flat_map(|(metric, _)| metric.keys().map(String::as_str))
+        .collect::<BTreeSet<_>>();
+
+    let mut csv = csv_row(
+        ["timestamp", "value"]
+            .into_iter()
+            .chain(labels.iter().copied()),
+    );
+    for (metric, values) in &series {
+        for value in values {
+            let timestamp = value.0.to_string();
+            csv.push_str(&csv_row(
+                [timestamp.as_str(), value.1.as_str()].into_iter().chain(
+                    labels
+                        .iter()
+                        .map(|label| metric.get(*label).map(String::as_str).unwrap_or_default()),
+                ),
+            ));
+        }
+    }
+    Some(csv)
+}
+
+fn result_embed(
+    query: &str,
+    datasource: &str,
+    result: &QueryResult,
+    table: Option<&Table>,
+    page: usize,
+    elapsed: Duration,
+) -> CreateEmbed {
+    let embed = CreateEmbed::default()
+        .title("Prometheus Query")
+        .field("Query", truncate_field(format!("`{}`", query)), false)
+        .field("Datasource", format!("`{}`", datasource), false)
+        .color(Color::from_rgb(255, 255, 255))
+        .timestamp(Utc::now());
+
+    match (result, table) {
+        (QueryResult::Scalar(value) | QueryResult::String(value), _) => embed
+            .field(
+                "Result",
+                format!("```{}```", truncate(value.1.clone(), 1000)),
+                false,
+            )
+            .footer(CreateEmbedFooter::new(format!(
+                "{} • {}ms",
+                result_type(result),
+                elapsed.as_millis()
+            ))),
+        (_, Some(table)) if table.rows.is_empty() => embed
+            .description("No series matched.")
+            .footer(CreateEmbedFooter::new(format!(
+                "{} • {}ms",
+                result_type(result),
+                elapsed.as_millis()
+            ))),
+        (_, Some(table)) => {
+            embed
+                .description(table.render_page(page))
+                .footer(CreateEmbedFooter::new(format!(
+                    "{} • {} series • page {}/{} • {}ms",
+                    result_type(result),
+                    table.rows.len(),
+                    page + 1,
+                    table.total_pages(),
+                    elapsed.as_millis()
+                )))
+        }
+        (_, None) => embed,
+    }
+}
+
+fn result_type(result: &QueryResult) -> &'static str {
+    match result {
+        QueryResult::Scalar(_) => "scalar",
+        QueryResult::String(_) => "string",
+        QueryResult::Vector(_) => "vector",
+        QueryResult::Matrix(_) => "matrix",
+    }
+}
+
+fn page_buttons(page: usize, total_pages: usize) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new("query_prev")
+            .label("◀ Previous")
+            .style(ButtonStyle::Secondary)
+            .disabled(page == 0),
+        CreateButton::new("query_next")
+            .label("Next ▶")
+            .style(ButtonStyle::Secondary)
+            .disabled(page + 1 >= total_pages),
+    ])]
+}
 
 /// Executes a raw Prometheus query and returns the results in a formatted Discord embed
 ///
+/// Vectors and matrices are shown as a paged table of series, with a CSV of
+/// every sample attached when there's more than one page.
+///
 /// # Arguments
 /// * `ctx` - The command context
 /// * `query` - The Prometheus query string to execute
+/// * `datasource` - Name of the configured datasource, the default one if unset
 ///
 /// # Returns
 /// * `Result<(), Error>` - Success or error status of the command execution
@@ -17,26 +286,168 @@ use std::time::Instant;
 pub async fn query(
     ctx: Context<'_>,
     #[description = "Prometheus query to execute"] query: String,
+    #[description = "Datasource to query (default: the default one)"]
+    #[autocomplete = datasource_autocomplete]
+    datasource: Option<String>,
 ) -> Result<(), Error> {
-    // Initialize metrics client and timing
-    let client = MetricsClient::new();
+    let client = match resolve_datasource(ctx, datasource) {
+        Ok(datasource) => ctx.data().datasources.get(datasource.as_deref()).unwrap(),
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+
+    ctx.defer().await?;
     let start_time = Instant::now();
+    let result = match client.fetch(&query).await {
+        Ok(result) => result,
+        Err(e) => {
+            ctx.say(format!("❌ Query failed: {}", e)).await?;
+            return Ok(());
+        }
+    };
+    let elapsed = start_time.elapsed();
 
-    // Execute query and measure execution time
-    let result = client.fetch_metric(&query).await?;
-    let _ = start_time.elapsed();
-
-    // Construct the response embed
-    let message = CreateReply::default().embed(
-        CreateEmbed::default()
-            .title("Prometheus Query")
-            .field("Query", format!("`{}`", query), false)
-            .field("Result", format!("```{:.2?}```", result), false)
-            .color(Color::from_rgb(255, 255, 255))
-            .timestamp(Utc::now()),
-    );
+    let table = Table::from_result(&result);
+    let total_pages = table.as_ref().map_or(1, Table::total_pages);
+    let embed = |page| {
+        result_embed(
+            &query,
+            client.name(),
+            &result,
+            table.as_ref(),
+            page,
+            elapsed,
+        )
+    };
+
+    let mut reply = CreateReply::default().embed(embed(0));
+    if total_pages > 1 {
+        reply = reply.components(page_buttons(0, total_pages));
+        if let Some(csv) = to_csv(&result) {
+            reply = reply.attachment(CreateAttachment::bytes(csv, "query.csv"));
+        }
+    }
+    let msg = ctx.send(reply).await?;
+    if total_pages == 1 {
+        return Ok(());
+    }
+
+    let mut page: usize = 0;
+    while let Some(interaction) = msg
+        .message()
+        .await?
+        .await_component_interaction(&ctx.serenity_context().shard)
+        .author_id(ctx.author().id)
+        .timeout(PAGE_TIMEOUT)
+        .await
+    {
+        interaction.defer(ctx.http()).await?;
+        match interaction.data.custom_id.as_str() {
+            "query_prev" => page = page.saturating_sub(1),
+            "query_next" => page = (page + 1).min(total_pages - 1),
+            _ => continue,
+        }
+        msg.edit(
+            ctx,
+            CreateReply::default()
+                .embed(embed(page))
+                .components(page_buttons(page, total_pages)),
+        )
+        .await?;
+    }
+
+    // Stop offering buttons nobody is listening to anymore
+    msg.edit(
+        ctx,
+        CreateReply::default().embed(embed(page)).components(vec![]),
+    )
+    .await?;
 
-    // Send the response
-    ctx.send(message).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(json: &str) -> QueryResult {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn renders_vectors_as_aligned_columns() {
+        let table = Table::from_result(&result(
+            r#"{"resultType": "vector", "result": [
+                {"metric": {"__name__": "up", "job": "node"}, "value": [1700000000, "1"]},
+                {"metric": {"__name__": "up"}, "value": [1700000000, "0"]}
+            ]}"#,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            table.render_page(0),
+            "```\n\
+             Series          Value\n\
+             --------------  -----\n\
+             up{job=\"node\"}  1\n\
+             up              0\n\
+             ```"
+        );
+    }
+
+    #[test]
+    fn pages_hold_page_size_rows() {
+        let table = Table {
+            headers: &["Series", "Value"],
+            rows: (0..PAGE_SIZE + 3)
+                .map(|i| vec![format!("s{}", i), i.to_string()])
+                .collect(),
+        };
+
+        assert_eq!(table.total_pages(), 2);
+        // Header, separator, the rows and the code fences
+        assert_eq!(table.render_page(0).lines().count(), PAGE_SIZE + 4);
+        assert_eq!(table.render_page(1).lines().count(), 3 + 4);
+        assert!(table.render_page(1).contains(&format!("s{}", PAGE_SIZE)));
+    }
+
+    #[test]
+    fn long_cells_are_truncated() {
+        let table = Table {
+            headers: &["Series", "Value"],
+            rows: vec![vec!["x".repeat(MAX_CELL_LEN * 2), "1".to_string()]],
+        };
+
+        let page = table.render_page(0);
+        assert!(page.contains(&format!("{}…", "x".repeat(MAX_CELL_LEN - 1))));
+        assert!(!page.contains(&"x".repeat(MAX_CELL_LEN)));
+    }
+
+    #[test]
+    fn scalars_have_no_table_or_csv() {
+        let scalar = result(r#"{"resultType": "scalar", "result": [1700000000, "4"]}"#);
+        assert!(Table::from_result(&scalar).is_none());
+        assert!(to_csv(&scalar).is_none());
+    }
+
+    #[test]
+    fn csv_has_a_column_per_label() {
+        let csv = to_csv(&result(
+            r#"{"resultType": "matrix", "result": [
+                {"metric": {"job": "node", "instance": "a,b"}, "values": [[1, "0.5"], [2, "0.75"]]},
+                {"metric": {"job": "api"}, "values": [[1, "3"]]}
+            ]}"#,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            csv,
+            "timestamp,value,instance,job\r\n\
+             1,0.5,\"a,b\",node\r\n\
+             2,0.75,\"a,b\",node\r\n\
+             1,3,,api\r\n"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::commands::query::{check_query, datasource_autocomplete, resolve_datasource};
+use crate::db::stat_channels::{self, StatChannel, StatFormat, VALUE_PLACEHOLDER};
+use crate::metrics::{QueryResult, DEFAULT_DATASOURCE};
+use crate::util::truncate_description;
+use crate::{Context, Error};
+use poise::serenity_prelude::{
+    self as serenity, ChannelType, Color, CreateChannel, CreateEmbed, EditChannel,
+};
+use poise::CreateReply;
+use std::sync::Arc;
+
+// Channel names are capped at 100 characters, leave room for the value
+const MAX_LABEL_LEN: usize = 80;
+
+/// Channels whose names show live values from Prometheus.
+#[poise::command(slash_command, subcommands("add", "edit", "remove", "list"))]
+pub async fn stats(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+fn check_label(label: &str) -> Result<(), String> {
+    if !label.contains(VALUE_PLACEHOLDER) {
+        return Err(format!(
+            "The label needs `{}` where the value goes, e.g. `🎮 Players {}`.",
+            VALUE_PLACEHOLDER, VALUE_PLACEHOLDER
+        ));
+    }
+    if label.chars().count() > MAX_LABEL_LEN {
+        return Err(format!(
+            "The label can be at most {} characters long.",
+            MAX_LABEL_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// Stat channels show one number, from a scalar or the first sample.
+fn single_value(result: QueryResult) -> Result<f64, String> {
+    result
+        .value()
+        .ok_or_else(|| "That query didn't return a value.".to_string())
+}
+
+/// Adds a channel that shows the value of a PromQL query.
+#[poise::command(slash_command, required_permissions = "MANAGE_CHANNELS", ephemeral)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Channel name, with {value} where the value goes, e.g. 🎮 Players {value}"]
+    label: String,
+    #[description = "PromQL query that returns a single value"] query: String,
+    #[description = "How to show the value"] format: StatFormat,
+    #[description = "Channel to rename, otherwise a voice channel is made in the stats category"]
+    #[channel_types("Voice")]
+    channel: Option<serenity::GuildChannel>,
+    #[description = "Datasource to query (default: the default one)"]
+    #[autocomplete = datasource_autocomplete]
+    datasource: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+    let label = label.trim().to_string();
+
+    if let Err(reason) = check_label(&label) {
+        ctx.say(format!("❌ {}", reason)).await?;
+        return Ok(());
+    }
+    let datasource = match resolve_datasource(ctx, datasource) {
+        Ok(datasource) => datasource,
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+    if let Some(channel) = &channel {
+        if stat_channels::get(&pool, guild_id, channel.id)
+            .await?
+            .is_some()
+        {
+            ctx.say(format!(
+                "❌ {} already shows a stat. Use `/stats edit` to change it.",
+                channel
+            ))
+            .await?;
+            return Ok(());
+        }
+    }
+
+    ctx.defer_ephemeral().await?;
+    let value = match check_query(ctx, datasource.as_deref(), &query)
+        .await
+        .and_then(single_value)
+    {
+        Ok(value) => value,
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+
+    let created = channel.is_none();
+    let channel_id = match channel {
+        Some(channel) => channel.id,
+        None => {
+            let category = ctx
+                .data()
+                .settings
+                .read()
+                .await
+                .get_guild_settings(guild_id)
+                .stats_category;
+            let mut builder = CreateChannel::new(label.replace(VALUE_PLACEHOLDER, "Loading..."))
+                .kind(ChannelType::Voice);
+            if let Some(category) = category {
+                builder = builder.category(category);
+            }
+            guild_id.create_channel(ctx.http(), builder).await?.id
+        }
+    };
+
+    let stat = StatChannel {
+        channel_id,
+        query,
+        label,
+        format,
+        datasource,
+    };
+    let added = stat_channels::add(&pool, guild_id, &stat).await;
+    if !matches!(added, Ok(true)) {
+        if created {
+            // Don't leave a "Loading..." channel behind that nothing updates.
+            let _ = channel_id.delete(ctx.http()).await;
+        }
+        added?;
+        if created {
+            ctx.say("❌ The stat couldn't be saved, so the channel was removed again.")
+                .await?;
+        } else {
+            ctx.say(format!(
+                "❌ <#{}> already shows a stat. Use `/stats edit` to change it.",
+                channel_id
+            ))
+            .await?;
+        }
+        return Ok(());
+    }
+    channel_id
+        .edit(
+            ctx.http(),
+            EditChannel::new().name(stat.channel_name(value)),
+        )
+        .await?;
+
+    ctx.say(format!(
+        "✅ <#{}> now shows `{}`. It's refreshed every few minutes.",
+        channel_id,
+        stat.channel_name(value)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Changes the query, label or format of a stat channel.
+#[poise::command(slash_command, required_permissions = "MANAGE_CHANNELS", ephemeral)]
+pub async fn edit(
+    ctx: Context<'_>,
+    #[description = "Stat channel to change"] channel: serenity::GuildChannel,
+    #[description = "New channel name, with {value} where the value goes"] label: Option<String>,
+    #[description = "New PromQL query"] query: Option<String>,
+    #[description = "New way to show the value"] format: Option<StatFormat>,
+    #[description = "New datasource to query"]
+    #[autocomplete = datasource_autocomplete]
+    datasource: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let Some(mut stat) = stat_channels::get(&pool, guild_id, channel.id).await? else {
+        ctx.say(format!(
+            "❌ {} doesn't show a stat. Use `/stats add` to set one up.",
+            channel
+        ))
+        .await?;
+        return Ok(());
+    };
+    if let Some(label) = label {
+        stat.label = label.trim().to_string();
+    }
+    if let Some(query) = query {
+        stat.query = query;
+    }
+    if let Some(format) = format {
+        stat.format = format;
+    }
+    if datasource.is_some() {
+        match resolve_datasource(ctx, datasource) {
+            Ok(datasource) => stat.datasource = datasource,
+            Err(reason) => {
+                ctx.say(format!("❌ {}", reason)).await?;
+                return Ok(());
+            }
+        }
+    }
+
+    if let Err(reason) = check_label(&stat.label) {
+        ctx.say(format!("❌ {}", reason)).await?;
+        return Ok(());
+    }
+
+    ctx.defer_ephemeral().await?;
+    let value = match check_query(ctx, stat.datasource.as_deref(), &stat.query)
+        .await
+        .and_then(single_value)
+    {
+        Ok(value) => value,
+        Err(reason) => {
+            ctx.say(format!("❌ {}", reason)).await?;
+            return Ok(());
+        }
+    };
+
+    stat_channels::update(&pool, guild_id, &stat).await?;
+    channel
+        .id
+        .edit(
+            ctx.http(),
+            EditChannel::new().name(stat.channel_name(value)),
+        )
+        .await?;
+
+    ctx.say(format!(
+        "✅ {} now shows `{}`.",
+        channel,
+        stat.channel_name(value)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stops updating a stat channel.
+#[poise::command(slash_command, required_permissions = "MANAGE_CHANNELS", ephemeral)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Stat channel to stop updating"] channel: serenity::GuildChannel,
+    #[description = "Delete the channel too (default: no)"] delete_channel: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    if !stat_channels::remove(&pool, guild_id, channel.id).await? {
+        ctx.say(format!("❌ {} doesn't show a stat.", channel))
+            .await?;
+        return Ok(());
+    }
+
+    if delete_channel.unwrap_or(false) {
+        channel.delete(ctx.http()).await?;
+        ctx.say(format!("🗑️ Deleted `{}`.", channel.name)).await?;
+    } else {
+        ctx.say(format!("✅ {} is no longer updated.", channel))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Lists the stat channels and their queries.
+#[poise::command(slash_command)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().unwrap();
+    let pool = Arc::clone(&ctx.data().pool);
+
+    let stats = stat_channels::list(&pool, guild_id).await?;
+    if stats.is_empty() {
+        ctx.say("📈 No stat channels yet. Add one with `/stats add` or `/setup_stats`.")
+            .await?;
+        return Ok(());
+    }
+
+    let stat_list = stats
+        .iter()
+        .map(|stat| {
+            format!(
+                "<#{}> — `{}` ({}, {})\n```{}```",
+                stat.channel_id,
+                stat.label,
+                stat.format.as_str(),
+                stat.datasource.as_deref().unwrap_or(DEFAULT_DATASOURCE),
+                stat.query
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::default()
+                .title("📈 Stat Channels")
+                .description(truncate_description(stat_list))
+                .color(Color::from_rgb(255, 255, 255)),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,421 @@
+use crate::db::stat_channels::StatFormat;
+use crate::Error;
+use chrono::Utc;
+use poise::serenity_prelude::{Color, GuildId, UserId};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum Comparison {
+    #[name = "above (>)"]
+    Above,
+    #[name = "at least (>=)"]
+    AtLeast,
+    #[name = "below (<)"]
+    Below,
+    #[name = "at most (<=)"]
+    AtMost,
+    #[name = "equal to (==)"]
+    Equal,
+    #[name = "not equal to (!=)"]
+    NotEqual,
+}
+
+impl Comparison {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::AtLeast => ">=",
+            Comparison::Below => "<",
+            Comparison::AtMost => "<=",
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            ">=" => Comparison::AtLeast,
+            "<" => Comparison::Below,
+            "<=" => Comparison::AtMost,
+            "==" => Comparison::Equal,
+            "!=" => Comparison::NotEqual,
+            _ => Comparison::Above,
+        }
+    }
+
+    pub fn matches(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::AtLeast => value >= threshold,
+            Comparison::Below => value < threshold,
+            Comparison::AtMost => value <= threshold,
+            Comparison::Equal => value == threshold,
+            Comparison::NotEqual => value != threshold,
+        }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "info",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "info" => AlertSeverity::Info,
+            "critical" => AlertSeverity::Critical,
+            _ => AlertSeverity::Warning,
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "ℹ️",
+            AlertSeverity::Warning => "⚠️",
+            AlertSeverity::Critical => "🚨",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            AlertSeverity::Info => Color::from_rgb(66, 133, 244),
+            AlertSeverity::Warning => Color::from_rgb(251, 140, 0),
+            AlertSeverity::Critical => Color::from_rgb(229, 57, 53),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    /// Ignored when saving a new rule.
+    pub id: i64,
+    pub name: String,
+    pub query: String,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    /// How long a series has to match before the alert fires.
+    pub for_secs: u64,
+    pub severity: AlertSeverity,
+    /// How values and the threshold are shown.
+    pub format: StatFormat,
+    /// `None` queries the default datasource.
+    pub datasource: Option<String>,
+}
+
+impl AlertRule {
+    /// The condition as people would write it, e.g. `> 90.0%`.
+    pub fn condition(&self) -> String {
+        format!(
+            "{} {}",
+            self.comparison.as_str(),
+            self.format.format(self.threshold)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertStatus {
+    /// Matching, but not for long enough yet, or the firing post hasn't
+    /// gone out.
+    Pending,
+    /// Posted as firing.
+    Firing,
+}
+
+impl AlertStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AlertStatus::Pending => "pending",
+            AlertStatus::Firing => "firing",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "firing" => AlertStatus::Firing,
+            _ => AlertStatus::Pending,
+        }
+    }
+}
+
+/// A series that currently matches a rule. Series that don't match have no
+/// state.
+#[derive(Debug, Clone)]
+pub struct AlertState {
+    /// The series as `name{label="value", ...}`.
+    pub series: String,
+    pub status: AlertStatus,
+    pub active_since: i64,
+    /// The latest value seen.
+    pub value: f64,
+}
+
+/// Returns whether the name wasn't already taken.
+pub async fn add(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    rule: &AlertRule,
+    created_by: UserId,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let comparison = rule.comparison.as_str();
+    let for_secs = rule.for_secs as i64;
+    let severity = rule.severity.as_str();
+    let format = rule.format.as_str();
+    let created_by = created_by.get() as i64;
+    let now = Utc::now().timestamp();
+
+    let result = sqlx::query!(
+        r#"
+        insert or ignore into alert_rules (
+            guild_id, name, query, comparison, threshold, for_secs, severity, format,
+            datasource, created_by, created_at
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        "#,
+        guild_id,
+        rule.name,
+        rule.query,
+        comparison,
+        rule.threshold,
+        for_secs,
+        severity,
+        format,
+        rule.datasource,
+        created_by,
+        now,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Saves a changed rule. Pending series start over under the new condition.
+/// Firing ones are kept, so the next evaluation posts them as resolved if
+/// they no longer match.
+pub async fn update(pool: &SqlitePool, rule: &AlertRule) -> Result<(), Error> {
+    let comparison = rule.comparison.as_str();
+    let for_secs = rule.for_secs as i64;
+    let severity = rule.severity.as_str();
+    let format = rule.format.as_str();
+
+    sqlx::query!(
+        r#"
+        update alert_rules set
+            query = $1, comparison = $2, threshold = $3, for_secs = $4, severity = $5,
+            format = $6, datasource = $7
+        where id = $8
+        "#,
+        rule.query,
+        comparison,
+        rule.threshold,
+        for_secs,
+        severity,
+        format,
+        rule.datasource,
+        rule.id,
+    )
+    .execute(pool)
+    .await?;
+
+    let pending = AlertStatus::Pending.as_str();
+    sqlx::query!(
+        r#"
+        delete from alert_states where rule_id = $1 and state = $2
+        "#,
+        rule.id,
+        pending,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns whether there was anything to delete.
+pub async fn delete(pool: &SqlitePool, guild_id: GuildId, name: &str) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let result = sqlx::query!(
+        r#"
+        delete from alert_rules where guild_id = $1 and name = $2
+        "#,
+        guild_id,
+        name,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+) -> Result<Option<AlertRule>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let row = sqlx::query!(
+        r#"
+        select id as "id!", name, query, comparison, threshold, for_secs, severity, format, datasource
+        from alert_rules
+        where guild_id = $1 and name = $2
+        "#,
+        guild_id,
+        name,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|r| AlertRule {
+        id: r.id,
+        name: r.name,
+        query: r.query,
+        comparison: Comparison::parse(&r.comparison),
+        threshold: r.threshold,
+        for_secs: r.for_secs as u64,
+        severity: AlertSeverity::parse(&r.severity),
+        format: StatFormat::parse(&r.format),
+        datasource: r.datasource,
+    }))
+}
+
+/// Alphabetical.
+pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<AlertRule>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query!(
+        r#"
+        select id as "id!", name, query, comparison, threshold, for_secs, severity, format, datasource
+        from alert_rules
+        where guild_id = $1
+        order by name
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| AlertRule {
+            id: r.id,
+            name: r.name,
+            query: r.query,
+            comparison: Comparison::parse(&r.comparison),
+            threshold: r.threshold,
+            for_secs: r.for_secs as u64,
+            severity: AlertSeverity::parse(&r.severity),
+            format: StatFormat::parse(&r.format),
+            datasource: r.datasource,
+        })
+        .collect())
+}
+
+/// Every guild's rules, by guild and then alphabetical.
+pub async fn list_all(pool: &SqlitePool) -> Result<Vec<(GuildId, AlertRule)>, Error> {
+    let rows = sqlx::query!(
+        r#"
+        select guild_id, id as "id!", name, query, comparison, threshold, for_secs, severity, format,
+            datasource
+        from alert_rules
+        order by guild_id, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            (
+                GuildId::new(r.guild_id as u64),
+                AlertRule {
+                    id: r.id,
+                    name: r.name,
+                    query: r.query,
+                    comparison: Comparison::parse(&r.comparison),
+                    threshold: r.threshold,
+                    for_secs: r.for_secs as u64,
+                    severity: AlertSeverity::parse(&r.severity),
+                    format: StatFormat::parse(&r.format),
+                    datasource: r.datasource,
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn states(pool: &SqlitePool, rule_id: i64) -> Result<Vec<AlertState>, Error> {
+    let rows = sqlx::query!(
+        r#"
+        select series, state, active_since, value
+        from alert_states
+        where rule_id = $1
+        order by series
+        "#,
+        rule_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| AlertState {
+            series: r.series,
+            status: AlertStatus::parse(&r.state),
+            active_since: r.active_since,
+            value: r.value,
+        })
+        .collect())
+}
+
+pub async fn set_state(pool: &SqlitePool, rule_id: i64, state: &AlertState) -> Result<(), Error> {
+    let status = state.status.as_str();
+    sqlx::query!(
+        r#"
+        insert into alert_states (rule_id, series, state, active_since, value)
+        VALUES ($1, $2, $3, $4, $5)
+        on conflict(rule_id, series) do update set
+            state = excluded.state,
+            active_since = excluded.active_since,
+            value = excluded.value
+        "#,
+        rule_id,
+        state.series,
+        status,
+        state.active_since,
+        state.value,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_state(pool: &SqlitePool, rule_id: i64, series: &str) -> Result<(), Error> {
+    sqlx::query!(
+        r#"
+        delete from alert_states where rule_id = $1 and series = $2
+        "#,
+        rule_id,
+        series,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
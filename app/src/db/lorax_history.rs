@@ -0,0 +1,530 @@
+use crate::settings::{LoraxState, LoraxTimeline, Submission};
+use crate::Error;
+use poise::serenity_prelude::{GuildId, UserId};
+use sqlx::SqlitePool;
+use std::collections::BTreeSet;
+
+/// How a Lorax event ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoraxOutcome {
+    Winner,
+    NoSubmissions,
+    NoWinner,
+    Cancelled,
+}
+
+impl LoraxOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoraxOutcome::Winner => "winner",
+            LoraxOutcome::NoSubmissions => "no_submissions",
+            LoraxOutcome::NoWinner => "no_winner",
+            LoraxOutcome::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "winner" => LoraxOutcome::Winner,
+            "no_submissions" => LoraxOutcome::NoSubmissions,
+            "cancelled" => LoraxOutcome::Cancelled,
+            _ => LoraxOutcome::NoWinner,
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            LoraxOutcome::Winner => "🏆 Winner chosen",
+            LoraxOutcome::NoSubmissions => "🍂 No submissions",
+            LoraxOutcome::NoWinner => "🚫 Ended without a winner",
+            LoraxOutcome::Cancelled => "🛑 Cancelled",
+        }
+    }
+}
+
+/// One line in the `/lorax history` listing.
+#[derive(Debug, Clone)]
+pub struct EventSummary {
+    pub id: i64,
+    pub location: String,
+    pub outcome: LoraxOutcome,
+    pub winner: Option<String>,
+    pub ended_at: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchivedSubmission {
+    pub submitter: UserId,
+    pub name: String,
+    pub meaning: Option<String>,
+    /// Where the name finished, 1 is the winner. Names knocked out before the
+    /// final round don't have one.
+    pub placement: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchivedTally {
+    pub round: u32,
+    pub ended_at: i64,
+    pub name: String,
+    pub votes: usize,
+}
+
+/// An anonymous ballot, listing names in order of preference for ranked
+/// votes.
+#[derive(Debug, Clone)]
+pub struct ArchivedBallot {
+    pub round: u32,
+    pub choices: Vec<String>,
+}
+
+/// A fully archived event, including every submission, round and ballot.
+#[derive(Debug, Clone)]
+pub struct ArchivedEvent {
+    pub id: i64,
+    pub location: String,
+    pub outcome: LoraxOutcome,
+    pub winner: Option<String>,
+    pub winner_submitter: Option<UserId>,
+    pub started_at: i64,
+    pub voting_started_at: Option<i64>,
+    pub ended_at: i64,
+    pub submissions: Vec<ArchivedSubmission>,
+    pub tallies: Vec<ArchivedTally>,
+    pub ballots: Vec<ArchivedBallot>,
+}
+
+/// Outcome of events that haven't ended yet. They're left out of the history
+/// and stats until they're archived.
+const RUNNING: &str = "running";
+
+/// Adds the row for an event as its submissions open and returns the event ID
+/// it's known by from then on.
+pub async fn open_event(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    location: &str,
+    started_at: i64,
+) -> Result<i64, Error> {
+    let guild_id = guild_id.get() as i64;
+
+    let event_id = sqlx::query!(
+        r#"
+        insert into lorax_events (guild_id, location, outcome, started_at, ended_at)
+        VALUES ($1, $2, $3, $4, $4)
+        "#,
+        guild_id,
+        location,
+        RUNNING,
+        started_at,
+    )
+    .execute(pool)
+    .await?
+    .last_insert_rowid();
+
+    Ok(event_id)
+}
+
+/// Removes the row for an event that never got announced.
+pub async fn discard_event(pool: &SqlitePool, event_id: i64) -> Result<(), Error> {
+    sqlx::query!(
+        "delete from lorax_events where id = $1 and outcome = $2",
+        event_id,
+        RUNNING,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Archives whatever is in `state` under the event's ID. Idle states have
+/// nothing to archive.
+pub async fn archive_state(
+    pool: &SqlitePool,
+    event_id: i64,
+    state: &LoraxState,
+    outcome: LoraxOutcome,
+    winner: Option<(&str, UserId)>,
+) -> Result<(), Error> {
+    let (location, submissions, timeline) = match state {
+        LoraxState::Idle => return Ok(()),
+        LoraxState::Submissions {
+            location,
+            submissions,
+            timeline,
+            ..
+        }
+        | LoraxState::Voting {
+            location,
+            submissions,
+            timeline,
+            ..
+        }
+        | LoraxState::TieBreaker {
+            location,
+            submissions,
+            timeline,
+            ..
+        } => (location, submissions, timeline),
+    };
+
+    archive_event(
+        pool,
+        event_id,
+        location,
+        submissions,
+        timeline,
+        outcome,
+        winner,
+    )
+    .await
+}
+
+pub async fn archive_event(
+    pool: &SqlitePool,
+    event_id: i64,
+    location: &str,
+    submissions: &[Submission],
+    timeline: &LoraxTimeline,
+    outcome: LoraxOutcome,
+    winner: Option<(&str, UserId)>,
+) -> Result<(), Error> {
+    let outcome = outcome.as_str();
+    let winner_name = winner.map(|(name, _)| name);
+    let winner_submitter = winner.map(|(_, user_id)| user_id.get() as i64);
+    let ended_at = chrono::Utc::now().timestamp();
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        update lorax_events
+        set location = $1, outcome = $2, winner = $3, winner_submitter = $4,
+            started_at = $5, voting_started_at = $6, ended_at = $7
+        where id = $8
+        "#,
+        location,
+        outcome,
+        winner_name,
+        winner_submitter,
+        timeline.started_at,
+        timeline.voting_started_at,
+        ended_at,
+        event_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let standings = standings(timeline, winner_name);
+    for submission in submissions {
+        let user_id = submission.submitter.get() as i64;
+        let name = &submission.name;
+        let meaning = &submission.meaning;
+        let placement = standings
+            .iter()
+            .position(|standing| *standing == name)
+            .map(|i| i as i64 + 1);
+        sqlx::query!(
+            r#"
+            insert into lorax_event_submissions (event_id, user_id, name, meaning, placement)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            event_id,
+            user_id,
+            name,
+            meaning,
+            placement,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let voters: BTreeSet<UserId> = timeline
+        .rounds
+        .iter()
+        .flat_map(|round| round.voters.iter().copied())
+        .collect();
+    for voter in voters {
+        let user_id = voter.get() as i64;
+        sqlx::query!(
+            r#"
+            insert into lorax_event_voters (event_id, user_id)
+            VALUES ($1, $2)
+            "#,
+            event_id,
+            user_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for round in &timeline.rounds {
+        for (name, votes) in &round.tallies {
+            let votes = *votes as i64;
+            sqlx::query!(
+                r#"
+                insert into lorax_event_tallies (event_id, round, ended_at, name, votes)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+                event_id,
+                round.round,
+                round.ended_at,
+                name,
+                votes,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for ballot in &round.ballots {
+            let choices = serde_json::to_string(ballot)?;
+            sqlx::query!(
+                r#"
+                insert into lorax_event_ballots (event_id, round, choices)
+                VALUES ($1, $2, $3)
+                "#,
+                event_id,
+                round.round,
+                choices,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Where the names in the final round finished, winner first. Names knocked
+/// out in earlier rounds don't get a place, and nobody does without a winner.
+fn standings<'a>(timeline: &'a LoraxTimeline, winner: Option<&'a str>) -> Vec<&'a str> {
+    let Some(winner) = winner else {
+        return Vec::new();
+    };
+    let Some(last_round) = timeline.rounds.last() else {
+        return vec![winner];
+    };
+
+    let mut standings: Vec<(&str, usize)> = last_round
+        .tallies
+        .iter()
+        .map(|(name, votes)| (name.as_str(), *votes))
+        .collect();
+    // A drawn or IRV winner can be level with others on votes
+    standings.sort_by(|a, b| {
+        (b.0 == winner)
+            .cmp(&(a.0 == winner))
+            .then(b.1.cmp(&a.1))
+            .then(a.0.cmp(b.0))
+    });
+    standings.into_iter().map(|(name, _)| name).collect()
+}
+
+pub async fn count_events(pool: &SqlitePool, guild_id: GuildId) -> Result<i64, Error> {
+    let guild_id = guild_id.get() as i64;
+    let row = sqlx::query!(
+        r#"
+        select count(*) as "count: i64" from lorax_events
+        where guild_id = $1 and outcome != $2
+        "#,
+        guild_id,
+        RUNNING,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.count)
+}
+
+/// Most recent events first.
+pub async fn list_events(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<EventSummary>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query!(
+        r#"
+        select id as "id!", location, outcome, winner, ended_at
+        from lorax_events
+        where guild_id = $1 and outcome != $2
+        order by ended_at desc, id desc
+        limit $3 offset $4
+        "#,
+        guild_id,
+        RUNNING,
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| EventSummary {
+            id: r.id,
+            location: r.location,
+            outcome: LoraxOutcome::parse(&r.outcome),
+            winner: r.winner,
+            ended_at: r.ended_at,
+        })
+        .collect())
+}
+
+pub async fn get_event(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    event_id: i64,
+) -> Result<Option<ArchivedEvent>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let Some(event) = sqlx::query!(
+        r#"
+        select id, location, outcome, winner, winner_submitter,
+                started_at, voting_started_at, ended_at
+        from lorax_events
+        where guild_id = $1 and id = $2 and outcome != $3
+        "#,
+        guild_id,
+        event_id,
+        RUNNING,
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let submissions = sqlx::query!(
+        r#"
+        select user_id, name, meaning, placement from lorax_event_submissions
+        where event_id = $1
+        order by name
+        "#,
+        event_id,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| ArchivedSubmission {
+        submitter: UserId::new(r.user_id as u64),
+        name: r.name,
+        meaning: r.meaning,
+        placement: r.placement.map(|p| p as u32),
+    })
+    .collect();
+
+    let tallies = sqlx::query!(
+        r#"
+        select round, ended_at, name, votes from lorax_event_tallies
+        where event_id = $1
+        order by round, votes desc, name
+        "#,
+        event_id,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| ArchivedTally {
+        round: r.round as u32,
+        ended_at: r.ended_at,
+        name: r.name,
+        votes: r.votes as usize,
+    })
+    .collect();
+
+    let ballots = sqlx::query!(
+        r#"
+        select round, choices from lorax_event_ballots
+        where event_id = $1
+        order by round, rowid
+        "#,
+        event_id,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| ArchivedBallot {
+        round: r.round as u32,
+        choices: serde_json::from_str(&r.choices).unwrap_or_default(),
+    })
+    .collect();
+
+    Ok(Some(ArchivedEvent {
+        id: event.id,
+        location: event.location,
+        outcome: LoraxOutcome::parse(&event.outcome),
+        winner: event.winner,
+        winner_submitter: event.winner_submitter.map(|id| UserId::new(id as u64)),
+        started_at: event.started_at,
+        voting_started_at: event.voting_started_at,
+        ended_at: event.ended_at,
+        submissions,
+        tallies,
+        ballots,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn running_events_keep_their_id_once_archived() {
+        let path = std::env::temp_dir().join(format!("history-test-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite://{}?mode=rwc", path.display()))
+            .await
+            .unwrap();
+        sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+        let guild_id = GuildId::new(1);
+
+        let event_id = open_event(&pool, guild_id, "eu-west", 100).await.unwrap();
+        // Running events aren't history yet
+        assert_eq!(count_events(&pool, guild_id).await.unwrap(), 0);
+        assert!(get_event(&pool, guild_id, event_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        let timeline = LoraxTimeline {
+            started_at: 100,
+            voting_started_at: Some(200),
+            rounds: Vec::new(),
+        };
+        let submitter = UserId::new(2);
+        let submissions = vec![Submission {
+            submitter,
+            name: "oak".to_string(),
+            meaning: None,
+            statement: None,
+        }];
+        archive_event(
+            &pool,
+            event_id,
+            "eu-west",
+            &submissions,
+            &timeline,
+            LoraxOutcome::Winner,
+            Some(("oak", submitter)),
+        )
+        .await
+        .unwrap();
+
+        let listed = list_events(&pool, guild_id, 10, 0).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, event_id);
+        let event = get_event(&pool, guild_id, event_id).await.unwrap().unwrap();
+        assert_eq!(event.winner.as_deref(), Some("oak"));
+        assert_eq!(event.voting_started_at, Some(200));
+        assert_eq!(event.submissions[0].placement, Some(1));
+
+        pool.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+}
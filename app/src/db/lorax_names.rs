@@ -0,0 +1,217 @@
+use crate::Error;
+use chrono::Utc;
+use poise::serenity_prelude::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameRule {
+    /// Nobody can submit exactly this name.
+    Reserved,
+    /// Nobody can submit a name containing this word.
+    Blocked,
+}
+
+impl NameRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameRule::Reserved => "reserved",
+            NameRule::Blocked => "blocked",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "blocked" => NameRule::Blocked,
+            _ => NameRule::Reserved,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListedName {
+    pub name: String,
+    pub rule: NameRule,
+}
+
+/// Why a name can't be submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameConflict {
+    Reserved,
+    Blocked,
+    /// Won an earlier event, these are reserved automatically.
+    PastWinner,
+    /// Already the name of a node in the inventory, planned, live or retired.
+    ExistingNode,
+}
+
+/// Returns whether the name wasn't already on the list.
+pub async fn add(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+    rule: NameRule,
+    added_by: UserId,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let kind = rule.as_str();
+    let added_by = added_by.get() as i64;
+    let added_at = Utc::now().timestamp();
+
+    let result = sqlx::query!(
+        r#"
+        insert or ignore into lorax_names (guild_id, name, kind, added_by, added_at)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        guild_id,
+        name,
+        kind,
+        added_by,
+        added_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns whether there was anything to remove.
+pub async fn remove(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+    rule: NameRule,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let kind = rule.as_str();
+
+    let result = sqlx::query!(
+        r#"
+        delete from lorax_names where guild_id = $1 and name = $2 and kind = $3
+        "#,
+        guild_id,
+        name,
+        kind,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Reserved names first, then blocked words, alphabetically.
+pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<ListedName>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query!(
+        r#"
+        select name, kind
+        from lorax_names
+        where guild_id = $1
+        order by kind desc, name
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| ListedName {
+            name: r.name,
+            rule: NameRule::parse(&r.kind),
+        })
+        .collect())
+}
+
+/// Names that won an earlier event in the guild.
+pub async fn past_winners(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<String>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query!(
+        r#"
+        select distinct winner as "winner!"
+        from lorax_events
+        where guild_id = $1 and outcome = 'winner' and winner is not null
+        order by winner
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|r| r.winner).collect())
+}
+
+/// Checks a name against the guild's reserved and blocked lists, its past
+/// winners and its node inventory.
+pub async fn conflict(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+) -> Result<Option<NameConflict>, Error> {
+    let guild_id = guild_id.get() as i64;
+
+    let blocked = sqlx::query!(
+        r#"
+        select count(*) as "count!: i64"
+        from lorax_names
+        where guild_id = $1 and kind = 'blocked' and instr($2, name) > 0
+        "#,
+        guild_id,
+        name,
+    )
+    .fetch_one(pool)
+    .await?
+    .count;
+    if blocked > 0 {
+        return Ok(Some(NameConflict::Blocked));
+    }
+
+    let reserved = sqlx::query!(
+        r#"
+        select count(*) as "count!: i64"
+        from lorax_names
+        where guild_id = $1 and kind = 'reserved' and name = $2
+        "#,
+        guild_id,
+        name,
+    )
+    .fetch_one(pool)
+    .await?
+    .count;
+    if reserved > 0 {
+        return Ok(Some(NameConflict::Reserved));
+    }
+
+    let won = sqlx::query!(
+        r#"
+        select count(*) as "count!: i64"
+        from lorax_events
+        where guild_id = $1 and outcome = 'winner' and winner = $2
+        "#,
+        guild_id,
+        name,
+    )
+    .fetch_one(pool)
+    .await?
+    .count;
+    if won > 0 {
+        return Ok(Some(NameConflict::PastWinner));
+    }
+
+    let nodes = sqlx::query!(
+        r#"
+        select count(*) as "count!: i64"
+        from nodes
+        where guild_id = $1 and name = $2
+        "#,
+        guild_id,
+        name,
+    )
+    .fetch_one(pool)
+    .await?
+    .count;
+    if nodes > 0 {
+        return Ok(Some(NameConflict::ExistingNode));
+    }
+
+    Ok(None)
+}
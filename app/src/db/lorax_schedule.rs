@@ -0,0 +1,205 @@
+use crate::settings::LoraxEventConfig;
+use crate::Error;
+use poise::serenity_prelude::GuildId;
+use sqlx::SqlitePool;
+
+/// Teasers go out this long before a scheduled event opens.
+pub const TEASER_LEAD_TIME: i64 = 24 * 60 * 60;
+
+/// A Lorax event queued to open at `start_time`.
+#[derive(Debug, Clone)]
+pub struct ScheduledEvent {
+    pub id: i64,
+    pub guild_id: GuildId,
+    pub location: String,
+    pub start_time: i64,
+    pub teaser_sent: bool,
+    pub config: LoraxEventConfig,
+    /// Failed attempts to open it since it was last changed.
+    pub failures: u32,
+    /// Not retried before this after a failure.
+    pub retry_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+struct ScheduleRow {
+    id: i64,
+    guild_id: i64,
+    location: String,
+    start_time: i64,
+    teaser_sent: bool,
+    config: String,
+    failures: i64,
+    retry_at: Option<i64>,
+    last_error: Option<String>,
+}
+
+impl From<ScheduleRow> for ScheduledEvent {
+    fn from(row: ScheduleRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: GuildId::new(row.guild_id as u64),
+            location: row.location,
+            start_time: row.start_time,
+            teaser_sent: row.teaser_sent,
+            config: serde_json::from_str(&row.config).unwrap_or_default(),
+            failures: row.failures as u32,
+            retry_at: row.retry_at,
+            last_error: row.last_error,
+        }
+    }
+}
+
+pub async fn insert(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    location: &str,
+    start_time: i64,
+    config: &LoraxEventConfig,
+) -> Result<i64, Error> {
+    let guild_id = guild_id.get() as i64;
+    let config = serde_json::to_string(config)?;
+
+    let id = sqlx::query!(
+        r#"
+        insert into lorax_schedule (guild_id, location, start_time, config)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        guild_id,
+        location,
+        start_time,
+        config,
+    )
+    .execute(pool)
+    .await?
+    .last_insert_rowid();
+
+    Ok(id)
+}
+
+/// Saves changes to an already queued event.
+pub async fn update(pool: &SqlitePool, event: &ScheduledEvent) -> Result<(), Error> {
+    let config = serde_json::to_string(&event.config)?;
+    let failures = event.failures as i64;
+
+    sqlx::query!(
+        r#"
+        update lorax_schedule
+        set location = $1, start_time = $2, teaser_sent = $3, config = $4, failures = $5,
+            retry_at = $6, last_error = $7
+        where id = $8
+        "#,
+        event.location,
+        event.start_time,
+        event.teaser_sent,
+        config,
+        failures,
+        event.retry_at,
+        event.last_error,
+        event.id,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns whether there was anything to delete.
+pub async fn delete(pool: &SqlitePool, guild_id: GuildId, id: i64) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let result = sqlx::query!(
+        r#"
+        delete from lorax_schedule where guild_id = $1 and id = $2
+        "#,
+        guild_id,
+        id,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    id: i64,
+) -> Result<Option<ScheduledEvent>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let row = sqlx::query_as!(
+        ScheduleRow,
+        r#"
+        select id, guild_id, location, start_time, teaser_sent, config, failures, retry_at,
+            last_error
+        from lorax_schedule
+        where guild_id = $1 and id = $2
+        "#,
+        guild_id,
+        id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(ScheduledEvent::from))
+}
+
+/// Soonest first.
+pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<ScheduledEvent>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query_as!(
+        ScheduleRow,
+        r#"
+        select id as "id!", guild_id, location, start_time, teaser_sent, config, failures,
+            retry_at, last_error
+        from lorax_schedule
+        where guild_id = $1
+        order by start_time
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(ScheduledEvent::from).collect())
+}
+
+/// Events due to open at `now`, across every guild. Ones that failed to open
+/// are left out until they're due to be retried.
+pub async fn due(pool: &SqlitePool, now: i64) -> Result<Vec<ScheduledEvent>, Error> {
+    let rows = sqlx::query_as!(
+        ScheduleRow,
+        r#"
+        select id as "id!", guild_id, location, start_time, teaser_sent, config, failures,
+            retry_at, last_error
+        from lorax_schedule
+        where start_time <= $1 and (retry_at is null or retry_at <= $1)
+        order by start_time
+        "#,
+        now,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(ScheduledEvent::from).collect())
+}
+
+/// Events close enough to opening that their teaser should go out.
+pub async fn due_teasers(pool: &SqlitePool, now: i64) -> Result<Vec<ScheduledEvent>, Error> {
+    let teaser_until = now + TEASER_LEAD_TIME;
+    let rows = sqlx::query_as!(
+        ScheduleRow,
+        r#"
+        select id as "id!", guild_id, location, start_time, teaser_sent, config, failures,
+            retry_at, last_error
+        from lorax_schedule
+        where not teaser_sent and start_time > $1 and start_time <= $2
+        order by start_time
+        "#,
+        now,
+        teaser_until,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(ScheduledEvent::from).collect())
+}
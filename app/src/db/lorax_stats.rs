@@ -0,0 +1,199 @@
+use crate::Error;
+use poise::serenity_prelude::{GuildId, UserId};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+
+/// How a member has taken part in a guild's Lorax events.
+#[derive(Debug, Clone, Default)]
+pub struct ParticipantStats {
+    pub user_id: UserId,
+    /// Events they submitted at least one name to.
+    pub submitted: usize,
+    pub voted: usize,
+    /// Events they submitted to or voted in.
+    pub participated: usize,
+    pub wins: usize,
+    pub runner_ups: usize,
+    /// Events in a row they've taken part in, up to the latest one.
+    pub current_streak: usize,
+    pub best_streak: usize,
+}
+
+/// Stats for everyone who has taken part in a finished event. Cancelled events
+/// don't count, and don't break anyone's streak either.
+pub async fn participants(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+) -> Result<Vec<ParticipantStats>, Error> {
+    let guild_id = guild_id.get() as i64;
+
+    let events: Vec<i64> = sqlx::query!(
+        r#"
+        select id as "id!"
+        from lorax_events
+        where guild_id = $1 and outcome not in ('cancelled', 'running')
+        order by ended_at, id
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| r.id)
+    .collect();
+
+    let submissions = sqlx::query!(
+        r#"
+        select s.event_id, s.user_id, s.placement
+        from lorax_event_submissions s
+        join lorax_events e on e.id = s.event_id
+        where e.guild_id = $1 and e.outcome not in ('cancelled', 'running')
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let voters = sqlx::query!(
+        r#"
+        select v.event_id, v.user_id
+        from lorax_event_voters v
+        join lorax_events e on e.id = v.event_id
+        where e.guild_id = $1 and e.outcome not in ('cancelled', 'running')
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut stats: HashMap<UserId, ParticipantStats> = HashMap::new();
+    let mut submitted: HashSet<(i64, UserId)> = HashSet::new();
+    let mut voted: HashSet<(i64, UserId)> = HashSet::new();
+    fn entry(
+        stats: &mut HashMap<UserId, ParticipantStats>,
+        user_id: UserId,
+    ) -> &mut ParticipantStats {
+        stats.entry(user_id).or_insert_with(|| ParticipantStats {
+            user_id,
+            ..Default::default()
+        })
+    }
+
+    for row in submissions {
+        let user_id = UserId::new(row.user_id as u64);
+        let user = entry(&mut stats, user_id);
+        match row.placement {
+            Some(1) => user.wins += 1,
+            Some(2) => user.runner_ups += 1,
+            _ => {}
+        }
+        submitted.insert((row.event_id, user_id));
+    }
+    for row in voters {
+        let user_id = UserId::new(row.user_id as u64);
+        entry(&mut stats, user_id);
+        voted.insert((row.event_id, user_id));
+    }
+
+    for user in stats.values_mut() {
+        let mut streak = 0;
+        for event_id in &events {
+            let did_submit = submitted.contains(&(*event_id, user.user_id));
+            let did_vote = voted.contains(&(*event_id, user.user_id));
+            user.submitted += did_submit as usize;
+            user.voted += did_vote as usize;
+            if did_submit || did_vote {
+                user.participated += 1;
+                streak += 1;
+                user.best_streak = user.best_streak.max(streak);
+            } else {
+                streak = 0;
+            }
+        }
+        user.current_streak = streak;
+    }
+
+    Ok(stats.into_values().collect())
+}
+
+/// Sorts participants into leaderboard order: wins, then runner-up finishes,
+/// then how often and how recently they've taken part.
+pub fn rank(participants: &mut [ParticipantStats]) {
+    participants.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.runner_ups.cmp(&a.runner_ups))
+            .then(b.participated.cmp(&a.participated))
+            .then(b.current_streak.cmp(&a.current_streak))
+    });
+}
+
+/// The `count` highest placed members on the leaderboard who have won at
+/// least once.
+pub async fn top_namers(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    count: usize,
+) -> Result<Vec<UserId>, Error> {
+    let mut participants = participants(pool, guild_id).await?;
+    rank(&mut participants);
+    Ok(participants
+        .into_iter()
+        .filter(|user| user.wins > 0)
+        .take(count)
+        .map(|user| user.user_id)
+        .collect())
+}
+
+/// Members the winner role was last given to.
+pub async fn role_holders(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<UserId>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query!(
+        r#"
+        select user_id from lorax_top_namers where guild_id = $1
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| UserId::new(r.user_id as u64))
+        .collect())
+}
+
+pub async fn set_role_holders(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    holders: &[UserId],
+) -> Result<(), Error> {
+    let guild_id = guild_id.get() as i64;
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        delete from lorax_top_namers where guild_id = $1
+        "#,
+        guild_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    for user_id in holders {
+        let user_id = user_id.get() as i64;
+        sqlx::query!(
+            r#"
+            insert into lorax_top_namers (guild_id, user_id)
+            VALUES ($1, $2)
+            "#,
+            guild_id,
+            user_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
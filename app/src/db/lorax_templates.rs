@@ -0,0 +1,116 @@
+use crate::settings::LoraxEventConfig;
+use crate::Error;
+use chrono::Utc;
+use poise::serenity_prelude::{GuildId, UserId};
+use sqlx::SqlitePool;
+
+/// Used by `/lorax start` when no other template is picked.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+#[derive(Debug, Clone)]
+pub struct LoraxTemplate {
+    pub name: String,
+    pub config: LoraxEventConfig,
+}
+
+/// Returns whether the template is new, otherwise it was replaced.
+pub async fn save(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+    config: &LoraxEventConfig,
+    updated_by: UserId,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let config = serde_json::to_string(config)?;
+    let updated_by = updated_by.get() as i64;
+    let updated_at = Utc::now().timestamp();
+
+    let existed = get_row(pool, guild_id, name).await?.is_some();
+    sqlx::query!(
+        r#"
+        insert into lorax_templates (guild_id, name, config, updated_by, updated_at)
+        VALUES ($1, $2, $3, $4, $5)
+        on conflict(guild_id, name) do update set
+            config = excluded.config,
+            updated_by = excluded.updated_by,
+            updated_at = excluded.updated_at
+        "#,
+        guild_id,
+        name,
+        config,
+        updated_by,
+        updated_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(!existed)
+}
+
+/// Returns whether there was anything to delete.
+pub async fn delete(pool: &SqlitePool, guild_id: GuildId, name: &str) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let result = sqlx::query!(
+        r#"
+        delete from lorax_templates where guild_id = $1 and name = $2
+        "#,
+        guild_id,
+        name,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+) -> Result<Option<LoraxTemplate>, Error> {
+    let guild_id = guild_id.get() as i64;
+    Ok(get_row(pool, guild_id, name)
+        .await?
+        .map(|config| LoraxTemplate {
+            name: name.to_string(),
+            config: serde_json::from_str(&config).unwrap_or_default(),
+        }))
+}
+
+async fn get_row(pool: &SqlitePool, guild_id: i64, name: &str) -> Result<Option<String>, Error> {
+    let row = sqlx::query!(
+        r#"
+        select config from lorax_templates where guild_id = $1 and name = $2
+        "#,
+        guild_id,
+        name,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|r| r.config))
+}
+
+/// Alphabetical.
+pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<LoraxTemplate>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query!(
+        r#"
+        select name, config from lorax_templates
+        where guild_id = $1
+        order by name
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| LoraxTemplate {
+            name: r.name,
+            config: serde_json::from_str(&r.config).unwrap_or_default(),
+        })
+        .collect())
+}
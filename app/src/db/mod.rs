@@ -0,0 +1,8 @@
+pub mod alerts;
+pub mod lorax_history;
+pub mod lorax_names;
+pub mod lorax_schedule;
+pub mod lorax_stats;
+pub mod lorax_templates;
+pub mod nodes;
+pub mod stat_channels;
@@ -0,0 +1,134 @@
+use crate::Error;
+use chrono::Utc;
+use poise::serenity_prelude::GuildId;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum NodeStatus {
+    /// Named, but not up yet.
+    Planned,
+    Live,
+    Retired,
+}
+
+impl NodeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeStatus::Planned => "planned",
+            NodeStatus::Live => "live",
+            NodeStatus::Retired => "retired",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "live" => NodeStatus::Live,
+            "retired" => NodeStatus::Retired,
+            _ => NodeStatus::Planned,
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            NodeStatus::Planned => "🌱",
+            NodeStatus::Live => "🌳",
+            NodeStatus::Retired => "🍂",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub name: String,
+    pub location: String,
+    /// The archived Lorax event that named it.
+    pub event_id: Option<i64>,
+    pub status: NodeStatus,
+}
+
+/// Adds a planned node. Returns whether the name wasn't already taken.
+pub async fn add(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+    location: &str,
+    event_id: Option<i64>,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let now = Utc::now().timestamp();
+
+    let result = sqlx::query!(
+        r#"
+        insert or ignore into nodes (guild_id, name, location, event_id, status, added_at, updated_at)
+        VALUES ($1, $2, $3, $4, 'planned', $5, $5)
+        "#,
+        guild_id,
+        name,
+        location,
+        event_id,
+        now,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns whether there was a node with that name.
+pub async fn set_status(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    name: &str,
+    status: NodeStatus,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let status = status.as_str();
+    let now = Utc::now().timestamp();
+
+    let result = sqlx::query!(
+        r#"
+        update nodes set status = $1, updated_at = $2
+        where guild_id = $3 and name = $4
+        "#,
+        status,
+        now,
+        guild_id,
+        name,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Alphabetical, optionally only nodes with the given status.
+pub async fn list(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    status: Option<NodeStatus>,
+) -> Result<Vec<Node>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let status = status.map(|s| s.as_str());
+    let rows = sqlx::query!(
+        r#"
+        select name, location, event_id, status
+        from nodes
+        where guild_id = $1 and ($2 is null or status = $2)
+        order by name
+        "#,
+        guild_id,
+        status,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| Node {
+            name: r.name,
+            location: r.location,
+            event_id: r.event_id,
+            status: NodeStatus::parse(&r.status),
+        })
+        .collect())
+}
@@ -0,0 +1,275 @@
+use crate::metrics::format_bytes;
+use crate::Error;
+use chrono::Utc;
+use poise::serenity_prelude::{ChannelId, GuildId};
+use sqlx::SqlitePool;
+
+/// Where the value goes in a stat channel's label.
+pub const VALUE_PLACEHOLDER: &str = "{value}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum StatFormat {
+    /// A size, e.g. `1.50GB`.
+    Bytes,
+    /// Bytes per second, e.g. `12.0MB/s`.
+    Rate,
+    /// A whole number, e.g. `42`.
+    Count,
+    /// A query that returns 0 to 100, e.g. `37.5%`.
+    Percent,
+}
+
+impl StatFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatFormat::Bytes => "bytes",
+            StatFormat::Rate => "rate",
+            StatFormat::Count => "count",
+            StatFormat::Percent => "percent",
+        }
+    }
+
+    pub(crate) fn parse(s: &str) -> Self {
+        match s {
+            "bytes" => StatFormat::Bytes,
+            "rate" => StatFormat::Rate,
+            "percent" => StatFormat::Percent,
+            _ => StatFormat::Count,
+        }
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            StatFormat::Bytes => format_bytes(value),
+            StatFormat::Rate => format!("{}/s", format_bytes(value)),
+            StatFormat::Count => format!("{:.0}", value),
+            StatFormat::Percent => format!("{:.1}%", value),
+        }
+    }
+}
+
+/// A channel whose name shows the latest value of a PromQL query.
+#[derive(Debug, Clone)]
+pub struct StatChannel {
+    pub channel_id: ChannelId,
+    pub query: String,
+    /// The channel name, with `{value}` where the formatted value goes.
+    pub label: String,
+    pub format: StatFormat,
+    /// `None` queries the default datasource.
+    pub datasource: Option<String>,
+}
+
+impl StatChannel {
+    pub fn channel_name(&self, value: f64) -> String {
+        self.label
+            .replace(VALUE_PLACEHOLDER, &self.format.format(value))
+    }
+}
+
+/// Returns whether the channel didn't already show a stat.
+pub async fn add(pool: &SqlitePool, guild_id: GuildId, stat: &StatChannel) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let channel_id = stat.channel_id.get() as i64;
+    let format = stat.format.as_str();
+    let now = Utc::now().timestamp();
+
+    let result = sqlx::query!(
+        r#"
+        insert or ignore into stat_channels (guild_id, channel_id, query, label, format, datasource, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        guild_id,
+        channel_id,
+        stat.query,
+        stat.label,
+        format,
+        stat.datasource,
+        now,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns whether the channel showed a stat.
+pub async fn update(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    stat: &StatChannel,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let channel_id = stat.channel_id.get() as i64;
+    let format = stat.format.as_str();
+
+    let result = sqlx::query!(
+        r#"
+        update stat_channels set query = $1, label = $2, format = $3, datasource = $4
+        where guild_id = $5 and channel_id = $6
+        "#,
+        stat.query,
+        stat.label,
+        format,
+        stat.datasource,
+        guild_id,
+        channel_id,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns whether there was anything to remove.
+pub async fn remove(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<bool, Error> {
+    let guild_id = guild_id.get() as i64;
+    let channel_id = channel_id.get() as i64;
+
+    let result = sqlx::query!(
+        r#"
+        delete from stat_channels where guild_id = $1 and channel_id = $2
+        "#,
+        guild_id,
+        channel_id,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get(
+    pool: &SqlitePool,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<Option<StatChannel>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let channel_id = channel_id.get() as i64;
+
+    let row = sqlx::query!(
+        r#"
+        select channel_id, query, label, format, datasource from stat_channels
+        where guild_id = $1 and channel_id = $2
+        "#,
+        guild_id,
+        channel_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|r| StatChannel {
+        channel_id: ChannelId::new(r.channel_id as u64),
+        query: r.query,
+        label: r.label,
+        format: StatFormat::parse(&r.format),
+        datasource: r.datasource,
+    }))
+}
+
+/// In the order they were added.
+pub async fn list(pool: &SqlitePool, guild_id: GuildId) -> Result<Vec<StatChannel>, Error> {
+    let guild_id = guild_id.get() as i64;
+    let rows = sqlx::query!(
+        r#"
+        select channel_id, query, label, format, datasource from stat_channels
+        where guild_id = $1
+        order by id
+        "#,
+        guild_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| StatChannel {
+            channel_id: ChannelId::new(r.channel_id as u64),
+            query: r.query,
+            label: r.label,
+            format: StatFormat::parse(&r.format),
+            datasource: r.datasource,
+        })
+        .collect())
+}
+
+/// Every guild's stat channels, by guild and then in the order they were added.
+pub async fn list_all(pool: &SqlitePool) -> Result<Vec<(GuildId, StatChannel)>, Error> {
+    let rows = sqlx::query!(
+        r#"
+        select guild_id, channel_id, query, label, format, datasource from stat_channels
+        order by guild_id, id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            (
+                GuildId::new(r.guild_id as u64),
+                StatChannel {
+                    channel_id: ChannelId::new(r.channel_id as u64),
+                    query: r.query,
+                    label: r.label,
+                    format: StatFormat::parse(&r.format),
+                    datasource: r.datasource,
+                },
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(label: &str, format: StatFormat) -> StatChannel {
+        StatChannel {
+            channel_id: ChannelId::new(1),
+            query: "up".to_string(),
+            label: label.to_string(),
+            format,
+            datasource: None,
+        }
+    }
+
+    #[test]
+    fn formats_values() {
+        assert_eq!(StatFormat::Bytes.format(1536.0 * 1024.0 * 1024.0), "1.50GB");
+        assert_eq!(StatFormat::Rate.format(12.0 * 1024.0 * 1024.0), "12.00MB/s");
+        assert_eq!(StatFormat::Count.format(41.6), "42");
+        assert_eq!(StatFormat::Percent.format(37.54), "37.5%");
+    }
+
+    #[test]
+    fn formats_round_trip_through_the_database() {
+        for format in [
+            StatFormat::Bytes,
+            StatFormat::Rate,
+            StatFormat::Count,
+            StatFormat::Percent,
+        ] {
+            assert_eq!(StatFormat::parse(format.as_str()), format);
+        }
+        assert_eq!(StatFormat::parse("unknown"), StatFormat::Count);
+    }
+
+    #[test]
+    fn channel_name_fills_in_every_value() {
+        assert_eq!(
+            stat("👥 Players: {value}", StatFormat::Count).channel_name(128.0),
+            "👥 Players: 128"
+        );
+        assert_eq!(
+            stat("{value} / {value}", StatFormat::Percent).channel_name(50.0),
+            "50.0% / 50.0%"
+        );
+        assert_eq!(stat("No value", StatFormat::Count).channel_name(1.0), "No value");
+    }
+}
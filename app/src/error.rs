@@ -11,6 +11,9 @@ pub enum BotError {
 
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
+
+    #[error("Datasource config error: {0}")]
+    Config(String),
 }
 
 impl From<&str> for BotError {
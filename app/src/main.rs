@@ -1,11 +1,15 @@
 mod commands;
+mod db;
 mod error;
 mod events;
 mod metrics;
 mod settings;
 mod tasks;
+mod util;
+mod voting;
 
 use events::event_handler;
+use metrics::Datasources;
 use poise::serenity_prelude as serenity;
 use settings::Settings;
 use sqlx::SqlitePool;
@@ -14,11 +18,13 @@ use tokio::sync::RwLock;
 use tasks::{server_deletion, TaskManager};
 use tasks::stats_updater::StatsUpdaterTask;
 use tasks::lorax_scheduler::LoraxSchedulerTask;
+use tasks::alerts::AlertsTask;
 
 #[derive(Clone)]
 pub struct Data {
     pub settings: Arc<RwLock<Settings>>,
     pub pool: Arc<SqlitePool>,
+    pub datasources: Arc<Datasources>,
 }
 
 pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -44,6 +50,7 @@ async fn main() -> Result<(), Error> {
     task_manager.register_task(StatsUpdaterTask::new());
     task_manager.register_task(LoraxSchedulerTask::new());
     task_manager.register_task(server_deletion::ServerDeletionTask::new());
+    task_manager.register_task(AlertsTask::new());
 
     // Create and migrate the Sqlite DB.
     // SeaORM made me want to kill myself.
@@ -51,15 +58,20 @@ async fn main() -> Result<(), Error> {
     sqlx::migrate!("../migrations").run(&pool).await?;
 
     let settings = Arc::new(RwLock::new(Settings::load(&pool).await?));
+    let datasources = Arc::new(Datasources::from_env()?);
     let pool_arc = Arc::new(pool);
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
             commands: vec![
                 commands::lorax::lorax(),
+                commands::nodes::nodes(),
                 commands::modrinth::modrinth(),
                 commands::query::query(),
+                commands::graph::graph(),
                 commands::network::setup_stats(),
+                commands::stats::stats(),
+                commands::alerts::alerts(),
             ],
             event_handler: |ctx, event, framework, data| {
                 Box::pin(event_handler(ctx, event, framework, data))
@@ -69,7 +81,7 @@ async fn main() -> Result<(), Error> {
         .setup(|ctx, _ready, framework| {
             Box::pin(async move {
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
-                let data = Data { settings, pool: pool_arc };
+                let data = Data { settings, pool: pool_arc, datasources };
                 
                 // Run tasks after framework setup
                 task_manager.run_all(ctx, data.clone()).await;
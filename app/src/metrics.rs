@@ -1,160 +1,417 @@
-use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, RequestBuilder};
 use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::time::Duration;
-use std::collections::HashMap;
 
 use crate::error::BotError;
 
 const API_TIMEOUT: Duration = Duration::from_secs(5);
-const API_ENDPOINT: &str = "https://metrics.pyro.host/api/v1/query";
-
-#[derive(Debug)]
-pub struct Metric {
-    pub name: &'static str,
-    pub icon: &'static str,
-    pub query: &'static str,
-    pub format: fn(f64) -> String,
-}
-
-impl Metric {
-    const fn new(
-        name: &'static str,
-        icon: &'static str,
-        query: &'static str,
-        format: fn(f64) -> String,
-    ) -> Self {
-        Self {
-            name,
-            icon,
-            query,
-            format,
+const DEFAULT_URL: &str = "https://metrics.pyro.host";
+const QUERY_PATH: &str = "/api/v1/query";
+const QUERY_RANGE_PATH: &str = "/api/v1/query_range";
+
+/// The datasource configured by the unprefixed `PROMETHEUS_*` variables,
+/// used when a query or stat channel doesn't pick one.
+pub const DEFAULT_DATASOURCE: &str = "default";
+
+#[derive(Clone)]
+enum Auth {
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    Bearer(String),
+}
+
+// Hand-written so passwords and tokens never end up in the logs
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Basic { username, password } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &password.as_ref().map(|_| "<redacted>"))
+                .finish(),
+            Auth::Bearer(_) => f.debug_tuple("Bearer").field(&"<redacted>").finish(),
+        }
+    }
+}
+
+struct DatasourceConfig {
+    url: String,
+    auth: Option<Auth>,
+    headers: HeaderMap,
+    ca_cert: Option<String>,
+    insecure_skip_verify: bool,
+}
+
+impl DatasourceConfig {
+    /// Reads `{prefix}_URL`, `{prefix}_USERNAME`, `{prefix}_PASSWORD`,
+    /// `{prefix}_BEARER_TOKEN`, `{prefix}_HEADERS` (`Name=value,Name=value`),
+    /// `{prefix}_CA_CERT` (path to a PEM file) and
+    /// `{prefix}_INSECURE_SKIP_VERIFY`.
+    fn from_env(prefix: &str, default_url: Option<&str>) -> Result<Self, BotError> {
+        let var = |key: &str| {
+            std::env::var(format!("{}_{}", prefix, key))
+                .ok()
+                .filter(|v| !v.is_empty())
+        };
+
+        let url = var("URL")
+            .or_else(|| default_url.map(str::to_string))
+            .ok_or_else(|| BotError::Config(format!("{}_URL is not set", prefix)))?;
+
+        let auth = match (var("USERNAME"), var("BEARER_TOKEN")) {
+            (Some(_), Some(_)) => {
+                return Err(BotError::Config(format!(
+                    "{}_USERNAME and {}_BEARER_TOKEN can't both be set",
+                    prefix, prefix
+                )))
+            }
+            (Some(username), None) => Some(Auth::Basic {
+                username,
+                password: var("PASSWORD"),
+            }),
+            (None, Some(token)) => Some(Auth::Bearer(token)),
+            (None, None) => None,
+        };
+
+        let mut headers = HeaderMap::new();
+        for header in var("HEADERS").iter().flat_map(|h| h.split(',')) {
+            let (name, value) = header.split_once('=').ok_or_else(|| {
+                BotError::Config(format!(
+                    "{}_HEADERS: expected `Name=value`, got `{}`",
+                    prefix, header
+                ))
+            })?;
+            let name = HeaderName::from_bytes(name.trim().as_bytes()).map_err(|e| {
+                BotError::Config(format!("{}_HEADERS: bad header name: {}", prefix, e))
+            })?;
+            let value = HeaderValue::from_str(value.trim()).map_err(|e| {
+                BotError::Config(format!("{}_HEADERS: bad header value: {}", prefix, e))
+            })?;
+            headers.insert(name, value);
         }
+
+        let insecure_skip_verify = var("INSECURE_SKIP_VERIFY")
+            .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            auth,
+            headers,
+            ca_cert: var("CA_CERT"),
+            insecure_skip_verify,
+        })
     }
+}
 
-    pub fn format_value(&self, value: f64) -> String {
-        format!("{} {}", self.icon, (self.format)(value))
+// Extra headers often carry API keys, so only their names are shown
+impl fmt::Debug for DatasourceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatasourceConfig")
+            .field("url", &self.url)
+            .field("auth", &self.auth)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("ca_cert", &self.ca_cert)
+            .field("insecure_skip_verify", &self.insecure_skip_verify)
+            .finish()
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct PrometheusResponse {
-    status: String,
-    data: PrometheusData,
+/// Every Prometheus-compatible server the bot can query, by name.
+#[derive(Debug, Clone)]
+pub struct Datasources {
+    clients: BTreeMap<String, MetricsClient>,
 }
 
-#[derive(Debug, Deserialize)]
-struct PrometheusData {
-    result: Vec<PrometheusResult>,
+impl Datasources {
+    /// The default datasource comes from `PROMETHEUS_URL` and friends, see
+    /// `DatasourceConfig::from_env`. More can be listed in
+    /// `PROMETHEUS_DATASOURCES` (e.g. `staging,eu-vm`), each configured by
+    /// `PROMETHEUS_<NAME>_*` with the name upper-cased and `-` replaced by `_`.
+    pub fn from_env() -> Result<Self, BotError> {
+        let mut clients = BTreeMap::new();
+        clients.insert(
+            DEFAULT_DATASOURCE.to_string(),
+            MetricsClient::new(
+                DEFAULT_DATASOURCE,
+                DatasourceConfig::from_env("PROMETHEUS", Some(DEFAULT_URL))?,
+            )?,
+        );
+
+        let names = std::env::var("PROMETHEUS_DATASOURCES").unwrap_or_default();
+        for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let name = name.to_lowercase();
+            if clients.contains_key(&name) {
+                return Err(BotError::Config(format!(
+                    "Datasource `{}` is listed twice",
+                    name
+                )));
+            }
+            let prefix = format!("PROMETHEUS_{}", name.to_uppercase().replace('-', "_"));
+            let config = DatasourceConfig::from_env(&prefix, None)?;
+            clients.insert(name.clone(), MetricsClient::new(&name, config)?);
+        }
+
+        Ok(Self { clients })
+    }
+
+    /// `None` is the default datasource.
+    pub fn get(&self, name: Option<&str>) -> Option<&MetricsClient> {
+        self.clients.get(name.unwrap_or(DEFAULT_DATASOURCE))
+    }
+
+    pub fn default_client(&self) -> &MetricsClient {
+        &self.clients[DEFAULT_DATASOURCE]
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.clients.keys().map(String::as_str)
+    }
+}
+
+/// A timestamp in seconds and a value. Prometheus sends values as strings so
+/// `NaN` and `+Inf` survive the trip.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SampleValue(pub f64, pub String);
+
+impl SampleValue {
+    pub fn as_f64(&self) -> Option<f64> {
+        self.1.parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sample {
+    #[serde(default)]
+    pub metric: BTreeMap<String, String>,
+    pub value: SampleValue,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Series {
+    #[serde(default)]
+    pub metric: BTreeMap<String, String>,
+    pub values: Vec<SampleValue>,
+}
+
+/// The `data` of a query response, one variant per `resultType`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "resultType", content = "result", rename_all = "lowercase")]
+pub enum QueryResult {
+    Scalar(SampleValue),
+    String(SampleValue),
+    Vector(Vec<Sample>),
+    /// Only returned for range selectors such as `up[5m]`.
+    Matrix(Vec<Series>),
+}
+
+impl QueryResult {
+    /// The value of a scalar, or of the first sample of a vector.
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            QueryResult::Scalar(value) => value.as_f64(),
+            QueryResult::Vector(samples) => samples.first()?.value.as_f64(),
+            QueryResult::String(_) | QueryResult::Matrix(_) => None,
+        }
+    }
+
+    /// Every series and its value, for a scalar or vector. Values that aren't
+    /// numbers are left out.
+    pub fn samples(&self) -> Option<Vec<(String, f64)>> {
+        match self {
+            QueryResult::Scalar(value) => Some(
+                value
+                    .as_f64()
+                    .map(|v| (series_name(&BTreeMap::new()), v))
+                    .into_iter()
+                    .collect(),
+            ),
+            QueryResult::Vector(samples) => Some(
+                samples
+                    .iter()
+                    .filter_map(|s| s.value.as_f64().map(|v| (series_name(&s.metric), v)))
+                    .collect(),
+            ),
+            QueryResult::String(_) | QueryResult::Matrix(_) => None,
+        }
+    }
+}
+
+/// `name{label="value", ...}`, the way Prometheus shows a series.
+pub fn series_name(metric: &BTreeMap<String, String>) -> String {
+    let name = metric
+        .get("__name__")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let labels = metric
+        .iter()
+        .filter(|(key, _)| *key != "__name__")
+        .map(|(key, value)| format!("{}=\"{}\"", key, value))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if labels.is_empty() && !name.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}{{{}}}", name, labels)
+    }
 }
 
 #[derive(Debug, Deserialize)]
-struct PrometheusResult {
-    metric: HashMap<String, String>,
-    value: (f64, serde_json::Value),
+struct PrometheusResponse {
+    status: String,
+    data: Option<QueryResult>,
+    error: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MetricsClient {
+    name: String,
     client: Client,
+    url: String,
+    auth: Option<Auth>,
+}
+
+// The reqwest client would print the default headers as they are
+impl fmt::Debug for MetricsClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsClient")
+            .field("name", &self.name)
+            .field("url", &self.url)
+            .field("auth", &self.auth)
+            .finish_non_exhaustive()
+    }
 }
 
 impl MetricsClient {
-    pub fn new() -> Self {
-        Self {
-            client: Client::builder()
-                .timeout(API_TIMEOUT)
-                .build()
-                .expect("Failed to create HTTP client"),
+    fn new(name: &str, config: DatasourceConfig) -> Result<Self, BotError> {
+        let mut builder = Client::builder()
+            .timeout(API_TIMEOUT)
+            .default_headers(config.headers)
+            .danger_accept_invalid_certs(config.insecure_skip_verify);
+        if let Some(path) = &config.ca_cert {
+            let pem = std::fs::read(path).map_err(|e| {
+                BotError::Config(format!("Can't read CA cert for `{}`: {}", name, e))
+            })?;
+            builder = builder.add_root_certificate(Certificate::from_pem(&pem)?);
         }
+
+        Ok(Self {
+            name: name.to_string(),
+            client: builder.build()?,
+            url: config.url,
+            auth: config.auth,
+        })
     }
 
-    pub async fn fetch_metric(&self, query: &str) -> crate::error::Result<f64> {
-        let resp: PrometheusResponse = self
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn request(&self, path: &str, params: &[(&str, &str)]) -> RequestBuilder {
+        let request = self
             .client
-            .get(API_ENDPOINT)
-            .query(&[("query", query)])
-            .send()
-            .await
-            .map_err(BotError::Http)?
-            .json()
+            .get(format!("{}{}", self.url, path))
+            .query(params);
+        match &self.auth {
+            Some(Auth::Basic { username, password }) => {
+                request.basic_auth(username, password.as_ref())
+            }
+            Some(Auth::Bearer(token)) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    pub async fn fetch(&self, query: &str) -> crate::error::Result<QueryResult> {
+        self.send(self.request(QUERY_PATH, &[("query", query)]))
             .await
-            .map_err(BotError::Http)?;
+    }
+
+    /// Samples from `start` to `end` (unix seconds), one every `step` seconds.
+    pub async fn fetch_range(
+        &self,
+        query: &str,
+        start: i64,
+        end: i64,
+        step: u64,
+    ) -> crate::error::Result<Vec<Series>> {
+        let (start, end, step) = (start.to_string(), end.to_string(), step.to_string());
+        let request = self.request(
+            QUERY_RANGE_PATH,
+            &[
+                ("query", query),
+                ("start", &start),
+                ("end", &end),
+                ("step", &step),
+            ],
+        );
+
+        match self.send(request).await? {
+            QueryResult::Matrix(series) => Ok(series),
+            _ => Err(BotError::Metrics(
+                "Range query didn't return a matrix".to_string(),
+            )),
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder) -> crate::error::Result<QueryResult> {
+        let response = request.send().await.map_err(BotError::Http)?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(BotError::Http)?;
+
+        // Prometheus answers bad queries with a 4xx and a JSON error, but an
+        // auth proxy in front of it answers with its own page
+        let resp: PrometheusResponse = match serde_json::from_slice(&body) {
+            Ok(resp) => resp,
+            Err(_) if !status.is_success() => {
+                return Err(BotError::Metrics(format!(
+                    "`{}` answered with HTTP {}",
+                    self.name, status
+                )))
+            }
+            Err(e) => {
+                return Err(BotError::Metrics(format!(
+                    "`{}` sent a response that isn't valid: {}",
+                    self.name, e
+                )))
+            }
+        };
 
         match resp {
             PrometheusResponse {
                 status,
-                data: PrometheusData { result },
-            } if status == "success" => result
-                .first()
-                .and_then(|r| r.value.1.as_str().and_then(|s| s.parse().ok()))
-                .ok_or_else(|| BotError::Metrics("No valid metric value found".to_string())),
-            _ => Err(BotError::Metrics("Prometheus query failed".to_string())),
+                data: Some(data),
+                ..
+            } if status == "success" => Ok(data),
+            PrometheusResponse { error, .. } => Err(BotError::Metrics(
+                error.unwrap_or_else(|| "Prometheus query failed".to_string()),
+            )),
         }
     }
 
+    /// The value of a scalar, or of the first sample of a vector.
+    pub async fn fetch_metric(&self, query: &str) -> crate::error::Result<f64> {
+        self.fetch(query)
+            .await?
+            .value()
+            .ok_or_else(|| BotError::Metrics("No valid metric value found".to_string()))
+    }
+
     pub async fn fetch_existing_trees(&self) -> crate::error::Result<Vec<String>> {
-        let query = "node_uname_info";
-        let resp: PrometheusResponse = self
-            .client
-            .get(API_ENDPOINT)
-            .query(&[("query", query)])
-            .send()
-            .await
-            .map_err(BotError::Http)?
-            .json()
-            .await
-            .map_err(BotError::Http)?;
-
-        let mut node_names = Vec::new();
-        let PrometheusResponse {
-            status,
-            data: PrometheusData { result },
-        } = resp;
-        
-        if status == "success" {
-            for item in result {
-                if let Some(nodename) = item.metric.get("nodename") {
-                    node_names.push(nodename.clone());
-                }
-            }
-        }
-        
-        Ok(node_names)
-    }
-}
-
-pub const METRICS: &[Metric] = &[
-    Metric::new(
-        "nodes",
-        "🖥️",
-        "count(up{job=\"node\"} == 1)",
-        |v| format!("Active Nodes {:.0}", v),
-    ),
-    Metric::new(
-        "network",
-        "🌐",
-        "sum(rate(node_network_receive_bytes_total[5m]) + rate(node_network_transmit_bytes_total[5m])) or vector(0)",
-        |v| format!("Net {}/s", format_bytes(v)),
-    ),
-    Metric::new(
-        "network_total",
-        "📊",
-        "sum(increase(node_network_receive_bytes_total[7d]) + increase(node_network_transmit_bytes_total[7d])) or vector(0)",
-        |v| format!("7d Total {}", format_large_bytes(v)),
-    ),
-    Metric::new(
-        "storage",
-        "💾",
-        "sum(node_filesystem_size_bytes{mountpoint=\"/\"} - node_filesystem_free_bytes{mountpoint=\"/\"})",
-        |v| format!("Disk {}", format_bytes(v)),
-    ),
-    Metric::new(
-        "memory",
-        "🧠",
-        "sum(node_memory_MemTotal_bytes - node_memory_MemAvailable_bytes) or vector(0)",
-        |v| format!("Mem {}", format_bytes(v)),
-    ),
-];
+        let QueryResult::Vector(samples) = self.fetch("node_uname_info").await? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(samples
+            .into_iter()
+            .filter_map(|s| s.metric.get("nodename").cloned())
+            .collect())
+    }
+}
 
 pub fn format_bytes(bytes: f64) -> String {
     const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
@@ -173,15 +430,108 @@ pub fn format_bytes(bytes: f64) -> String {
     }
 }
 
-pub fn format_large_bytes(bytes: f64) -> String {
-    const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
-    let mut value = bytes;
-    let mut unit_index = 0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
 
-    while value >= 1024.0 && unit_index < UNITS.len() - 1 {
-        value /= 1024.0;
-        unit_index += 1;
+    /// `PROMETHEUS_DATASOURCES` is shared by the whole process.
+    static DATASOURCES_ENV: Mutex<()> = Mutex::new(());
+
+    fn set_vars(prefix: &str, vars: &[(&str, &str)]) {
+        for (key, value) in vars {
+            std::env::set_var(format!("{}_{}", prefix, key), value);
+        }
+    }
+
+    #[test]
+    fn parses_headers() {
+        set_vars(
+            "TEST_HEADERS",
+            &[
+                ("URL", "https://vm.example/"),
+                ("HEADERS", "X-Scope-OrgID=tenant, X-Key = abc"),
+            ],
+        );
+        let config = DatasourceConfig::from_env("TEST_HEADERS", None).unwrap();
+        assert_eq!(config.url, "https://vm.example");
+        assert_eq!(config.headers["x-scope-orgid"], "tenant");
+        assert_eq!(config.headers["x-key"], "abc");
+        assert!(config.auth.is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        set_vars(
+            "TEST_BAD_HEADERS",
+            &[("URL", "https://vm.example"), ("HEADERS", "X-Key")],
+        );
+        assert!(DatasourceConfig::from_env("TEST_BAD_HEADERS", None).is_err());
+
+        set_vars(
+            "TEST_BAD_HEADER_NAME",
+            &[("URL", "https://vm.example"), ("HEADERS", "X Key=a")],
+        );
+        assert!(DatasourceConfig::from_env("TEST_BAD_HEADER_NAME", None).is_err());
+    }
+
+    #[test]
+    fn rejects_basic_and_bearer_auth_together() {
+        set_vars(
+            "TEST_BOTH_AUTH",
+            &[("USERNAME", "bot"), ("BEARER_TOKEN", "secret")],
+        );
+        let error = DatasourceConfig::from_env("TEST_BOTH_AUTH", Some(DEFAULT_URL)).unwrap_err();
+        assert!(error.to_string().contains("can't both be set"));
+
+        set_vars(
+            "TEST_BASIC_AUTH",
+            &[("USERNAME", "bot"), ("PASSWORD", "hunter2")],
+        );
+        let config = DatasourceConfig::from_env("TEST_BASIC_AUTH", Some(DEFAULT_URL)).unwrap();
+        assert!(matches!(
+            config.auth,
+            Some(Auth::Basic { ref username, password: Some(_) }) if username == "bot"
+        ));
+        assert_eq!(config.url, DEFAULT_URL);
     }
 
-    format!("{:.1}{}", value, UNITS[unit_index])
+    #[test]
+    fn named_datasources_need_a_url() {
+        assert!(DatasourceConfig::from_env("TEST_NO_URL", None).is_err());
+    }
+
+    #[test]
+    fn maps_datasource_names_to_their_prefix() {
+        let _env = DATASOURCES_ENV.lock().unwrap();
+        std::env::set_var("PROMETHEUS_DATASOURCES", "eu-west, Staging");
+        set_vars("PROMETHEUS_EU_WEST", &[("URL", "https://eu.example")]);
+        set_vars("PROMETHEUS_STAGING", &[("URL", "https://staging.example")]);
+
+        let datasources = Datasources::from_env().unwrap();
+        assert_eq!(
+            datasources.names().collect::<Vec<_>>(),
+            [DEFAULT_DATASOURCE, "eu-west", "staging"]
+        );
+        assert_eq!(
+            datasources.get(Some("eu-west")).unwrap().url,
+            "https://eu.example"
+        );
+        assert_eq!(
+            datasources.get(Some("staging")).unwrap().url,
+            "https://staging.example"
+        );
+        std::env::remove_var("PROMETHEUS_DATASOURCES");
+    }
+
+    #[test]
+    fn rejects_duplicate_datasources() {
+        let _env = DATASOURCES_ENV.lock().unwrap();
+        std::env::set_var("PROMETHEUS_DATASOURCES", "eu-west,EU-WEST");
+        set_vars("PROMETHEUS_EU_WEST", &[("URL", "https://eu.example")]);
+
+        let error = Datasources::from_env().unwrap_err();
+        assert!(error.to_string().contains("listed twice"));
+        std::env::remove_var("PROMETHEUS_DATASOURCES");
+    }
 }
@@ -1,7 +1,9 @@
+use crate::db::alerts::AlertSeverity;
+use crate::voting;
 use poise::serenity_prelude::{self as serenity, ChannelId, GuildId, MessageId, RoleId, UserId};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::SqlitePool;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use tracing::info;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -13,35 +15,165 @@ pub struct TestingServer {
 #[derive(Serialize, Deserialize, Default, Clone, Debug)]
 pub struct GuildSettings {
     pub stats_category: Option<ChannelId>,
-    pub nodes_channel: Option<ChannelId>,
-    pub network_channel: Option<ChannelId>,
-    pub network_total_channel: Option<ChannelId>,
-    pub storage_channel: Option<ChannelId>,
-    pub memory_channel: Option<ChannelId>,
     pub lorax_role: Option<RoleId>,
+    /// Held by the top namers on the leaderboard, the few members with the
+    /// most winning names. It's handed on as events end.
+    pub lorax_winner_role: Option<RoleId>,
     pub lorax_channel: Option<ChannelId>,
-    pub lorax_state: LoraxState,
+    /// Forum to post a campaign thread per name in, for events that ask for
+    /// one.
+    pub lorax_campaign_forum: Option<ChannelId>,
+    /// Running Lorax events by the ID of their `lorax_events` row. Events are
+    /// removed once they're over.
+    pub lorax_events: BTreeMap<u64, LoraxState>,
+    /// Minutes before the end of a phase to remind people, `None` uses
+    /// `DEFAULT_LORAX_REMINDERS`.
+    pub lorax_reminders: Option<Vec<u64>>,
+    pub lorax_eligibility: LoraxEligibility,
+    /// Language of the built-in announcement copy.
+    pub lorax_locale: LoraxLocale,
+    /// Announcement copy that replaces the built-in text for the locale.
+    pub lorax_messages: BTreeMap<LoraxMessage, String>,
+    /// Where firing and resolved alerts are posted.
+    pub alerts_channel: Option<ChannelId>,
+    /// Roles pinged when an alert of that severity fires.
+    pub alert_roles: BTreeMap<AlertSeverity, RoleId>,
 }
 
 impl GuildSettings {
-    pub fn get_stats_channels(&self) -> Vec<ChannelId> {
-        let mut channels = Vec::new();
-        if let Some(ch) = self.nodes_channel {
-            channels.push(ch);
-        }
-        if let Some(ch) = self.network_channel {
-            channels.push(ch);
-        }
-        if let Some(ch) = self.network_total_channel {
-            channels.push(ch);
+    pub fn lorax_event(&self, event_id: u64) -> Option<&LoraxState> {
+        self.lorax_events.get(&event_id)
+    }
+
+    pub fn lorax_event_mut(&mut self, event_id: u64) -> Option<&mut LoraxState> {
+        self.lorax_events.get_mut(&event_id)
+    }
+
+    /// Forgets an event once it's been archived or cancelled.
+    pub fn end_lorax_event(&mut self, event_id: u64) {
+        self.lorax_events.remove(&event_id);
+    }
+
+    pub fn lorax_reminders(&self) -> &[u64] {
+        self.lorax_reminders
+            .as_deref()
+            .unwrap_or(DEFAULT_LORAX_REMINDERS)
+    }
+}
+
+pub const DEFAULT_LORAX_REMINDERS: &[u64] = &[60, 10];
+
+/// Who may submit names and vote in Lorax events, to keep alt accounts from
+/// swinging the results.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct LoraxEligibility {
+    #[serde(default)]
+    pub min_account_age_days: u64,
+    #[serde(default)]
+    pub min_member_age_days: u64,
+    #[serde(default)]
+    pub required_role: Option<RoleId>,
+}
+
+impl LoraxEligibility {
+    pub fn is_unrestricted(&self) -> bool {
+        self.min_account_age_days == 0
+            && self.min_member_age_days == 0
+            && self.required_role.is_none()
+    }
+}
+
+/// Languages the built-in Lorax announcements come in.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum LoraxLocale {
+    #[default]
+    #[name = "English"]
+    English,
+    #[name = "Deutsch"]
+    German,
+    #[name = "Español"]
+    Spanish,
+    #[name = "Français"]
+    French,
+    #[name = "Nederlands"]
+    Dutch,
+}
+
+impl LoraxLocale {
+    pub fn code(&self) -> &'static str {
+        match self {
+            LoraxLocale::English => "en",
+            LoraxLocale::German => "de",
+            LoraxLocale::Spanish => "es",
+            LoraxLocale::French => "fr",
+            LoraxLocale::Dutch => "nl",
         }
-        if let Some(ch) = self.storage_channel {
-            channels.push(ch);
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "en" => Some(LoraxLocale::English),
+            "de" => Some(LoraxLocale::German),
+            "es" => Some(LoraxLocale::Spanish),
+            "fr" => Some(LoraxLocale::French),
+            "nl" => Some(LoraxLocale::Dutch),
+            _ => None,
         }
-        if let Some(ch) = self.memory_channel {
-            channels.push(ch);
+    }
+}
+
+/// Lorax announcements a guild can write its own copy for.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LoraxMessage {
+    #[name = "Event teaser"]
+    Teaser,
+    #[name = "Submissions open"]
+    SubmissionsOpen,
+    #[name = "No submissions"]
+    NoSubmissions,
+    #[name = "Voting open"]
+    VotingOpen,
+    #[name = "Campaign thread"]
+    CampaignThread,
+    #[name = "Tiebreaker"]
+    Tiebreaker,
+    #[name = "Winner"]
+    Winner,
+    #[name = "Submissions reminder"]
+    SubmissionsReminder,
+    #[name = "Voting reminder"]
+    VotingReminder,
+    #[name = "Phase extended"]
+    Extended,
+    #[name = "Phase shortened"]
+    Shortened,
+    #[name = "Event cancelled"]
+    Cancelled,
+}
+
+impl LoraxMessage {
+    /// The placeholders that are filled in for this message.
+    ///
+    /// Announcements leave out `{end}`: their text isn't re-rendered when the
+    /// close time changes, so the embed's close time is the one to trust.
+    pub fn placeholders(&self) -> &'static [&'static str] {
+        match self {
+            LoraxMessage::Teaser => &["role", "location", "start"],
+            LoraxMessage::SubmissionsOpen => &["role", "location"],
+            LoraxMessage::NoSubmissions => &["role", "location"],
+            LoraxMessage::VotingOpen => &["role", "location", "count"],
+            LoraxMessage::CampaignThread => &["location", "count"],
+            LoraxMessage::Tiebreaker => &["role", "location", "count", "round"],
+            LoraxMessage::Winner => &["role", "location", "count", "winner", "submitter"],
+            LoraxMessage::SubmissionsReminder | LoraxMessage::VotingReminder => {
+                &["role", "location", "end"]
+            }
+            LoraxMessage::Extended | LoraxMessage::Shortened => &["location", "minutes", "end"],
+            LoraxMessage::Cancelled => &["location"],
         }
-        channels
     }
 }
 
@@ -62,6 +194,64 @@ impl Default for UserSettings {
     }
 }
 
+/// A suggested tree name, with what it means if the submitter told us.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub submitter: UserId,
+    pub name: String,
+    #[serde(default)]
+    pub meaning: Option<String>,
+    /// The submitter's pitch for the name, pinned on its campaign post.
+    #[serde(default)]
+    pub statement: Option<String>,
+}
+
+/// Submissions used to be keyed by submitter, one each, and before that were
+/// just the name.
+fn deserialize_submissions<'de, D>(deserializer: D) -> Result<Vec<Submission>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredSubmissions {
+        List(Vec<Submission>),
+        BySubmitter(HashMap<UserId, StoredSubmission>),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredSubmission {
+        Name(String),
+        Full {
+            name: String,
+            #[serde(default)]
+            meaning: Option<String>,
+        },
+    }
+
+    Ok(match StoredSubmissions::deserialize(deserializer)? {
+        StoredSubmissions::List(submissions) => submissions,
+        StoredSubmissions::BySubmitter(submissions) => submissions
+            .into_iter()
+            .map(|(submitter, stored)| match stored {
+                StoredSubmission::Name(name) => Submission {
+                    submitter,
+                    name,
+                    meaning: None,
+                    statement: None,
+                },
+                StoredSubmission::Full { name, meaning } => Submission {
+                    submitter,
+                    name,
+                    meaning,
+                    statement: None,
+                },
+            })
+            .collect(),
+    })
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub enum LoraxState {
     #[default]
@@ -69,10 +259,35 @@ pub enum LoraxState {
     Submissions {
         end_time: i64,
         message_id: MessageId,
-        submissions: HashMap<UserId, String>,
+        #[serde(deserialize_with = "deserialize_submissions")]
+        submissions: Vec<Submission>,
         location: String,
         voting_duration: u64, // Add this field
         tiebreaker_duration: u64,
+        #[serde(default)]
+        timeline: LoraxTimeline,
+        #[serde(default)]
+        voting_method: VotingMethod,
+        /// Tiebreaker rounds to run before the winner is drawn at random.
+        #[serde(default = "default_tiebreaker_rounds")]
+        max_tiebreaker_rounds: u32,
+        /// Reminders already sent for this phase, in minutes before the end.
+        #[serde(default)]
+        reminders_sent: Vec<u64>,
+        /// Hide the running tallies until voting closes.
+        #[serde(default = "default_blind_voting")]
+        blind_voting: bool,
+        #[serde(default = "default_max_submissions")]
+        max_submissions_per_user: u32,
+        /// Replaces the guild's eligibility rules for this event.
+        #[serde(default)]
+        eligibility: Option<LoraxEligibility>,
+        /// Open a campaign thread when voting starts.
+        #[serde(default = "default_campaign_thread")]
+        campaign_thread: bool,
+        /// Open a forum post per name instead of a single campaign thread.
+        #[serde(default)]
+        campaign_forum: bool,
     },
     Voting {
         end_time: i64,
@@ -80,9 +295,45 @@ pub enum LoraxState {
         thread_id: Option<ChannelId>, // Add this
         options: Vec<String>,
         votes: HashMap<UserId, usize>,
-        submissions: HashMap<UserId, String>,
+        #[serde(deserialize_with = "deserialize_submissions")]
+        submissions: Vec<Submission>,
         location: String,
         tiebreaker_duration: u64,
+        #[serde(default)]
+        timeline: LoraxTimeline,
+        #[serde(default)]
+        voting_method: VotingMethod,
+        /// Tiebreaker rounds to run before the winner is drawn at random.
+        #[serde(default = "default_tiebreaker_rounds")]
+        max_tiebreaker_rounds: u32,
+        /// Reminders already sent for this phase, in minutes before the end.
+        #[serde(default)]
+        reminders_sent: Vec<u64>,
+        /// Hide the running tallies until voting closes.
+        #[serde(default = "default_blind_voting")]
+        blind_voting: bool,
+        /// Ranked ballots for instant-runoff, or the approved options for
+        /// approval voting. Single choice votes go in `votes`.
+        #[serde(default)]
+        ballots: HashMap<UserId, Vec<usize>>,
+        #[serde(default)]
+        eligibility: Option<LoraxEligibility>,
+        /// Forum post for each name, when the event has them instead of a
+        /// single campaign thread.
+        #[serde(default)]
+        campaign_posts: BTreeMap<String, ChannelId>,
+        /// When each voter first cast their ballot this round, as a unix
+        /// timestamp.
+        #[serde(default)]
+        vote_times: HashMap<UserId, i64>,
+        /// When each voter joined the server, taken from their first
+        /// ballot this round so the audit doesn't have to look them up.
+        #[serde(default)]
+        join_times: HashMap<UserId, i64>,
+        /// Voters whose ballot a moderator threw out. They can't vote again
+        /// in this event.
+        #[serde(default)]
+        voided: Vec<UserId>,
     },
     TieBreaker {
         end_time: i64,
@@ -93,10 +344,242 @@ pub enum LoraxState {
         location: String,
         round: u32,
         tiebreaker_duration: u64,
-        submissions: HashMap<UserId, String>,
+        #[serde(deserialize_with = "deserialize_submissions")]
+        submissions: Vec<Submission>,
+        #[serde(default)]
+        timeline: LoraxTimeline,
+        #[serde(default)]
+        voting_method: VotingMethod,
+        /// Tiebreaker rounds to run before the winner is drawn at random.
+        #[serde(default = "default_tiebreaker_rounds")]
+        max_tiebreaker_rounds: u32,
+        /// Reminders already sent for this phase, in minutes before the end.
+        #[serde(default)]
+        reminders_sent: Vec<u64>,
+        /// Hide the running tallies until voting closes.
+        #[serde(default = "default_blind_voting")]
+        blind_voting: bool,
+        #[serde(default)]
+        ballots: HashMap<UserId, Vec<usize>>,
+        #[serde(default)]
+        eligibility: Option<LoraxEligibility>,
+        #[serde(default)]
+        campaign_posts: BTreeMap<String, ChannelId>,
+        #[serde(default)]
+        vote_times: HashMap<UserId, i64>,
+        #[serde(default)]
+        join_times: HashMap<UserId, i64>,
+        #[serde(default)]
+        voided: Vec<UserId>,
     },
 }
 
+/// How a Lorax event runs once it's opened, shared by `/lorax start` and
+/// scheduled events.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoraxEventConfig {
+    /// Phase durations in minutes
+    pub submission_duration: u64,
+    pub voting_duration: u64,
+    pub tiebreaker_duration: u64,
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    #[serde(default = "default_tiebreaker_rounds")]
+    pub max_tiebreaker_rounds: u32,
+    #[serde(default = "default_blind_voting")]
+    pub blind_voting: bool,
+    #[serde(default = "default_max_submissions")]
+    pub max_submissions_per_user: u32,
+    /// Replaces the guild's eligibility rules, `None` uses them as they are.
+    #[serde(default)]
+    pub eligibility: Option<LoraxEligibility>,
+    /// Posted instead of the guild's submissions open message, with the same
+    /// placeholders.
+    #[serde(default)]
+    pub announcement: Option<String>,
+    #[serde(default = "default_campaign_thread")]
+    pub campaign_thread: bool,
+    /// Campaign in a forum post per name rather than a single thread, if the
+    /// guild has a campaign forum.
+    #[serde(default)]
+    pub campaign_forum: bool,
+}
+
+impl Default for LoraxEventConfig {
+    fn default() -> Self {
+        Self {
+            submission_duration: 60,
+            voting_duration: 30,
+            tiebreaker_duration: 15,
+            voting_method: VotingMethod::default(),
+            max_tiebreaker_rounds: DEFAULT_TIEBREAKER_ROUNDS,
+            blind_voting: true,
+            max_submissions_per_user: 1,
+            eligibility: None,
+            announcement: None,
+            campaign_thread: true,
+            campaign_forum: false,
+        }
+    }
+}
+
+fn default_tiebreaker_rounds() -> u32 {
+    DEFAULT_TIEBREAKER_ROUNDS
+}
+
+fn default_blind_voting() -> bool {
+    true
+}
+
+fn default_max_submissions() -> u32 {
+    1
+}
+
+fn default_campaign_thread() -> bool {
+    true
+}
+
+pub const DEFAULT_TIEBREAKER_ROUNDS: u32 = 3;
+
+impl LoraxState {
+    /// Where the node being named is.
+    pub fn location(&self) -> Option<&str> {
+        match self {
+            LoraxState::Idle => None,
+            LoraxState::Submissions { location, .. }
+            | LoraxState::Voting { location, .. }
+            | LoraxState::TieBreaker { location, .. } => Some(location),
+        }
+    }
+
+    pub fn phase_name(&self) -> &'static str {
+        match self {
+            LoraxState::Idle => "idle",
+            LoraxState::Submissions { .. } => "submissions open",
+            LoraxState::Voting { .. } => "voting",
+            LoraxState::TieBreaker { .. } => "tiebreaker",
+        }
+    }
+
+    /// The event's own eligibility rules, if it doesn't use the guild's.
+    pub fn eligibility(&self) -> Option<&LoraxEligibility> {
+        match self {
+            LoraxState::Idle => None,
+            LoraxState::Submissions { eligibility, .. }
+            | LoraxState::Voting { eligibility, .. }
+            | LoraxState::TieBreaker { eligibility, .. } => eligibility.as_ref(),
+        }
+    }
+
+    /// Campaign posts by name, empty unless voting has started in a forum.
+    pub fn campaign_posts(&self) -> Option<&BTreeMap<String, ChannelId>> {
+        match self {
+            LoraxState::Idle | LoraxState::Submissions { .. } => None,
+            LoraxState::Voting { campaign_posts, .. }
+            | LoraxState::TieBreaker { campaign_posts, .. } => Some(campaign_posts),
+        }
+    }
+
+    /// Every name submitted to the event, including ones out of the running.
+    pub fn submissions(&self) -> &[Submission] {
+        match self {
+            LoraxState::Idle => &[],
+            LoraxState::Submissions { submissions, .. }
+            | LoraxState::Voting { submissions, .. }
+            | LoraxState::TieBreaker { submissions, .. } => submissions,
+        }
+    }
+
+    pub fn timeline(&self) -> Option<&LoraxTimeline> {
+        match self {
+            LoraxState::Idle => None,
+            LoraxState::Submissions { timeline, .. }
+            | LoraxState::Voting { timeline, .. }
+            | LoraxState::TieBreaker { timeline, .. } => Some(timeline),
+        }
+    }
+
+    /// The announcement message for the current phase.
+    pub fn message_id(&self) -> Option<MessageId> {
+        match self {
+            LoraxState::Idle => None,
+            LoraxState::Submissions { message_id, .. }
+            | LoraxState::Voting { message_id, .. }
+            | LoraxState::TieBreaker { message_id, .. } => Some(*message_id),
+        }
+    }
+
+    /// Number of people who have cast a vote or ballot in the current round.
+    pub fn voter_count(&self) -> usize {
+        match self {
+            LoraxState::Voting { votes, ballots, .. }
+            | LoraxState::TieBreaker { votes, ballots, .. } => votes.len() + ballots.len(),
+            _ => 0,
+        }
+    }
+
+    /// Votes per option index so far. Ranked ballots count by first
+    /// preference, since the runoff only happens once voting closes.
+    pub fn live_tally(&self) -> HashMap<usize, usize> {
+        match self {
+            LoraxState::Voting {
+                votes,
+                ballots,
+                voting_method,
+                ..
+            }
+            | LoraxState::TieBreaker {
+                votes,
+                ballots,
+                voting_method,
+                ..
+            } => match voting_method {
+                VotingMethod::Single => voting::plurality(votes.values().copied()),
+                VotingMethod::InstantRunoff => {
+                    voting::plurality(ballots.values().filter_map(|b| b.first().copied()))
+                }
+                VotingMethod::Approval => voting::approval(ballots.values()),
+            },
+            _ => HashMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum VotingMethod {
+    #[default]
+    #[name = "Single choice"]
+    Single,
+    #[name = "Ranked choice (instant-runoff)"]
+    InstantRunoff,
+    #[name = "Approval (pick every name you like)"]
+    Approval,
+}
+
+/// Everything about a running Lorax event that we need to archive it once
+/// it's over, carried along from phase to phase.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LoraxTimeline {
+    pub started_at: i64,
+    pub voting_started_at: Option<i64>,
+    pub rounds: Vec<LoraxRound>,
+}
+
+/// Final tallies of a finished voting round. Round 0 is the main vote,
+/// anything after that is a tiebreaker.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoraxRound {
+    pub round: u32,
+    pub ended_at: i64,
+    pub tallies: Vec<(String, usize)>,
+    /// Everyone who cast a ballot this round.
+    #[serde(default)]
+    pub voters: Vec<UserId>,
+    /// The names on every ballot cast this round, without who cast them.
+    #[serde(default)]
+    pub ballots: Vec<Vec<String>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Settings {
     pub guilds: HashMap<GuildId, GuildSettings>,
@@ -120,8 +603,9 @@ impl Settings {
         // gives for Ids cannot be automatically converted :^)
         let guild_rows_res = sqlx::query!(
             r#"
-            select id, stats_category, nodes_channel, network_channel, network_total_channel,
-                    storage_channel, memory_channel, lorax_role, lorax_channel, lorax_state
+            select id, stats_category, lorax_role, lorax_winner_role, lorax_channel,
+                    lorax_campaign_forum, lorax_events, lorax_reminders, lorax_eligibility,
+                    lorax_locale, lorax_messages, alerts_channel, alert_roles
             from guilds
             "#,
         )
@@ -147,14 +631,31 @@ impl Settings {
                     GuildId::new(r.id as u64),
                     GuildSettings {
                         stats_category: from_db(r.stats_category),
-                        nodes_channel: from_db(r.nodes_channel),
-                        network_channel: from_db(r.network_channel),
-                        network_total_channel: from_db(r.network_total_channel),
-                        storage_channel: from_db(r.storage_channel),
-                        memory_channel: from_db(r.memory_channel),
                         lorax_role: from_db(r.lorax_role),
+                        lorax_winner_role: from_db(r.lorax_winner_role),
                         lorax_channel: from_db(r.lorax_channel),
-                        lorax_state: serde_json::from_str(r.lorax_state.unwrap().as_str()).unwrap(),
+                        lorax_campaign_forum: from_db(r.lorax_campaign_forum),
+                        lorax_events: r
+                            .lorax_events
+                            .map(|e| serde_json::from_str(e.as_str()).unwrap())
+                            .unwrap_or_default(),
+                        lorax_reminders: r
+                            .lorax_reminders
+                            .and_then(|r| serde_json::from_str(r.as_str()).ok()),
+                        lorax_eligibility: r
+                            .lorax_eligibility
+                            .and_then(|e| serde_json::from_str(e.as_str()).ok())
+                            .unwrap_or_default(),
+                        lorax_locale: LoraxLocale::from_code(&r.lorax_locale).unwrap_or_default(),
+                        lorax_messages: r
+                            .lorax_messages
+                            .and_then(|m| serde_json::from_str(m.as_str()).ok())
+                            .unwrap_or_default(),
+                        alerts_channel: from_db(r.alerts_channel),
+                        alert_roles: r
+                            .alert_roles
+                            .and_then(|a| serde_json::from_str(a.as_str()).ok())
+                            .unwrap_or_default(),
                     },
                 );
             });
@@ -193,43 +694,56 @@ impl Settings {
         for (k, v) in self.guilds.iter() {
             let id = k.get() as i64;
             let stats_category = v.stats_category.map(|v| v.get() as i64);
-            let nodes_channel = v.nodes_channel.map(|v| v.get() as i64);
-            let network_channel = v.network_channel.map(|v| v.get() as i64);
-            let network_total_channel = v.network_total_channel.map(|v| v.get() as i64);
-            let storage_channel = v.storage_channel.map(|v| v.get() as i64);
-            let memory_channel = v.memory_channel.map(|v| v.get() as i64);
             let lorax_role = v.lorax_role.map(|v| v.get() as i64);
+            let lorax_winner_role = v.lorax_winner_role.map(|v| v.get() as i64);
             let lorax_channel = v.lorax_channel.map(|v| v.get() as i64);
-            let lorax_state_serialized = serde_json::to_string(&v.lorax_state).unwrap();
+            let lorax_campaign_forum = v.lorax_campaign_forum.map(|v| v.get() as i64);
+            let lorax_events_serialized = serde_json::to_string(&v.lorax_events).unwrap();
+            let lorax_reminders_serialized = v
+                .lorax_reminders
+                .as_ref()
+                .map(|r| serde_json::to_string(r).unwrap());
+            let lorax_eligibility_serialized = serde_json::to_string(&v.lorax_eligibility).unwrap();
+            let lorax_locale = v.lorax_locale.code();
+            let lorax_messages_serialized = serde_json::to_string(&v.lorax_messages).unwrap();
+            let alerts_channel = v.alerts_channel.map(|v| v.get() as i64);
+            let alert_roles_serialized = serde_json::to_string(&v.alert_roles).unwrap();
 
             sqlx::query!(
                 r#"
                 insert into guilds (
-                    id, stats_category, nodes_channel, network_channel, 
-                    network_total_channel, storage_channel, memory_channel,
-                    lorax_role, lorax_channel, lorax_state
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                    id, stats_category,
+                    lorax_role, lorax_winner_role, lorax_channel, lorax_campaign_forum,
+                    lorax_events, lorax_reminders, lorax_eligibility,
+                    lorax_locale, lorax_messages, alerts_channel, alert_roles
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 on conflict(id) do update set
                     stats_category = excluded.stats_category,
-                    nodes_channel = excluded.nodes_channel,
-                    network_channel = excluded.network_channel,
-                    network_total_channel = excluded.network_total_channel,
-                    storage_channel = excluded.storage_channel,
-                    memory_channel = excluded.memory_channel,
                     lorax_role = excluded.lorax_role,
+                    lorax_winner_role = excluded.lorax_winner_role,
                     lorax_channel = excluded.lorax_channel,
-                    lorax_state = excluded.lorax_state
+                    lorax_campaign_forum = excluded.lorax_campaign_forum,
+                    lorax_events = excluded.lorax_events,
+                    lorax_reminders = excluded.lorax_reminders,
+                    lorax_eligibility = excluded.lorax_eligibility,
+                    lorax_locale = excluded.lorax_locale,
+                    lorax_messages = excluded.lorax_messages,
+                    alerts_channel = excluded.alerts_channel,
+                    alert_roles = excluded.alert_roles
                 "#,
                 id,
                 stats_category,
-                nodes_channel,
-                network_channel,
-                network_total_channel,
-                storage_channel,
-                memory_channel,
                 lorax_role,
+                lorax_winner_role,
                 lorax_channel,
-                lorax_state_serialized,
+                lorax_campaign_forum,
+                lorax_events_serialized,
+                lorax_reminders_serialized,
+                lorax_eligibility_serialized,
+                lorax_locale,
+                lorax_messages_serialized,
+                alerts_channel,
+                alert_roles_serialized,
             )
             .execute(pool)
             .await?;
@@ -0,0 +1,463 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use poise::serenity_prelude::{
+    self as serenity, Color, CreateAllowedMentions, CreateEmbed, CreateMessage,
+};
+use sqlx::SqlitePool;
+use std::{collections::HashMap, time::Duration};
+use tokio::time::{self, MissedTickBehavior};
+use tracing::{error, info};
+
+use crate::{
+    commands::alerts::describe_condition,
+    db::alerts::{self, AlertRule, AlertState, AlertStatus},
+    util::{truncate_description, truncate_field},
+    Data, Error,
+};
+
+use super::Task;
+
+const EVAL_INTERVAL: Duration = Duration::from_secs(60);
+// Series listed in one firing or resolved embed
+const MAX_LISTED_SERIES: usize = 15;
+
+#[derive(Debug)]
+pub struct AlertsTask;
+
+impl AlertsTask {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// What one evaluation does to the series of a rule.
+#[derive(Debug, Default)]
+struct Transitions {
+    /// Still pending or still firing, with the latest value.
+    ongoing: Vec<AlertState>,
+    /// Matched for long enough, to be posted and then saved as firing.
+    fired: Vec<AlertState>,
+    /// Firing but no longer matching, to be posted and then cleared.
+    resolved: Vec<AlertState>,
+    /// Pending but no longer matching, cleared without a post.
+    dropped: Vec<AlertState>,
+}
+
+fn transitions(
+    rule: &AlertRule,
+    previous: Vec<AlertState>,
+    samples: Vec<(String, f64)>,
+    now: i64,
+) -> Transitions {
+    let mut previous: HashMap<String, AlertState> = previous
+        .into_iter()
+        .map(|state| (state.series.clone(), state))
+        .collect();
+
+    let mut result = Transitions::default();
+    for (series, value) in samples {
+        if !rule.comparison.matches(value, rule.threshold) {
+            continue;
+        }
+        let mut state = previous.remove(&series).unwrap_or(AlertState {
+            series,
+            status: AlertStatus::Pending,
+            active_since: now,
+            value,
+        });
+        state.value = value;
+        if state.status == AlertStatus::Pending && now - state.active_since >= rule.for_secs as i64
+        {
+            state.status = AlertStatus::Firing;
+            result.fired.push(state);
+        } else {
+            result.ongoing.push(state);
+        }
+    }
+
+    // Whatever is left no longer matches, or is gone from the query
+    for state in previous.into_values() {
+        match state.status {
+            AlertStatus::Firing => result.resolved.push(state),
+            AlertStatus::Pending => result.dropped.push(state),
+        }
+    }
+    result
+}
+
+/// Saves the outcome of an evaluation. Fired series that weren't posted stay
+/// pending, and resolved ones that weren't posted stay firing, so the next
+/// evaluation posts them again.
+async fn save(
+    pool: &SqlitePool,
+    rule_id: i64,
+    transitions: &Transitions,
+    fired_posted: bool,
+    resolved_posted: bool,
+) -> Result<(), Error> {
+    for state in &transitions.ongoing {
+        alerts::set_state(pool, rule_id, state).await?;
+    }
+    for state in &transitions.fired {
+        if fired_posted {
+            alerts::set_state(pool, rule_id, state).await?;
+        } else {
+            let state = AlertState {
+                status: AlertStatus::Pending,
+                ..state.clone()
+            };
+            alerts::set_state(pool, rule_id, &state).await?;
+        }
+    }
+    for state in &transitions.dropped {
+        alerts::clear_state(pool, rule_id, &state.series).await?;
+    }
+    if resolved_posted {
+        for state in &transitions.resolved {
+            alerts::clear_state(pool, rule_id, &state.series).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Moves every series of a rule between pending, firing and ok, and posts
+/// the ones that started firing or resolved.
+async fn evaluate(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    rule: &AlertRule,
+) -> Result<(), Error> {
+    let datasource = rule.datasource.as_deref();
+    let client = data
+        .datasources
+        .get(datasource)
+        .ok_or_else(|| format!("unknown datasource `{}`", datasource.unwrap_or_default()))?;
+    let samples = client
+        .fetch(&rule.query)
+        .await?
+        .samples()
+        .ok_or("the query has to return a vector or scalar")?;
+
+    let previous = alerts::states(&data.pool, rule.id).await?;
+    let transitions = transitions(rule, previous, samples, Utc::now().timestamp());
+
+    let fired_posted = transitions.fired.is_empty()
+        || post(ctx, data, guild_id, rule, &transitions.fired, true).await;
+    let resolved_posted = transitions.resolved.is_empty()
+        || post(ctx, data, guild_id, rule, &transitions.resolved, false).await;
+    save(
+        &data.pool,
+        rule.id,
+        &transitions,
+        fired_posted,
+        resolved_posted,
+    )
+    .await
+}
+
+/// Whether the post went out. It doesn't without an alerts channel.
+async fn post(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    rule: &AlertRule,
+    states: &[AlertState],
+    firing: bool,
+) -> bool {
+    match notify(ctx, data, guild_id, rule, states, firing).await {
+        Ok(posted) => posted,
+        Err(e) => {
+            error!(
+                "Failed to post alert `{}` for guild {}, retrying next time: {}",
+                rule.name, guild_id, e
+            );
+            false
+        }
+    }
+}
+
+async fn notify(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: serenity::GuildId,
+    rule: &AlertRule,
+    states: &[AlertState],
+    firing: bool,
+) -> Result<bool, Error> {
+    let settings = data.settings.read().await.get_guild_settings(guild_id);
+    let Some(channel) = settings.alerts_channel else {
+        return Ok(false);
+    };
+
+    let mut lines = states
+        .iter()
+        .take(MAX_LISTED_SERIES)
+        .map(|state| {
+            if firing {
+                format!("`{}` — {}", state.series, rule.format.format(state.value))
+            } else {
+                format!(
+                    "`{}` — firing since <t:{}:R>",
+                    state.series, state.active_since
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+    if states.len() > MAX_LISTED_SERIES {
+        lines.push(format!("…and {} more", states.len() - MAX_LISTED_SERIES));
+    }
+
+    let embed = CreateEmbed::new()
+        .description(truncate_description(lines.join("\n")))
+        .field("Query", truncate_field(format!("`{}`", rule.query)), false)
+        .field("Condition", describe_condition(rule), true)
+        .field("Severity", rule.severity.as_str(), true)
+        .timestamp(Utc::now());
+    let embed = if firing {
+        embed
+            .title(format!("{} {} is firing", rule.severity.emoji(), rule.name))
+            .color(rule.severity.color())
+    } else {
+        embed
+            .title(format!("✅ {} resolved", rule.name))
+            .color(Color::from_rgb(67, 160, 71))
+    };
+
+    let mut message = CreateMessage::new().embed(embed);
+    if firing {
+        if let Some(role) = settings.alert_roles.get(&rule.severity) {
+            message = message
+                .content(format!("<@&{}>", role))
+                .allowed_mentions(CreateAllowedMentions::new().roles(vec![*role]));
+        }
+    }
+    channel.send_message(&ctx.http, message).await?;
+
+    Ok(true)
+}
+
+#[async_trait]
+impl Task for AlertsTask {
+    async fn run(&self, ctx: &serenity::Context, data: Data) -> Result<(), Error> {
+        let mut interval = time::interval(EVAL_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+
+            let rules = match alerts::list_all(&data.pool).await {
+                Ok(rules) => rules,
+                Err(e) => {
+                    error!("Failed to load alert rules: {}", e);
+                    continue;
+                }
+            };
+            if !rules.is_empty() {
+                info!("Evaluating {} alert rules", rules.len());
+            }
+
+            for (guild_id, rule) in rules {
+                // A broken rule shouldn't hold up the others
+                if let Err(e) = evaluate(ctx, &data, guild_id, &rule).await {
+                    error!(
+                        "Failed to evaluate alert `{}` for guild {}: {}",
+                        rule.name, guild_id, e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::alerts::{AlertSeverity, Comparison};
+    use crate::db::stat_channels::StatFormat;
+    use poise::serenity_prelude::{GuildId, UserId};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn rule(for_secs: u64) -> AlertRule {
+        AlertRule {
+            id: 0,
+            name: "High load".to_string(),
+            query: "node_load1".to_string(),
+            comparison: Comparison::Above,
+            threshold: 2.0,
+            for_secs,
+            severity: AlertSeverity::Warning,
+            format: StatFormat::Count,
+            datasource: None,
+        }
+    }
+
+    fn sample(series: &str, value: f64) -> (String, f64) {
+        (series.to_string(), value)
+    }
+
+    /// Applies transitions the way a successful evaluation saves them.
+    fn settle(transitions: Transitions) -> Vec<AlertState> {
+        let mut states = transitions.ongoing;
+        states.extend(transitions.fired);
+        states
+    }
+
+    #[test]
+    fn pending_then_firing_then_resolved() {
+        let rule = rule(120);
+
+        let first = transitions(
+            &rule,
+            Vec::new(),
+            vec![sample("a", 3.0), sample("b", 1.0)],
+            0,
+        );
+        assert!(first.fired.is_empty());
+        assert_eq!(first.ongoing.len(), 1);
+        assert_eq!(first.ongoing[0].series, "a");
+        assert_eq!(first.ongoing[0].status, AlertStatus::Pending);
+
+        let second = transitions(&rule, settle(first), vec![sample("a", 4.0)], 60);
+        assert!(second.fired.is_empty());
+        assert_eq!(second.ongoing[0].active_since, 0);
+        assert_eq!(second.ongoing[0].value, 4.0);
+
+        let third = transitions(&rule, settle(second), vec![sample("a", 5.0)], 120);
+        assert_eq!(third.fired.len(), 1);
+        assert_eq!(third.fired[0].status, AlertStatus::Firing);
+        assert!(third.ongoing.is_empty());
+
+        // Firing series aren't posted again while they keep matching
+        let fourth = transitions(&rule, settle(third), vec![sample("a", 5.0)], 180);
+        assert!(fourth.fired.is_empty());
+        assert_eq!(fourth.ongoing[0].status, AlertStatus::Firing);
+
+        let fifth = transitions(&rule, settle(fourth), vec![sample("a", 1.0)], 240);
+        assert_eq!(fifth.resolved.len(), 1);
+        assert!(fifth.ongoing.is_empty() && fifth.dropped.is_empty());
+    }
+
+    #[test]
+    fn fires_right_away_without_a_duration() {
+        let fired = transitions(&rule(0), Vec::new(), vec![sample("a", 3.0)], 0).fired;
+        assert_eq!(fired.len(), 1);
+    }
+
+    #[test]
+    fn pending_series_that_stop_matching_are_dropped() {
+        let rule = rule(120);
+        let first = transitions(&rule, Vec::new(), vec![sample("a", 3.0)], 0);
+        // Gone from the query entirely
+        let second = transitions(&rule, settle(first), Vec::new(), 60);
+        assert_eq!(second.dropped.len(), 1);
+        assert!(second.resolved.is_empty());
+    }
+
+    async fn open(path: &std::path::Path) -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite://{}?mode=rwc", path.display()))
+            .await
+            .unwrap();
+        sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn state_survives_a_restart_and_unposted_alerts_are_retried() {
+        let path = std::env::temp_dir().join(format!("alerts-test-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let guild_id = GuildId::new(1);
+
+        let pool = open(&path).await;
+        alerts::add(&pool, guild_id, &rule(120), UserId::new(1))
+            .await
+            .unwrap();
+        let rule = alerts::get(&pool, guild_id, "High load")
+            .await
+            .unwrap()
+            .unwrap();
+        let first = transitions(&rule, Vec::new(), vec![sample("a", 3.0)], 0);
+        save(&pool, rule.id, &first, true, true).await.unwrap();
+        pool.close().await;
+
+        // After a restart the series is still pending since 0
+        let pool = open(&path).await;
+        let previous = alerts::states(&pool, rule.id).await.unwrap();
+        assert_eq!(previous.len(), 1);
+        assert_eq!(previous[0].active_since, 0);
+
+        // The post fails, so it stays pending and fires again next time
+        let second = transitions(&rule, previous, vec![sample("a", 3.0)], 120);
+        assert_eq!(second.fired.len(), 1);
+        save(&pool, rule.id, &second, false, true).await.unwrap();
+        let previous = alerts::states(&pool, rule.id).await.unwrap();
+        assert_eq!(previous[0].status, AlertStatus::Pending);
+
+        let third = transitions(&rule, previous, vec![sample("a", 3.0)], 180);
+        assert_eq!(third.fired.len(), 1);
+        save(&pool, rule.id, &third, true, true).await.unwrap();
+        let previous = alerts::states(&pool, rule.id).await.unwrap();
+        assert_eq!(previous[0].status, AlertStatus::Firing);
+
+        // Resolving also waits for the post to go out
+        let fourth = transitions(&rule, previous, Vec::new(), 240);
+        save(&pool, rule.id, &fourth, true, false).await.unwrap();
+        let previous = alerts::states(&pool, rule.id).await.unwrap();
+        assert_eq!(previous[0].status, AlertStatus::Firing);
+
+        let fifth = transitions(&rule, previous, Vec::new(), 300);
+        assert_eq!(fifth.resolved.len(), 1);
+        save(&pool, rule.id, &fifth, true, true).await.unwrap();
+        assert!(alerts::states(&pool, rule.id).await.unwrap().is_empty());
+
+        pool.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn editing_a_rule_resolves_what_no_longer_matches() {
+        let path =
+            std::env::temp_dir().join(format!("alerts-edit-test-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let guild_id = GuildId::new(1);
+
+        let pool = open(&path).await;
+        alerts::add(&pool, guild_id, &rule(0), UserId::new(1))
+            .await
+            .unwrap();
+        let mut rule = alerts::get(&pool, guild_id, "High load")
+            .await
+            .unwrap()
+            .unwrap();
+        rule.for_secs = 120;
+        let first = transitions(&rule, Vec::new(), vec![sample("a", 3.0)], 0);
+        let second = transitions(
+            &rule,
+            settle(first),
+            vec![sample("a", 3.0), sample("b", 3.0)],
+            120,
+        );
+        save(&pool, rule.id, &second, true, true).await.unwrap();
+
+        // `a` is firing and `b` pending when the threshold goes up
+        rule.threshold = 5.0;
+        alerts::update(&pool, &rule).await.unwrap();
+        let previous = alerts::states(&pool, rule.id).await.unwrap();
+        assert_eq!(previous.len(), 1);
+        assert_eq!(previous[0].series, "a");
+
+        let third = transitions(
+            &rule,
+            previous,
+            vec![sample("a", 3.0), sample("b", 3.0)],
+            180,
+        );
+        assert_eq!(third.resolved.len(), 1);
+        assert!(third.ongoing.is_empty() && third.fired.is_empty());
+
+        pool.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,8 +1,15 @@
+use crate::commands::lorax::OpenOutcome;
+use crate::db::lorax_schedule;
 use crate::settings::LoraxState;
 use crate::{Data, Error};
 use async_trait::async_trait;
 use poise::serenity_prelude as serenity;
-use tracing::{error, info};
+use tracing::{debug, error, info};
+
+// A scheduled event that fails to open is retried after this, doubling with
+// every failure up to the max
+const RETRY_BASE_SECS: i64 = 60;
+const RETRY_MAX_SECS: i64 = 6 * 60 * 60;
 
 pub struct LoraxSchedulerTask {
     interval: std::time::Duration,
@@ -23,15 +30,35 @@ impl crate::tasks::Task for LoraxSchedulerTask {
         ctx: &serenity::Context,
         data: Data,
     ) -> Result<(), Error> {
+        // Announcements can be out of date after a restart, and ones posted
+        // before events were renumbered have Vote buttons with the old IDs
+        for (guild_id, event_id) in running_events(&data).await {
+            crate::commands::lorax::refresh_announcement(&ctx.http, &data, guild_id, event_id)
+                .await;
+        }
+
         loop {
-            let guild_ids: Vec<_> = {
-                let settings = data.settings.read().await;
-                settings.guilds.keys().cloned().collect()
-            };
-
-            for guild_id in guild_ids {
-                if let Err(e) = process_guild_lorax_event(ctx, &data, guild_id).await {
-                    error!("Error processing Lorax event for guild {}: {}", guild_id, e);
+            if let Err(e) = process_scheduled_events(ctx, &data).await {
+                error!("Error processing scheduled Lorax events: {}", e);
+            }
+
+            // Every running event moves along on its own clock
+            for (guild_id, event_id) in running_events(&data).await {
+                if let Err(e) = crate::commands::lorax::send_due_reminders(
+                    &ctx.http, &data, guild_id, event_id,
+                )
+                .await
+                {
+                    error!(
+                        "Error sending Lorax reminders for event {} in guild {}: {}",
+                        event_id, guild_id, e
+                    );
+                }
+                if let Err(e) = process_lorax_event(ctx, &data, guild_id, event_id).await {
+                    error!(
+                        "Error processing Lorax event {} for guild {}: {}",
+                        event_id, guild_id, e
+                    );
                 }
             }
 
@@ -41,34 +68,120 @@ impl crate::tasks::Task for LoraxSchedulerTask {
     }
 }
 
-async fn process_guild_lorax_event(
+async fn running_events(data: &Data) -> Vec<(serenity::GuildId, u64)> {
+    let settings = data.settings.read().await;
+    settings
+        .guilds
+        .iter()
+        .flat_map(|(guild_id, guild)| {
+            guild
+                .lorax_events
+                .keys()
+                .map(move |event_id| (*guild_id, *event_id))
+        })
+        .collect()
+}
+
+/// Sends teasers for upcoming scheduled events and opens the ones that are due.
+async fn process_scheduled_events(ctx: &serenity::Context, data: &Data) -> Result<(), Error> {
+    let now = chrono::Utc::now().timestamp();
+
+    for mut event in lorax_schedule::due_teasers(&data.pool, now).await? {
+        info!("Sending teaser for scheduled Lorax event {}", event.id);
+        if let Err(e) =
+            crate::commands::lorax::announce_scheduled_event(&ctx.http, data, &event).await
+        {
+            error!("Failed to send teaser for scheduled Lorax event {}: {}", event.id, e);
+        }
+        // Don't retry the teaser every minute if the channel is gone
+        event.teaser_sent = true;
+        lorax_schedule::update(&data.pool, &event).await?;
+    }
+
+    for mut event in lorax_schedule::due(&data.pool, now).await? {
+        let outcome = crate::commands::lorax::open_submissions(
+            &ctx.http,
+            data,
+            event.guild_id,
+            event.location.clone(),
+            &event.config,
+        )
+        .await;
+
+        match outcome {
+            Ok(OpenOutcome::Opened(event_id)) => {
+                info!(
+                    "Opened scheduled Lorax event {} as event {} for guild {}",
+                    event.id, event_id, event.guild_id
+                );
+                lorax_schedule::delete(&data.pool, event.guild_id, event.id).await?;
+            }
+            // Stay queued until the location's current event is over
+            Ok(OpenOutcome::AlreadyRunning | OpenOutcome::NotConfigured) => {
+                debug!("Scheduled Lorax event {} is waiting to open", event.id);
+            }
+            // Missing permissions or a deleted channel won't fix themselves
+            // within a minute, so wait longer every time
+            Err(e) => {
+                event.failures += 1;
+                let delay = retry_delay(event.failures);
+                event.retry_at = Some(now + delay);
+                event.last_error = Some(e.to_string());
+                error!(
+                    "Failed to open scheduled Lorax event {} (attempt {}), retrying in {}s: {}",
+                    event.id, event.failures, delay, e
+                );
+                lorax_schedule::update(&data.pool, &event).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn retry_delay(failures: u32) -> i64 {
+    let doublings = failures.saturating_sub(1).min(16);
+    (RETRY_BASE_SECS << doublings).min(RETRY_MAX_SECS)
+}
+
+async fn process_lorax_event(
     ctx: &serenity::Context,
     data: &Data,
     guild_id: serenity::GuildId,
+    event_id: u64,
 ) -> Result<(), Error> {
-    let mut settings = data.settings.write().await;
-    let guild_settings = settings.guilds.get_mut(&guild_id).unwrap();
+    let settings = data.settings.read().await;
+    let Some(state) = settings
+        .guilds
+        .get(&guild_id)
+        .and_then(|guild| guild.lorax_event(event_id))
+    else {
+        return Ok(());
+    };
+    let now = chrono::Utc::now().timestamp();
 
-    match &guild_settings.lorax_state {
+    match state {
         LoraxState::Submissions { end_time, .. } => {
-            if chrono::Utc::now().timestamp() >= *end_time {
-                info!("Submission phase ended for guild {}", guild_id);
+            if now >= *end_time {
+                info!("Submission phase of event {} ended for guild {}", event_id, guild_id);
                 drop(settings);
-                crate::commands::lorax::start_voting(ctx, data, guild_id).await?;
+                crate::commands::lorax::start_voting(ctx, data, guild_id, event_id).await?;
             }
         }
         LoraxState::Voting { end_time, .. } => {
-            if chrono::Utc::now().timestamp() >= *end_time {
-                info!("Voting phase ended for guild {}", guild_id);
+            if now >= *end_time {
+                info!("Voting phase of event {} ended for guild {}", event_id, guild_id);
                 drop(settings);
-                crate::commands::lorax::announce_winner(&ctx.http, data, guild_id).await?;
+                crate::commands::lorax::announce_winner(&ctx.http, data, guild_id, event_id)
+                    .await?;
             }
         }
         LoraxState::TieBreaker { end_time, .. } => {
-            if chrono::Utc::now().timestamp() >= *end_time {
-                info!("Tiebreaker round ended for guild {}", guild_id);
+            if now >= *end_time {
+                info!("Tiebreaker round of event {} ended for guild {}", event_id, guild_id);
                 drop(settings);
-                crate::commands::lorax::announce_winner(&ctx.http, data, guild_id).await?;
+                crate::commands::lorax::announce_winner(&ctx.http, data, guild_id, event_id)
+                    .await?;
             }
         }
         LoraxState::Idle => {
@@ -77,4 +190,19 @@ async fn process_guild_lorax_event(
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_back_off_up_to_the_max() {
+        assert_eq!(retry_delay(1), 60);
+        assert_eq!(retry_delay(2), 120);
+        assert_eq!(retry_delay(5), 960);
+        assert_eq!(retry_delay(9), 15_360);
+        assert_eq!(retry_delay(10), RETRY_MAX_SECS);
+        assert_eq!(retry_delay(u32::MAX), RETRY_MAX_SECS);
+    }
+}
@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use poise::serenity_prelude as serenity;
 use std::sync::Arc;
 
+pub mod alerts;
 pub mod lorax_scheduler;
 pub mod server_deletion;
 pub mod stats_updater;
@@ -1,11 +1,15 @@
 use poise::serenity_prelude as serenity;
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 use tokio::time::{self, MissedTickBehavior};
 use tracing::{error, info};
 use async_trait::async_trait;
 
 use crate::{
-    metrics::{Metric, MetricsClient, METRICS},
+    db::stat_channels::{self, StatChannel},
+    metrics::Datasources,
     Data, Error,
 };
 
@@ -17,14 +21,12 @@ const RATE_LIMIT_DELAY: Duration = Duration::from_secs(1);
 #[derive(Debug, Clone)]
 pub struct ChannelUpdater {
     previous_names: HashMap<serenity::ChannelId, String>,
-    metrics_client: MetricsClient,
 }
 
 impl ChannelUpdater {
     fn new() -> Self {
         Self {
             previous_names: HashMap::new(),
-            metrics_client: MetricsClient::new(),
         }
     }
 
@@ -47,40 +49,42 @@ impl ChannelUpdater {
         Ok(())
     }
 
-    async fn update_metric(
+    async fn update_stat(
         &mut self,
         ctx: &serenity::Context,
-        channel_id: Option<serenity::ChannelId>,
-        metric: &Metric,
+        datasources: &Datasources,
+        stat: &StatChannel,
     ) -> std::result::Result<(), Error> {
-        if let Some(channel) = channel_id {
-            let value = self.metrics_client.fetch_metric(metric.query).await?;
-            let name = metric.format_value(value);
-            self.update_if_changed(ctx, channel, name).await?;
-            time::sleep(Duration::from_millis(500)).await;
-        }
+        let datasource = stat.datasource.as_deref();
+        let client = datasources
+            .get(datasource)
+            .ok_or_else(|| format!("unknown datasource `{}`", datasource.unwrap_or_default()))?;
+        let value = client.fetch_metric(&stat.query).await?;
+        self.update_if_changed(ctx, stat.channel_id, stat.channel_name(value))
+            .await?;
+        time::sleep(Duration::from_millis(500)).await;
         Ok(())
     }
 
-    async fn update_guild_metrics(
+    async fn update_guild_stats(
         &mut self,
         ctx: &serenity::Context,
+        datasources: &Datasources,
         guild_id: serenity::GuildId,
-        channels: &[Option<serenity::ChannelId>],
-    ) -> std::result::Result<(), Error> {
+        stats: &[StatChannel],
+    ) {
         info!("Updating stats for guild {}", guild_id);
 
-        for (metric, &channel) in METRICS.iter().zip(channels.iter()) {
+        for stat in stats {
             time::sleep(RATE_LIMIT_DELAY).await;
-            if let Err(e) = self.update_metric(ctx, channel, metric).await {
+            // One broken query or deleted channel shouldn't stall the rest
+            if let Err(e) = self.update_stat(ctx, datasources, stat).await {
                 error!(
-                    "Failed to update {} metric for guild {}: {}",
-                    metric.name, guild_id, e
+                    "Failed to update stat channel {} for guild {}: {}",
+                    stat.channel_id, guild_id, e
                 );
-                return Err(e);
             }
         }
-        Ok(())
     }
 }
 
@@ -108,20 +112,23 @@ impl Task for StatsUpdaterTask {
             interval.tick().await;
             info!("Starting channel update cycle");
 
-            let guild_settings = data.settings.read().await.guilds.clone();
+            let stats = match stat_channels::list_all(&data.pool).await {
+                Ok(stats) => stats,
+                Err(e) => {
+                    error!("Failed to load stat channels: {}", e);
+                    continue;
+                }
+            };
 
-            for (guild_id, settings) in guild_settings {
-                let channels = [
-                    settings.nodes_channel,
-                    settings.network_channel,
-                    settings.network_total_channel,
-                    settings.storage_channel,
-                    settings.memory_channel,
-                ];
+            let mut by_guild: BTreeMap<serenity::GuildId, Vec<StatChannel>> = BTreeMap::new();
+            for (guild_id, stat) in stats {
+                by_guild.entry(guild_id).or_default().push(stat);
+            }
 
-                if let Err(e) = updater.update_guild_metrics(ctx, guild_id, &channels).await {
-                    tracing::error!("Failed to update metrics: {}", e);
-                }
+            for (guild_id, stats) in by_guild {
+                updater
+                    .update_guild_stats(ctx, &data.datasources, guild_id, &stats)
+                    .await;
             }
         }
     }
@@ -0,0 +1,60 @@
+/// Shortens `value` to at most `max_len` characters, ending it with `…` when
+/// anything was cut.
+pub fn truncate(value: String, max_len: usize) -> String {
+    if value.chars().count() <= max_len {
+        return value;
+    }
+    let mut truncated: String = value.chars().take(max_len - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Embed descriptions are capped at 4096 characters by Discord.
+pub const MAX_EMBED_DESCRIPTION: usize = 4096;
+
+/// Embed field values are capped at 1024 characters by Discord.
+pub const MAX_EMBED_FIELD: usize = 1024;
+
+pub fn truncate_description(value: String) -> String {
+    truncate(value, MAX_EMBED_DESCRIPTION)
+}
+
+pub fn truncate_field(value: String) -> String {
+    truncate(value, MAX_EMBED_FIELD)
+}
+
+/// Joins fields into one CSV line, quoting the ones that need it.
+pub fn csv_row<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    let mut row = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_row_quotes_only_when_needed() {
+        assert_eq!(csv_row(["oak", "12"]), "oak,12\r\n");
+        assert_eq!(csv_row(["oak, ash", ""]), "\"oak, ash\",\r\n");
+        assert_eq!(csv_row(["say \"hi\""]), "\"say \"\"hi\"\"\"\r\n");
+        assert_eq!(csv_row(["two\nlines"]), "\"two\nlines\"\r\n");
+    }
+
+    #[test]
+    fn truncate_counts_characters() {
+        assert_eq!(truncate("oak".to_string(), 3), "oak");
+        assert_eq!(truncate("🌳🌳🌳🌳".to_string(), 3), "🌳🌳…");
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+/// Counts single-choice votes. Options nobody voted for are left out.
+pub fn plurality(votes: impl IntoIterator<Item = usize>) -> HashMap<usize, usize> {
+    let mut counts = HashMap::new();
+    for choice in votes {
+        *counts.entry(choice).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Counts approval ballots, every option on a ballot gets a vote. Options
+/// nobody approved of are left out.
+pub fn approval<'a>(ballots: impl IntoIterator<Item = &'a Vec<usize>>) -> HashMap<usize, usize> {
+    plurality(ballots.into_iter().flatten().copied())
+}
+
+#[derive(Debug, Clone)]
+pub struct RunoffRound {
+    /// First preferences among the options still standing, most votes first.
+    pub counts: Vec<(usize, usize)>,
+    pub eliminated: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunoffResult {
+    pub rounds: Vec<RunoffRound>,
+    /// Options left standing at the end. This is a single winner unless the
+    /// remaining options were dead even and IRV couldn't separate them.
+    pub remaining: Vec<usize>,
+    /// What each option finished on: its tally in the round it was eliminated,
+    /// or in the last round if it survived.
+    pub final_counts: HashMap<usize, usize>,
+}
+
+/// Runs an instant-runoff count over ranked ballots.
+///
+/// Every round each ballot counts for its highest ranked option that's still
+/// standing. An option with a strict majority of those votes wins, otherwise
+/// everything tied for last place is eliminated together. When every option
+/// left is tied there's nothing to eliminate and they're all returned in
+/// `remaining`.
+pub fn instant_runoff<'a>(
+    option_count: usize,
+    ballots: impl Iterator<Item = &'a Vec<usize>> + Clone,
+) -> RunoffResult {
+    let mut remaining: Vec<usize> = (0..option_count).collect();
+    let mut rounds = Vec::new();
+    let mut final_counts = HashMap::new();
+
+    while !remaining.is_empty() {
+        let mut counts: HashMap<usize, usize> = remaining.iter().map(|&o| (o, 0)).collect();
+        for ballot in ballots.clone() {
+            if let Some(choice) = ballot.iter().find(|o| counts.contains_key(o)) {
+                *counts.get_mut(choice).unwrap() += 1;
+            }
+        }
+
+        let mut counts: Vec<(usize, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        final_counts.extend(counts.iter().copied());
+
+        let total: usize = counts.iter().map(|(_, votes)| votes).sum();
+        let (leader, leader_votes) = counts[0];
+        if remaining.len() == 1 || leader_votes * 2 > total {
+            remaining = vec![leader];
+            rounds.push(RunoffRound {
+                counts,
+                eliminated: Vec::new(),
+            });
+            break;
+        }
+
+        let fewest = counts.last().map(|(_, votes)| *votes).unwrap_or(0);
+        let eliminated: Vec<usize> = counts
+            .iter()
+            .filter(|(_, votes)| *votes == fewest)
+            .map(|(option, _)| *option)
+            .collect();
+
+        if eliminated.len() == remaining.len() {
+            rounds.push(RunoffRound {
+                counts,
+                eliminated: Vec::new(),
+            });
+            break;
+        }
+
+        remaining.retain(|o| !eliminated.contains(o));
+        rounds.push(RunoffRound { counts, eliminated });
+    }
+
+    RunoffResult {
+        rounds,
+        remaining,
+        final_counts,
+    }
+}
+
+/// Picks one of `names` for a random tiebreak draw and returns its index.
+///
+/// The draw is reproducible from the seed so it can be audited: sort the
+/// names alphabetically and the winner is the one at `seed % names.len()`.
+pub fn draw(names: &[&str], seed: u64) -> usize {
+    let mut sorted: Vec<usize> = (0..names.len()).collect();
+    sorted.sort_by_key(|&i| names[i]);
+    sorted[(seed % names.len() as u64) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ballots(groups: &[(&[usize], usize)]) -> Vec<Vec<usize>> {
+        groups
+            .iter()
+            .flat_map(|(ballot, count)| std::iter::repeat(ballot.to_vec()).take(*count))
+            .collect()
+    }
+
+    #[test]
+    fn majority_wins_in_the_first_round() {
+        let ballots = ballots(&[(&[0, 1], 3), (&[1, 0], 2)]);
+        let result = instant_runoff(2, ballots.iter());
+        assert_eq!(result.remaining, vec![0]);
+        assert_eq!(result.rounds.len(), 1);
+        assert_eq!(result.rounds[0].counts, vec![(0, 3), (1, 2)]);
+    }
+
+    #[test]
+    fn eliminates_the_last_place_each_round() {
+        let ballots = ballots(&[(&[0], 5), (&[1, 2], 4), (&[2, 1], 3), (&[3, 2], 2)]);
+        let result = instant_runoff(4, ballots.iter());
+
+        let eliminated: Vec<_> = result.rounds.iter().map(|r| r.eliminated.clone()).collect();
+        assert_eq!(eliminated, vec![vec![3], vec![1], vec![]]);
+        assert_eq!(result.rounds[1].counts, vec![(0, 5), (2, 5), (1, 4)]);
+        assert_eq!(result.remaining, vec![2]);
+        assert_eq!(result.final_counts[&3], 2);
+        assert_eq!(result.final_counts[&1], 4);
+        assert_eq!(result.final_counts[&2], 9);
+    }
+
+    #[test]
+    fn options_tied_for_last_are_eliminated_together() {
+        let ballots = ballots(&[(&[0], 3), (&[1, 0], 1), (&[2, 3], 1), (&[3], 2)]);
+        let result = instant_runoff(4, ballots.iter());
+        assert_eq!(result.rounds[0].eliminated, vec![1, 2]);
+        assert_eq!(result.rounds[1].counts, vec![(0, 4), (3, 3)]);
+        assert_eq!(result.remaining, vec![0]);
+    }
+
+    #[test]
+    fn exhausted_ballots_leave_the_count() {
+        let ballots = ballots(&[(&[0], 4), (&[1], 3), (&[2], 2)]);
+        let result = instant_runoff(3, ballots.iter());
+        assert_eq!(result.rounds[0].eliminated, vec![2]);
+        // 4 of the 7 ballots still counting is a majority
+        let total: usize = result.rounds[1].counts.iter().map(|(_, v)| v).sum();
+        assert_eq!(total, 7);
+        assert_eq!(result.remaining, vec![0]);
+    }
+
+    #[test]
+    fn dead_even_options_all_remain() {
+        let ballots = ballots(&[(&[0, 1], 2), (&[1, 0], 2)]);
+        let result = instant_runoff(2, ballots.iter());
+        assert_eq!(result.remaining, vec![0, 1]);
+        assert!(result.rounds[0].eliminated.is_empty());
+    }
+
+    #[test]
+    fn options_nobody_ranked_go_first() {
+        let ballots = ballots(&[(&[0], 2), (&[1], 1)]);
+        let result = instant_runoff(3, ballots.iter());
+        assert_eq!(result.rounds[0].counts, vec![(0, 2), (1, 1), (2, 0)]);
+        assert_eq!(result.remaining, vec![0]);
+    }
+
+    #[test]
+    fn approval_counts_every_option_on_a_ballot() {
+        let ballots = ballots(&[(&[0, 1], 2), (&[1], 1), (&[], 1)]);
+        let counts = approval(ballots.iter());
+        assert_eq!(counts[&0], 2);
+        assert_eq!(counts[&1], 3);
+        assert!(!counts.contains_key(&2));
+    }
+
+    #[test]
+    fn draw_goes_by_alphabetical_order_and_seed() {
+        let names = ["oak", "ash", "elm"];
+        assert_eq!(draw(&names, 0), 1);
+        assert_eq!(draw(&names, 1), 2);
+        assert_eq!(draw(&names, 2), 0);
+        assert_eq!(draw(&names, 3), 1);
+    }
+
+    #[test]
+    fn draw_is_the_same_whatever_order_names_come_in() {
+        let seed = 0x5eed_1234_abcd;
+        let names = ["oak", "ash", "elm", "fir"];
+        let shuffled = ["fir", "elm", "oak", "ash"];
+        assert_eq!(names[draw(&names, seed)], shuffled[draw(&shuffled, seed)]);
+        assert_eq!(draw(&names, seed), draw(&names, seed));
+    }
+}
@@ -0,0 +1,11 @@
+-- kept so exports of past events can say what each name means
+alter table lorax_event_submissions add column meaning text;
+
+-- every ballot cast in an archived round, without who cast it. choices is a
+-- JSON list of names, in order of preference for ranked votes
+create table if not exists lorax_event_ballots
+(
+    event_id                integer not null references lorax_events (id) on delete cascade,
+    round                   integer not null,
+    choices                 text not null
+);
@@ -0,0 +1,21 @@
+-- nodes we know about, whether or not they're reporting metrics yet
+create table if not exists nodes
+(
+    id                      integer primary key autoincrement,
+    guild_id                integer not null,
+    name                    text not null,
+    location                text not null,
+    -- the archived Lorax event that named it, if any
+    event_id                integer references lorax_events (id) on delete set null,
+    -- one of 'planned', 'live', 'retired'
+    status                  text not null default 'planned',
+    added_at                integer not null,
+    updated_at              integer not null,
+    unique (guild_id, name)
+);
+
+-- past winners are planned nodes until someone says otherwise
+insert or ignore into nodes (guild_id, name, location, event_id, status, added_at, updated_at)
+select guild_id, winner, location, id, 'planned', ended_at, ended_at
+from lorax_events
+where outcome = 'winner' and winner is not null;
@@ -0,0 +1,10 @@
+-- named event setups, config is a JSON LoraxEventConfig
+create table if not exists lorax_templates
+(
+    guild_id                integer not null,
+    name                    text not null,
+    config                  text not null,
+    updated_by              integer not null,
+    updated_at              integer not null,
+    primary key (guild_id, name)
+);
@@ -0,0 +1,5 @@
+-- language of the built-in announcement copy
+alter table guilds add column lorax_locale text not null default 'en';
+
+-- the guild's own announcement copy, a JSON object keyed by message
+alter table guilds add column lorax_messages text;
@@ -0,0 +1,2 @@
+-- forum to open a campaign post per name in
+alter table guilds add column lorax_campaign_forum integer;
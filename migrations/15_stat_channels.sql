@@ -0,0 +1,48 @@
+-- channels whose name shows the latest value of a PromQL query
+create table if not exists stat_channels
+(
+    id                      integer primary key autoincrement,
+    guild_id                integer not null,
+    channel_id              integer not null unique,
+    query                   text not null,
+    -- channel name, with `{value}` replaced by the formatted value
+    label                   text not null,
+    -- one of 'bytes', 'rate', 'count', 'percent'
+    format                  text not null,
+    created_at              integer not null
+);
+
+-- carry over the channels that used to be fixed columns
+insert or ignore into stat_channels (guild_id, channel_id, query, label, format, created_at)
+select id, nodes_channel, 'count(up{job="node"} == 1)', '🖥️ Active Nodes {value}', 'count', unixepoch()
+from guilds where nodes_channel is not null;
+
+insert or ignore into stat_channels (guild_id, channel_id, query, label, format, created_at)
+select id, network_channel,
+    'sum(rate(node_network_receive_bytes_total[5m]) + rate(node_network_transmit_bytes_total[5m])) or vector(0)',
+    '🌐 Net {value}', 'rate', unixepoch()
+from guilds where network_channel is not null;
+
+insert or ignore into stat_channels (guild_id, channel_id, query, label, format, created_at)
+select id, network_total_channel,
+    'sum(increase(node_network_receive_bytes_total[7d]) + increase(node_network_transmit_bytes_total[7d])) or vector(0)',
+    '📊 7d Total {value}', 'bytes', unixepoch()
+from guilds where network_total_channel is not null;
+
+insert or ignore into stat_channels (guild_id, channel_id, query, label, format, created_at)
+select id, storage_channel,
+    'sum(node_filesystem_size_bytes{mountpoint="/"} - node_filesystem_free_bytes{mountpoint="/"})',
+    '💾 Disk {value}', 'bytes', unixepoch()
+from guilds where storage_channel is not null;
+
+insert or ignore into stat_channels (guild_id, channel_id, query, label, format, created_at)
+select id, memory_channel,
+    'sum(node_memory_MemTotal_bytes - node_memory_MemAvailable_bytes) or vector(0)',
+    '🧠 Mem {value}', 'bytes', unixepoch()
+from guilds where memory_channel is not null;
+
+alter table guilds drop column nodes_channel;
+alter table guilds drop column network_channel;
+alter table guilds drop column network_total_channel;
+alter table guilds drop column storage_channel;
+alter table guilds drop column memory_channel;
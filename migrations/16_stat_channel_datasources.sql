@@ -0,0 +1,2 @@
+-- the named datasource to query, null for the default one
+alter table stat_channels add column datasource text;
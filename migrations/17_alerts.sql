@@ -0,0 +1,41 @@
+-- PromQL threshold rules evaluated by the alerts task
+create table if not exists alert_rules
+(
+    id                      integer primary key autoincrement,
+    guild_id                integer not null,
+    name                    text not null,
+    query                   text not null,
+    -- one of '>', '>=', '<', '<=', '==', '!='
+    comparison              text not null,
+    threshold               real not null,
+    -- how long a series has to match before the alert fires
+    for_secs                integer not null default 0,
+    -- one of 'info', 'warning', 'critical'
+    severity                text not null,
+    -- one of 'bytes', 'rate', 'count', 'percent', for showing values
+    format                  text not null default 'count',
+    -- the named datasource to query, null for the default one
+    datasource              text,
+    created_by              integer not null,
+    created_at              integer not null,
+    unique (guild_id, name)
+);
+
+-- series that currently match a rule, so firing alerts survive a restart
+create table if not exists alert_states
+(
+    rule_id                 integer not null references alert_rules (id) on delete cascade,
+    -- the series as `name{label="value", ...}`
+    series                  text not null,
+    -- one of 'pending', 'firing'
+    state                   text not null,
+    active_since            integer not null,
+    value                   real not null,
+    primary key (rule_id, series)
+);
+
+-- where firing and resolved alerts are posted
+alter table guilds add column alerts_channel integer;
+
+-- roles to ping per severity, a JSON object keyed by severity
+alter table guilds add column alert_roles text;
@@ -0,0 +1,10 @@
+-- migration 7 only copied the old hard-coded names into guilds that existed
+-- back then, so seed them for every guild that joins from now on too. They
+-- can still be unreserved like any other name.
+create trigger if not exists guilds_seed_reserved_names
+    after insert on guilds
+begin
+    insert or ignore into lorax_names (guild_id, name, kind, added_at)
+    select new.id, reserved.column1, 'reserved', cast(strftime('%s', 'now') as integer)
+    from (values ('sakura'), ('cherry'), ('bamboo'), ('maple'), ('pine'), ('palm'), ('cedar')) as reserved;
+end;
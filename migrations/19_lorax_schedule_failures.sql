@@ -0,0 +1,4 @@
+-- scheduled events that failed to open are retried with a growing delay
+alter table lorax_schedule add column failures integer not null default 0;
+alter table lorax_schedule add column retry_at integer;
+alter table lorax_schedule add column last_error text;
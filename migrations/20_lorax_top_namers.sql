@@ -0,0 +1,8 @@
+-- members currently holding a guild's Lorax winner role, which goes to the
+-- top namers on the leaderboard
+create table if not exists lorax_top_namers
+(
+    guild_id                integer not null,
+    user_id                 integer not null,
+    primary key (guild_id, user_id)
+);
@@ -0,0 +1,33 @@
+-- events get their lorax_events row when submissions open, so running and
+-- finished events share one ID. outcome is 'running' until the event ends,
+-- and ended_at is the start time until then
+create temporary table running_lorax_events as
+select g.id as guild_id,
+       e.value as state,
+       (select value from json_each(e.value)) as phase,
+       (select coalesce(max(id), 0) from lorax_events)
+           + row_number() over (order by g.id, cast(e.key as integer)) as new_id
+from guilds g, json_each(g.lorax_events) e
+where g.lorax_events is not null and json_type(e.value) = 'object';
+
+insert into lorax_events (id, guild_id, location, outcome, started_at, voting_started_at, ended_at)
+select new_id,
+       guild_id,
+       coalesce(json_extract(phase, '$.location'), ''),
+       'running',
+       coalesce(json_extract(phase, '$.timeline.started_at'), 0),
+       json_extract(phase, '$.timeline.voting_started_at'),
+       coalesce(json_extract(phase, '$.timeline.started_at'), 0)
+from running_lorax_events;
+
+update guilds
+set lorax_events = (
+    select json_group_object(cast(r.new_id as text), json(r.state))
+    from running_lorax_events r
+    where r.guild_id = guilds.id
+)
+where id in (select guild_id from running_lorax_events);
+
+drop table running_lorax_events;
+
+alter table guilds drop column lorax_next_event_id;
@@ -0,0 +1,32 @@
+create table if not exists lorax_events
+(
+    id                      integer primary key autoincrement,
+    guild_id                integer not null,
+    location                text not null,
+    -- one of 'winner', 'no_submissions', 'no_winner', 'cancelled'
+    outcome                 text not null,
+    winner                  text,
+    winner_submitter        integer,
+    started_at              integer not null,
+    voting_started_at       integer,
+    ended_at                integer not null
+);
+
+create index if not exists lorax_events_guild on lorax_events (guild_id, ended_at);
+
+create table if not exists lorax_event_submissions
+(
+    event_id                integer not null references lorax_events (id) on delete cascade,
+    user_id                 integer not null,
+    name                    text not null
+);
+
+-- round 0 is the main vote, every round after that is a tiebreaker
+create table if not exists lorax_event_tallies
+(
+    event_id                integer not null references lorax_events (id) on delete cascade,
+    round                   integer not null,
+    ended_at                integer not null,
+    name                    text not null,
+    votes                   integer not null
+);
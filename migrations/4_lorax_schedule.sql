@@ -0,0 +1,12 @@
+create table if not exists lorax_schedule
+(
+    id                      integer primary key autoincrement,
+    guild_id                integer not null,
+    location                text not null,
+    start_time              integer not null,
+    teaser_sent             boolean not null default false,
+    -- store the phase durations and voting options as JSON
+    config                  text not null
+);
+
+create index if not exists lorax_schedule_start on lorax_schedule (start_time);
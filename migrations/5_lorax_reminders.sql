@@ -0,0 +1,2 @@
+-- minutes before a phase ends to send reminders, as a JSON array
+alter table guilds add column lorax_reminders text;
@@ -0,0 +1,2 @@
+-- who may submit and vote in Lorax events, as JSON
+alter table guilds add column lorax_eligibility text;
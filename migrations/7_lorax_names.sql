@@ -0,0 +1,16 @@
+create table if not exists lorax_names
+(
+    guild_id                integer not null,
+    name                    text not null,
+    -- 'reserved' matches the whole name, 'blocked' matches anywhere in it
+    kind                    text not null,
+    added_by                integer,
+    added_at                integer not null,
+    primary key (guild_id, name, kind)
+);
+
+-- these used to be hard-coded for every guild
+insert or ignore into lorax_names (guild_id, name, kind, added_at)
+select guilds.id, reserved.column1, 'reserved', cast(strftime('%s', 'now') as integer)
+from guilds,
+     (values ('sakura'), ('cherry'), ('bamboo'), ('maple'), ('pine'), ('palm'), ('cedar')) as reserved;
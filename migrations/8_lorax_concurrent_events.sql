@@ -0,0 +1,11 @@
+-- running Lorax events as a JSON object keyed by event ID, so a guild can
+-- name several nodes at once
+alter table guilds add column lorax_events text;
+alter table guilds add column lorax_next_event_id integer not null default 1;
+
+update guilds
+set lorax_events = json_object('1', json(lorax_state)),
+    lorax_next_event_id = 2
+where lorax_state is not null and lorax_state != '"Idle"';
+
+alter table guilds drop column lorax_state;
@@ -0,0 +1,21 @@
+-- who cast a ballot in each archived event, in any round
+create table if not exists lorax_event_voters
+(
+    event_id                integer not null references lorax_events (id) on delete cascade,
+    user_id                 integer not null,
+    primary key (event_id, user_id)
+);
+
+-- where each name finished, 1 is the winner. Names knocked out before the
+-- final round are left null
+alter table lorax_event_submissions add column placement integer;
+
+update lorax_event_submissions
+set placement = 1
+where exists (
+    select 1 from lorax_events e
+    where e.id = lorax_event_submissions.event_id and e.winner = lorax_event_submissions.name
+);
+
+-- given to members whose name wins an event
+alter table guilds add column lorax_winner_role integer;